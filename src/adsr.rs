@@ -0,0 +1,260 @@
+use crate::easing::exp2;
+use crate::easing::from_q62;
+use crate::easing::to_q62;
+use crate::Poui;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+use std::marker::PhantomData;
+
+/// One in Q62.
+const ONE: i128 = 1 << 62;
+
+/// How far past full scale the attack aims, 0.3, in Q62. Aiming past the
+/// end lets the exponential actually get there.
+const ATTACK_OVERSHOOT: i128 = 1383505805528216371;
+
+/// `log2((1 + 0.3) / 0.3)` in Q62, the number of halvings of the distance to
+/// the attack's aim over the whole attack.
+const ATTACK_HALVINGS: i128 = 9755916705877194162;
+
+/// How far past their end the decay and release aim, 0.0001, in Q62.
+const DECAY_OVERSHOOT: i128 = 461168601842739;
+
+/// `log2((1 + 0.0001) / 0.0001)` in Q62.
+const DECAY_HALVINGS: i128 = 61279422690043539499;
+
+/// The stage an [`Adsr`] envelope is in.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum AdsrStage {
+    /// The gate is off and the envelope has finished releasing.
+    #[default]
+    Idle,
+    /// The gate is on and the level is rising to full scale.
+    Attack,
+    /// The level is falling from full scale to the sustain level.
+    Decay,
+    /// The gate is on and the level is holding at the sustain level.
+    Sustain,
+    /// The gate is off and the level is falling to zero.
+    Release,
+}
+
+/// An attack-decay-sustain-release envelope generator.
+///
+/// Turning the gate on starts the attack, which rises to full scale, and
+/// the decay then falls to the sustain level, where the envelope holds
+/// until the gate is turned off and it releases to zero. Each segment is
+/// the curve of an analog envelope: a one-pole filter heading for a point
+/// slightly past the segment's end, so it gets there in the given number of
+/// ticks. The decay and release times are for falling the whole way from
+/// full scale to zero.
+///
+/// The level is kept with 62 fractional bits, and [`Adsr::tick`] returns it
+/// as a `Poui`, with full scale saturating to the largest value.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Adsr;
+/// use poui::Poui;
+/// use poui::AdsrStage;
+///
+/// let mut env = Adsr::new(10, 20, Poui(0x8000u16), 40);
+/// env.gate(true);
+/// let attack: Vec<_> = (0..10).map(|_| env.tick()).collect();
+/// assert!(attack.windows(2).all(|w| w[0] < w[1]));
+/// assert_eq!(attack[9], Poui(u16::MAX));
+/// for _ in 0..100 {
+///     env.tick();
+/// }
+/// assert_eq!((env.stage(), env.tick()), (AdsrStage::Sustain, Poui(0x8000)));
+/// env.gate(false);
+/// for _ in 0..40 {
+///     env.tick();
+/// }
+/// assert_eq!((env.stage(), env.tick()), (AdsrStage::Idle, Poui(0)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Adsr<N: PrimInt + Unsigned + WrappingAdd> {
+    attack: i128,
+    decay: i128,
+    sustain: Poui<N>,
+    release: i128,
+    stage: AdsrStage,
+    level: i128,
+    output: PhantomData<N>,
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Adsr<N> {
+    /// An idle envelope with the given attack, decay, and release times in
+    /// ticks, and the given sustain level.
+    pub fn new(attack: u32, decay: u32, sustain: Poui<N>, release: u32) -> Self {
+        Adsr {
+            attack: coefficient(ATTACK_HALVINGS, attack),
+            decay: coefficient(DECAY_HALVINGS, decay),
+            sustain,
+            release: coefficient(DECAY_HALVINGS, release),
+            stage: AdsrStage::Idle,
+            level: 0,
+            output: PhantomData,
+        }
+    }
+
+    /// Changes the attack time, in ticks.
+    pub fn set_attack(&mut self, ticks: u32) {
+        self.attack = coefficient(ATTACK_HALVINGS, ticks);
+    }
+
+    /// Changes the decay time, in ticks.
+    pub fn set_decay(&mut self, ticks: u32) {
+        self.decay = coefficient(DECAY_HALVINGS, ticks);
+    }
+
+    /// The sustain level.
+    pub fn sustain(&self) -> Poui<N> {
+        self.sustain
+    }
+
+    /// Changes the sustain level, which takes effect immediately if the
+    /// envelope is sustaining.
+    pub fn set_sustain(&mut self, sustain: Poui<N>) {
+        self.sustain = sustain;
+        if self.stage == AdsrStage::Sustain {
+            self.level = to_q62(sustain);
+        }
+    }
+
+    /// Changes the release time, in ticks.
+    pub fn set_release(&mut self, ticks: u32) {
+        self.release = coefficient(DECAY_HALVINGS, ticks);
+    }
+
+    /// The current stage.
+    pub fn stage(&self) -> AdsrStage {
+        self.stage
+    }
+
+    /// The current level.
+    pub fn level(&self) -> Poui<N> {
+        from_q62(self.level)
+    }
+
+    /// Turns the gate on, starting the attack from the current level, or
+    /// off, starting the release. Repeating the current state of the gate
+    /// does nothing.
+    pub fn gate(&mut self, on: bool) {
+        let gated = matches!(
+            self.stage,
+            AdsrStage::Attack | AdsrStage::Decay | AdsrStage::Sustain
+        );
+        match (on, gated) {
+            (true, false) => self.stage = AdsrStage::Attack,
+            (false, true) => self.stage = AdsrStage::Release,
+            _ => {}
+        }
+    }
+
+    /// Advances the envelope by one tick, and returns the new level.
+    pub fn tick(&mut self) -> Poui<N> {
+        let sustain = to_q62(self.sustain);
+        match self.stage {
+            AdsrStage::Idle => self.level = 0,
+            AdsrStage::Attack => {
+                self.level = approach(self.level, ONE + ATTACK_OVERSHOOT, self.attack);
+                if self.level >= ONE {
+                    self.level = ONE;
+                    self.stage = AdsrStage::Decay;
+                }
+            }
+            AdsrStage::Decay => {
+                self.level = approach(self.level, sustain - DECAY_OVERSHOOT, self.decay);
+                if self.level <= sustain {
+                    self.level = sustain;
+                    self.stage = AdsrStage::Sustain;
+                }
+            }
+            AdsrStage::Sustain => self.level = sustain,
+            AdsrStage::Release => {
+                self.level = approach(self.level, -DECAY_OVERSHOOT, self.release);
+                if self.level <= 0 {
+                    self.level = 0;
+                    self.stage = AdsrStage::Idle;
+                }
+            }
+        }
+        self.level()
+    }
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Iterator for Adsr<N> {
+    type Item = Poui<N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.tick())
+    }
+}
+
+/// The fraction of the remaining distance a one-pole filter covers each
+/// tick to halve it `halvings` times over `ticks` ticks, in Q62.
+fn coefficient(halvings: i128, ticks: u32) -> i128 {
+    if ticks == 0 {
+        return ONE;
+    }
+    ONE - exp2(-halvings / ticks as i128)
+}
+
+/// One step of a one-pole filter from `level` towards `aim`.
+fn approach(level: i128, aim: i128, coefficient: i128) -> i128 {
+    level + (((aim - level) * coefficient) >> 62)
+}
+
+#[test]
+fn adsr_segment_lengths() {
+    for (attack, decay, release) in [(1, 1, 1), (48, 480, 4800), (1000, 3, 77)] {
+        let mut env = Adsr::new(attack, decay, Poui(0x4000_0000u32), release);
+        env.gate(true);
+        let ticks_in = |env: &mut Adsr<u32>, stage| {
+            let mut n = 0u32;
+            while env.stage() == stage {
+                env.tick();
+                n += 1;
+            }
+            n
+        };
+        assert!(ticks_in(&mut env, AdsrStage::Attack).abs_diff(attack) <= 1);
+        // The decay and release times are for the whole way from full
+        // scale, so falling part of the way is quicker.
+        let r = 0.0001f64;
+        let fraction = |gap: f64| (r / (gap + r)).ln() / (r / (1.0 + r)).ln();
+        let decayed = ticks_in(&mut env, AdsrStage::Decay) as f64;
+        let expected = decay as f64 * fraction(0.75);
+        assert!((decayed - expected).abs() <= 1.5, "{decayed} {expected}");
+        env.gate(false);
+        let released = ticks_in(&mut env, AdsrStage::Release) as f64;
+        let expected = release as f64 * fraction(0.25);
+        assert!((released - expected).abs() <= 1.5, "{released} {expected}");
+        assert_eq!(env.level(), Poui(0));
+    }
+}
+
+#[test]
+fn adsr_retrigger_and_gate_changes() {
+    let mut env = Adsr::new(0, 0, Poui(200u8), 100);
+    assert_eq!(env.tick(), Poui(0));
+    env.gate(true);
+    assert_eq!(env.tick(), Poui(255));
+    assert_eq!(env.tick(), Poui(200));
+    assert_eq!(env.stage(), AdsrStage::Sustain);
+    env.set_sustain(Poui(100));
+    assert_eq!(env.level(), Poui(100));
+    env.gate(false);
+    let releasing = env.nth(10).unwrap();
+    assert!(releasing < Poui(100) && releasing > Poui(0));
+    env.set_attack(50);
+    env.gate(true);
+    assert_eq!(env.stage(), AdsrStage::Attack);
+    assert!(env.tick() > releasing);
+    env.gate(true);
+    assert_eq!(env.stage(), AdsrStage::Attack);
+}
@@ -0,0 +1,166 @@
+use crate::probability::total_weight;
+use crate::Poui;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+
+/// A table for sampling repeatedly from a fixed discrete distribution in
+/// constant time, by Walker's alias method.
+///
+/// The table has one column per outcome, each holding a threshold and an
+/// alias. Sampling picks a column uniformly, then flips a biased coin against
+/// the threshold to choose between the column's own outcome and its alias.
+/// The table is built with Vose's algorithm entirely in integer arithmetic,
+/// with every weight scaled by the number of outcomes, so it reproduces the
+/// weights exactly: outcome `i` is chosen with probability exactly
+/// `weights[i] / total`.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::AliasTable;
+/// use poui::Poui;
+///
+/// let table = AliasTable::new(&[Poui(3u8), Poui(1), Poui(0)]).unwrap();
+/// assert_eq!(table.len(), 3);
+/// assert_eq!(table.probability(0), (3, 4));
+/// assert_eq!(table.probability(2), (0, 4));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AliasTable {
+    /// The total weight, which is also the full height of every column.
+    total: u128,
+    /// The threshold below which a column picks its own outcome rather than
+    /// its alias, out of `total`, and the alias.
+    columns: Vec<(u128, usize)>,
+}
+
+impl AliasTable {
+    /// Builds a table choosing each index with probability proportional to
+    /// its weight, or returns `None` if every weight is zero.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the weights sum to `2^128` or more, or if the total times
+    /// the number of weights does, which needs 128-bit weights or more than
+    /// `2^64` of them.
+    pub fn new<N: PrimInt + Unsigned + WrappingAdd>(weights: &[Poui<N>]) -> Option<Self> {
+        let total = total_weight(weights);
+        if total == 0 {
+            return None;
+        }
+        let n = weights.len() as u128;
+        total.checked_mul(n).expect("alias table is too large");
+        let mut scaled: Vec<u128> = weights.iter().map(|w| w.0.to_u128().unwrap() * n).collect();
+        let (mut small, mut large): (Vec<usize>, Vec<usize>) =
+            (0..weights.len()).partition(|&i| scaled[i] < total);
+        let mut columns: Vec<_> = (0..weights.len()).map(|i| (total, i)).collect();
+        while let (Some(s), Some(&l)) = (small.pop(), large.last()) {
+            // Fill the rest of the short column with the tall one.
+            columns[s] = (scaled[s], l);
+            scaled[l] -= total - scaled[s];
+            if scaled[l] < total {
+                large.pop();
+                small.push(l);
+            }
+        }
+        // The arithmetic is exact, so whatever is left is exactly full.
+        debug_assert!(small.iter().chain(&large).all(|&i| scaled[i] == total));
+        Some(AliasTable { total, columns })
+    }
+
+    /// The number of outcomes, i.e. the number of weights the table was
+    /// built from.
+    pub fn len(&self) -> usize {
+        self.columns.len()
+    }
+
+    /// Whether the table has no outcomes, which is never the case.
+    pub fn is_empty(&self) -> bool {
+        self.columns.is_empty()
+    }
+
+    /// The probability of choosing `index`, as the ratio of its weight to
+    /// the total weight, recovered from its share of the table's columns.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `index` is out of bounds.
+    pub fn probability(&self, index: usize) -> (u128, u128) {
+        assert!(index < self.len(), "index out of bounds");
+        let n = self.len() as u128;
+        let mass = self
+            .columns
+            .iter()
+            .enumerate()
+            .map(|(column, &(threshold, alias))| {
+                let own = if column == index { threshold } else { 0 };
+                let aliased = if alias == index && column != index {
+                    self.total - threshold
+                } else {
+                    0
+                };
+                own + aliased
+            })
+            .sum::<u128>();
+        (mass / n, self.total)
+    }
+
+    /// Chooses an index at random, in constant time.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::AliasTable;
+    /// use poui::Poui;
+    ///
+    /// let table = AliasTable::new(&[Poui(200u8), Poui(50), Poui(0)]).unwrap();
+    /// assert!(table.sample(&mut rand::thread_rng()) < 2);
+    /// ```
+    #[cfg(feature = "rand")]
+    pub fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> usize {
+        let column = rng.gen_range(0..self.len());
+        let (threshold, alias) = self.columns[column];
+        if rng.gen_range(0..self.total) < threshold {
+            column
+        } else {
+            alias
+        }
+    }
+}
+
+#[test]
+fn alias_table_is_exact() {
+    let weights = [
+        Poui(7u16),
+        Poui(0),
+        Poui(65535),
+        Poui(1),
+        Poui(300),
+        Poui(300),
+        Poui(12345),
+    ];
+    let table = AliasTable::new(&weights).unwrap();
+    let total: u128 = weights.iter().map(|w| w.0 as u128).sum();
+    for (i, w) in weights.iter().enumerate() {
+        assert_eq!(table.probability(i), (w.0 as u128, total));
+    }
+    assert_eq!(AliasTable::new(&[Poui(0u8); 3]), None);
+    assert_eq!(AliasTable::new::<u8>(&[]), None);
+    let single = AliasTable::new(&[Poui(u64::MAX)]).unwrap();
+    assert_eq!(single.probability(0), (u64::MAX as u128, u64::MAX as u128));
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn alias_table_samples_proportionally() {
+    let table = AliasTable::new(&[Poui(1u8), Poui(0), Poui(2), Poui(1)]).unwrap();
+    let mut rng = crate::random::test_rng();
+    let mut counts = [0u32; 4];
+    for _ in 0..40_000 {
+        counts[table.sample(&mut rng)] += 1;
+    }
+    assert_eq!(counts[1], 0);
+    assert!(counts[2].abs_diff(20_000) < 600, "{counts:?}");
+    assert!(counts[3].abs_diff(10_000) < 500, "{counts:?}");
+}
@@ -0,0 +1,490 @@
+use crate::Poui;
+use num_traits::Num;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+use num_traits::WrappingSub;
+
+/// A half-open interval on the circle, running forward from a start angle.
+///
+/// An arc covers the angles from its start (inclusive) to its end
+/// (exclusive), wrapping past 1 back to 0 if the end comes before the start,
+/// so e.g. the sector from 0.9 to 0.1 of a turn passes through 0. Besides the
+/// arcs with distinct ends, there is an empty arc and an arc covering the
+/// whole circle, neither of which can be told apart by their ends alone.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Arc;
+/// use poui::Poui;
+///
+/// let sensor = Arc::new(Poui(230u8), Poui(26u8));
+/// assert!(sensor.contains(Poui(250u8)));
+/// assert!(sensor.contains(Poui(10u8)));
+/// assert!(!sensor.contains(Poui(26u8)));
+/// assert!(!sensor.contains(Poui(128u8)));
+/// assert_eq!(sensor.length(), Poui(52u8));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Arc<N: Num + WrappingAdd> {
+    start: Poui<N>,
+    len: N,
+    full: bool,
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd + WrappingSub> Arc<N> {
+    /// The arc running forward from `start` to `end`.
+    ///
+    /// If `start == end` the arc is empty; use [`Arc::full`] for the whole
+    /// circle.
+    pub fn new(start: Poui<N>, end: Poui<N>) -> Self {
+        Arc::from_start_len(start, Poui(end.0.wrapping_sub(&start.0)))
+    }
+
+    /// The arc running forward from `start` for a distance of `len`.
+    pub fn from_start_len(start: Poui<N>, len: Poui<N>) -> Self {
+        if len.0.is_zero() {
+            Arc::empty()
+        } else {
+            Arc {
+                start,
+                len: len.0,
+                full: false,
+            }
+        }
+    }
+
+    /// The arc containing no angles.
+    pub fn empty() -> Self {
+        Arc {
+            start: Poui(N::zero()),
+            len: N::zero(),
+            full: false,
+        }
+    }
+
+    /// The arc containing every angle.
+    pub fn full() -> Self {
+        Arc {
+            start: Poui(N::zero()),
+            len: N::zero(),
+            full: true,
+        }
+    }
+
+    /// The first angle in the arc. This is 0 for an empty or full arc.
+    pub fn start(self) -> Poui<N> {
+        self.start
+    }
+
+    /// The first angle after the end of the arc. This is 0 for an empty or
+    /// full arc.
+    pub fn end(self) -> Poui<N> {
+        Poui(self.start.0.wrapping_add(&self.len))
+    }
+
+    /// The length of the arc as a fraction of a turn, saturating to the
+    /// largest `Poui` for the full circle.
+    pub fn length(self) -> Poui<N> {
+        if self.full {
+            Poui(N::max_value())
+        } else {
+            Poui(self.len)
+        }
+    }
+
+    /// Whether the arc contains no angles.
+    pub fn is_empty(self) -> bool {
+        !self.full && self.len.is_zero()
+    }
+
+    /// Whether the arc contains every angle.
+    pub fn is_full(self) -> bool {
+        self.full
+    }
+
+    /// Whether the arc contains `x`.
+    pub fn contains(self, x: Poui<N>) -> bool {
+        self.full || x.0.wrapping_sub(&self.start.0) < self.len
+    }
+
+    /// The angles in both `self` and `other`.
+    ///
+    /// Two arcs that together cover more than the whole circle can overlap at
+    /// both ends, so the intersection may be split in two. The second arc is
+    /// present only in that case, and the first arc is empty if the arcs
+    /// don't overlap at all.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Arc;
+    /// use poui::Poui;
+    ///
+    /// let a = Arc::new(Poui(0u8), Poui(100u8));
+    /// let b = Arc::new(Poui(50u8), Poui(150u8));
+    /// assert_eq!(a.intersect(b), (Arc::new(Poui(50u8), Poui(100u8)), None));
+    ///
+    /// let c = Arc::new(Poui(80u8), Poui(20u8));
+    /// assert_eq!(
+    ///     a.intersect(c),
+    ///     (Arc::new(Poui(80u8), Poui(100u8)), Some(Arc::new(Poui(0u8), Poui(20u8))))
+    /// );
+    /// ```
+    pub fn intersect(self, other: Self) -> (Self, Option<Self>) {
+        if self.is_empty() || other.is_full() {
+            return (self, None);
+        }
+        if other.is_empty() || self.is_full() {
+            return (other, None);
+        }
+        if self.start == other.start {
+            return (if self.len < other.len { self } else { other }, None);
+        }
+        match (self.clip(other), other.clip(self)) {
+            (Some(a), Some(b)) if a.end() == b.start => {
+                (Arc::from_start_len(a.start, Poui(a.len + b.len)), None)
+            }
+            (Some(a), Some(b)) if b.end() == a.start => {
+                (Arc::from_start_len(b.start, Poui(a.len + b.len)), None)
+            }
+            (Some(a), b) => (a, b),
+            (None, Some(b)) => (b, None),
+            (None, None) => (Arc::empty(), None),
+        }
+    }
+
+    /// The part of the intersection of `self` and `other` that starts at
+    /// `other.start`, if `self` contains it.
+    fn clip(self, other: Self) -> Option<Self> {
+        let offset = other.start.0.wrapping_sub(&self.start.0);
+        if offset >= self.len {
+            return None;
+        }
+        let len = (self.len - offset).min(other.len);
+        Some(Arc::from_start_len(other.start, Poui(len)))
+    }
+
+    /// The angles in either `self` or `other`.
+    ///
+    /// If the arcs overlap or touch, the union is a single arc. Otherwise it
+    /// is both arcs, unchanged.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Arc;
+    /// use poui::Poui;
+    ///
+    /// let a = Arc::new(Poui(200u8), Poui(10u8));
+    /// let b = Arc::new(Poui(10u8), Poui(50u8));
+    /// assert_eq!(a.union(b), (Arc::new(Poui(200u8), Poui(50u8)), None));
+    ///
+    /// let c = Arc::new(Poui(5u8), Poui(210u8));
+    /// assert_eq!(a.union(c), (Arc::full(), None));
+    ///
+    /// let d = Arc::new(Poui(100u8), Poui(150u8));
+    /// assert_eq!(a.union(d), (a, Some(d)));
+    /// ```
+    pub fn union(self, other: Self) -> (Self, Option<Self>) {
+        if self.is_full() || other.is_empty() {
+            return (self, None);
+        }
+        if other.is_full() || self.is_empty() {
+            return (other, None);
+        }
+        match self.extend(other).or_else(|| other.extend(self)) {
+            Some(arc) => (arc, None),
+            None => (self, Some(other)),
+        }
+    }
+
+    /// The union of `self` and `other`, if `other` starts within or right
+    /// at the end of `self`.
+    fn extend(self, other: Self) -> Option<Self> {
+        let offset = other.start.0.wrapping_sub(&self.start.0);
+        if offset > self.len {
+            return None;
+        }
+        match offset.checked_add(&other.len) {
+            Some(end) => Some(Arc::from_start_len(self.start, Poui(self.len.max(end)))),
+            None => Some(Arc::full()),
+        }
+    }
+
+    /// The arc as closed ranges of raw values that don't wrap.
+    fn ranges(self) -> impl Iterator<Item = (N, N)> {
+        let (first, second) = if self.full {
+            (Some((N::zero(), N::max_value())), None)
+        } else if self.len.is_zero() {
+            (None, None)
+        } else {
+            let last = self.start.0.wrapping_add(&(self.len - N::one()));
+            if last >= self.start.0 {
+                (Some((self.start.0, last)), None)
+            } else {
+                (
+                    Some((self.start.0, N::max_value())),
+                    Some((N::zero(), last)),
+                )
+            }
+        };
+        first.into_iter().chain(second)
+    }
+
+    /// The arc covering a closed range of raw values.
+    fn from_range((first, last): (N, N)) -> Self {
+        if first.is_zero() && last == N::max_value() {
+            Arc::full()
+        } else {
+            Arc::from_start_len(Poui(first), Poui(last - first + N::one()))
+        }
+    }
+}
+
+/// A union of any number of arcs.
+///
+/// The arcs are kept sorted and merged, so two sets containing the same
+/// angles compare equal however they were built.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Arc;
+/// use poui::ArcSet;
+/// use poui::Poui;
+///
+/// let mut coverage = ArcSet::new();
+/// coverage.insert(Arc::new(Poui(0x1000u16), Poui(0x3000u16)));
+/// coverage.insert(Arc::new(Poui(0xF000u16), Poui(0x0800u16)));
+/// coverage.insert(Arc::new(Poui(0x0800u16), Poui(0x1000u16)));
+/// assert_eq!(coverage.arcs().collect::<Vec<_>>(), [Arc::new(Poui(0xF000), Poui(0x3000))]);
+/// assert!(coverage.contains(Poui(0x0000)));
+/// assert!(!coverage.contains(Poui(0x8000)));
+/// assert_eq!(coverage.complement().length(), Poui(0xC000));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct ArcSet<N> {
+    /// Sorted, disjoint, non-adjacent closed ranges of raw values.
+    ranges: Vec<(N, N)>,
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd + WrappingSub> ArcSet<N> {
+    /// The empty set.
+    pub fn new() -> Self {
+        ArcSet { ranges: Vec::new() }
+    }
+
+    /// Whether the set contains no angles.
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// Adds the angles in `arc` to the set.
+    pub fn insert(&mut self, arc: Arc<N>) {
+        self.ranges.extend(arc.ranges());
+        self.normalize();
+    }
+
+    /// Whether any arc in the set contains `x`.
+    pub fn contains(&self, x: Poui<N>) -> bool {
+        let i = self.ranges.partition_point(|&(first, _)| first <= x.0);
+        i > 0 && x.0 <= self.ranges[i - 1].1
+    }
+
+    /// The total length of the arcs in the set, saturating to the largest
+    /// `Poui` for the full circle.
+    pub fn length(&self) -> Poui<N> {
+        let total = self
+            .ranges
+            .iter()
+            .try_fold(N::zero(), |total, &(first, last)| {
+                (last - first).checked_add(&N::one())?.checked_add(&total)
+            });
+        Poui(total.unwrap_or(N::max_value()))
+    }
+
+    /// The angles in either `self` or `other`.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut ranges = self.ranges.clone();
+        ranges.extend_from_slice(&other.ranges);
+        let mut set = ArcSet { ranges };
+        set.normalize();
+        set
+    }
+
+    /// The angles in both `self` and `other`.
+    pub fn intersect(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+        while i < self.ranges.len() && j < other.ranges.len() {
+            let (a, b) = (self.ranges[i], other.ranges[j]);
+            let first = a.0.max(b.0);
+            let last = a.1.min(b.1);
+            if first <= last {
+                ranges.push((first, last));
+            }
+            if a.1 < b.1 {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+        ArcSet { ranges }
+    }
+
+    /// The angles not in `self`.
+    pub fn complement(&self) -> Self {
+        let mut ranges = Vec::new();
+        let mut next = Some(N::zero());
+        for &(first, last) in &self.ranges {
+            if let Some(start) = next {
+                if start < first {
+                    ranges.push((start, first - N::one()));
+                }
+            }
+            next = last.checked_add(&N::one());
+        }
+        if let Some(start) = next {
+            ranges.push((start, N::max_value()));
+        }
+        ArcSet { ranges }
+    }
+
+    /// The arcs in the set, in order of their start angles, except that an
+    /// arc wrapping past 0 comes last.
+    pub fn arcs(&self) -> impl Iterator<Item = Arc<N>> + '_ {
+        let ranges = &self.ranges[..];
+        let wraps = ranges.len() > 1
+            && ranges[0].0.is_zero()
+            && ranges[ranges.len() - 1].1 == N::max_value();
+        let (inner, wrapped) = if wraps {
+            let wrapped = Arc::from_range(ranges[ranges.len() - 1])
+                .union(Arc::from_range(ranges[0]))
+                .0;
+            (&ranges[1..ranges.len() - 1], Some(wrapped))
+        } else {
+            (ranges, None)
+        };
+        inner
+            .iter()
+            .map(|&range| Arc::from_range(range))
+            .chain(wrapped)
+    }
+
+    /// Restores the invariant on `ranges` after adding to it.
+    fn normalize(&mut self) {
+        self.ranges.sort_unstable();
+        let mut merged: Vec<(N, N)> = Vec::with_capacity(self.ranges.len());
+        for &(first, last) in &self.ranges {
+            match merged.last_mut() {
+                Some(prev) if prev.1 == N::max_value() || first <= prev.1 + N::one() => {
+                    prev.1 = prev.1.max(last);
+                }
+                _ => merged.push((first, last)),
+            }
+        }
+        self.ranges = merged;
+    }
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd + WrappingSub> FromIterator<Arc<N>> for ArcSet<N> {
+    fn from_iter<T: IntoIterator<Item = Arc<N>>>(iter: T) -> Self {
+        let mut set = ArcSet::new();
+        set.ranges.extend(iter.into_iter().flat_map(Arc::ranges));
+        set.normalize();
+        set
+    }
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd + WrappingSub> From<Arc<N>> for ArcSet<N> {
+    fn from(arc: Arc<N>) -> Self {
+        std::iter::once(arc).collect()
+    }
+}
+
+#[cfg(test)]
+fn members(arcs: &[Arc<u8>]) -> Vec<u8> {
+    (0..=255)
+        .filter(|&x| arcs.iter().any(|arc| arc.contains(Poui(x))))
+        .collect()
+}
+
+#[test]
+fn arc_empty_and_full() {
+    let empty = Arc::new(Poui(7u8), Poui(7u8));
+    assert_eq!(empty, Arc::empty());
+    assert!(empty.is_empty());
+    assert!(!empty.contains(Poui(7)));
+    assert!(Arc::<u8>::full().contains(Poui(7)));
+    assert_eq!(Arc::<u8>::full().length(), Poui(255));
+    assert_eq!(
+        Arc::from_start_len(Poui(128u128), Poui(1 << 127)).end(),
+        Poui((1 << 127) + 128)
+    );
+}
+
+#[test]
+fn arc_set_operations_match_membership() {
+    let arcs = [
+        Arc::new(Poui(0u8), Poui(100u8)),
+        Arc::new(Poui(50u8), Poui(150u8)),
+        Arc::new(Poui(80u8), Poui(20u8)),
+        Arc::new(Poui(200u8), Poui(10u8)),
+        Arc::new(Poui(10u8), Poui(200u8)),
+        Arc::new(Poui(100u8), Poui(99u8)),
+        Arc::full(),
+        Arc::empty(),
+    ];
+    for &a in &arcs {
+        for &b in &arcs {
+            let both: Vec<u8> = (0..=255)
+                .filter(|&x| a.contains(Poui(x)) && b.contains(Poui(x)))
+                .collect();
+            let either: Vec<u8> = (0..=255)
+                .filter(|&x| a.contains(Poui(x)) || b.contains(Poui(x)))
+                .collect();
+            let (i, j) = a.intersect(b);
+            assert_eq!(
+                members(&[i, j.unwrap_or(Arc::empty())]),
+                both,
+                "{a:?} {b:?}"
+            );
+            let (i, j) = a.union(b);
+            assert_eq!(
+                members(&[i, j.unwrap_or(Arc::empty())]),
+                either,
+                "{a:?} {b:?}"
+            );
+            let set_a = ArcSet::from(a);
+            let set_b = ArcSet::from(b);
+            let arcs: Vec<_> = set_a.intersect(&set_b).arcs().collect();
+            assert_eq!(members(&arcs), both);
+            let arcs: Vec<_> = set_a.union(&set_b).arcs().collect();
+            assert_eq!(members(&arcs), either);
+            assert_eq!(
+                set_a.union(&set_b).length().0 as usize,
+                either.len().min(255)
+            );
+        }
+    }
+}
+
+#[test]
+fn arc_set_complement() {
+    let set: ArcSet<u8> = [Arc::new(Poui(250), Poui(5)), Arc::new(Poui(20), Poui(30))]
+        .into_iter()
+        .collect();
+    let complement: Vec<_> = set.complement().arcs().collect();
+    assert_eq!(
+        complement,
+        [Arc::new(Poui(5), Poui(20)), Arc::new(Poui(30), Poui(250))]
+    );
+    assert_eq!(
+        set.union(&set.complement()).arcs().collect::<Vec<_>>(),
+        [Arc::full()]
+    );
+    assert!(set.intersect(&set.complement()).is_empty());
+    assert!(ArcSet::<u8>::new().complement().contains(Poui(0)));
+}
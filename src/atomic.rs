@@ -0,0 +1,291 @@
+use crate::Poui;
+use num_traits::Num;
+use num_traits::WrappingAdd;
+use std::sync::atomic::Ordering;
+
+/// Integer types that have a corresponding atomic type in `std`.
+///
+/// This is the bridge between an integer backing and its `std::sync::atomic`
+/// counterpart, used by [`AtomicPoui`]. It is implemented for all integer
+/// backings up to 64 bits wide.
+pub trait AtomicBacking: Num + WrappingAdd + Copy {
+    type Atomic: Send + Sync;
+    fn new_atomic(self) -> Self::Atomic;
+    fn into_inner(atomic: Self::Atomic) -> Self;
+    fn load(atomic: &Self::Atomic, order: Ordering) -> Self;
+    fn store(atomic: &Self::Atomic, value: Self, order: Ordering);
+    fn swap(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self;
+    fn compare_exchange(
+        atomic: &Self::Atomic,
+        current: Self,
+        new: Self,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Self, Self>;
+    fn fetch_add(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self;
+    fn fetch_sub(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self;
+    fn fetch_update<F: FnMut(Self) -> Option<Self>>(
+        atomic: &Self::Atomic,
+        set_order: Ordering,
+        fetch_order: Ordering,
+        f: F,
+    ) -> Result<Self, Self>;
+}
+
+macro_rules! impl_atomic_backing {
+    ($($n:ty => $atomic:ty),*) => {
+        $(
+            impl AtomicBacking for $n {
+                type Atomic = $atomic;
+                fn new_atomic(self) -> Self::Atomic {
+                    <$atomic>::new(self)
+                }
+                fn into_inner(atomic: Self::Atomic) -> Self {
+                    atomic.into_inner()
+                }
+                fn load(atomic: &Self::Atomic, order: Ordering) -> Self {
+                    atomic.load(order)
+                }
+                fn store(atomic: &Self::Atomic, value: Self, order: Ordering) {
+                    atomic.store(value, order)
+                }
+                fn swap(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self {
+                    atomic.swap(value, order)
+                }
+                fn compare_exchange(
+                    atomic: &Self::Atomic,
+                    current: Self,
+                    new: Self,
+                    success: Ordering,
+                    failure: Ordering,
+                ) -> Result<Self, Self> {
+                    atomic.compare_exchange(current, new, success, failure)
+                }
+                fn fetch_add(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self {
+                    atomic.fetch_add(value, order)
+                }
+                fn fetch_sub(atomic: &Self::Atomic, value: Self, order: Ordering) -> Self {
+                    atomic.fetch_sub(value, order)
+                }
+                fn fetch_update<F: FnMut(Self) -> Option<Self>>(
+                    atomic: &Self::Atomic,
+                    set_order: Ordering,
+                    fetch_order: Ordering,
+                    f: F,
+                ) -> Result<Self, Self> {
+                    atomic.fetch_update(set_order, fetch_order, f)
+                }
+            }
+        )*
+    };
+}
+
+impl_atomic_backing!(
+    u8 => std::sync::atomic::AtomicU8,
+    u16 => std::sync::atomic::AtomicU16,
+    u32 => std::sync::atomic::AtomicU32,
+    u64 => std::sync::atomic::AtomicU64,
+    i8 => std::sync::atomic::AtomicI8,
+    i16 => std::sync::atomic::AtomicI16,
+    i32 => std::sync::atomic::AtomicI32,
+    i64 => std::sync::atomic::AtomicI64
+);
+
+/// A `Poui` that can be safely shared between threads.
+///
+/// This wraps the atomic integer type corresponding to the backing, e.g.
+/// `AtomicPoui<u32>` wraps an `AtomicU32`, and so is lock-free wherever the
+/// atomic integer is. It is useful for sharing a phase accumulator or a
+/// progress fraction between e.g. an audio thread and a UI thread.
+///
+/// The atomic integer operations wrap around on overflow, which is exactly the
+/// semantics of `Poui` addition, so [`AtomicPoui::fetch_wrapping_add`] can
+/// advance a shared phase without a compare-and-swap loop.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::AtomicPoui;
+/// use poui::Poui;
+/// use std::sync::atomic::Ordering;
+///
+/// let phase = AtomicPoui::new(Poui(0xC000_0000u32));
+/// phase.fetch_wrapping_add(Poui(0x8000_0000u32), Ordering::Relaxed);
+/// assert_eq!(phase.load(Ordering::Relaxed), Poui(0x4000_0000u32));
+/// ```
+pub struct AtomicPoui<N: AtomicBacking>(N::Atomic);
+
+impl<N: AtomicBacking> AtomicPoui<N> {
+    /// Creates a new atomic `Poui` with the given initial value.
+    pub fn new(value: Poui<N>) -> Self {
+        AtomicPoui(value.0.new_atomic())
+    }
+
+    /// Consumes the atomic and returns the contained value.
+    pub fn into_inner(self) -> Poui<N> {
+        Poui(N::into_inner(self.0))
+    }
+
+    /// Loads the current value.
+    pub fn load(&self, order: Ordering) -> Poui<N> {
+        Poui(N::load(&self.0, order))
+    }
+
+    /// Stores a new value.
+    pub fn store(&self, value: Poui<N>, order: Ordering) {
+        N::store(&self.0, value.0, order)
+    }
+
+    /// Stores a new value, returning the previous one.
+    pub fn swap(&self, value: Poui<N>, order: Ordering) -> Poui<N> {
+        Poui(N::swap(&self.0, value.0, order))
+    }
+
+    /// Stores `new` if the current value is `current`.
+    ///
+    /// Returns the previous value, wrapped in `Ok` if it was `current` and the
+    /// store happened, or in `Err` otherwise.
+    pub fn compare_exchange(
+        &self,
+        current: Poui<N>,
+        new: Poui<N>,
+        success: Ordering,
+        failure: Ordering,
+    ) -> Result<Poui<N>, Poui<N>> {
+        N::compare_exchange(&self.0, current.0, new.0, success, failure)
+            .map(Poui)
+            .map_err(Poui)
+    }
+
+    /// Adds `delta` to the current value, wrapping around the end of the
+    /// interval, and returns the previous value.
+    pub fn fetch_wrapping_add(&self, delta: Poui<N>, order: Ordering) -> Poui<N> {
+        Poui(N::fetch_add(&self.0, delta.0, order))
+    }
+
+    /// Subtracts `delta` from the current value, wrapping around the start of
+    /// the interval, and returns the previous value.
+    pub fn fetch_wrapping_sub(&self, delta: Poui<N>, order: Ordering) -> Poui<N> {
+        Poui(N::fetch_sub(&self.0, delta.0, order))
+    }
+
+    /// Repeatedly applies `f` to the current value until the result is stored
+    /// without interference from another thread, or until `f` returns `None`.
+    ///
+    /// Returns the previous value, wrapped in `Ok` if a new value was stored,
+    /// or in `Err` if `f` returned `None`. This is the general-purpose way to
+    /// apply any `Poui` operation atomically, e.g. a saturating add.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::AtomicPoui;
+    /// use poui::Poui;
+    /// use std::sync::atomic::Ordering;
+    ///
+    /// let progress = AtomicPoui::new(Poui(200u8));
+    /// let update = progress.fetch_update(Ordering::SeqCst, Ordering::SeqCst, |p| {
+    ///     Some(Poui(p.0.saturating_add(100)))
+    /// });
+    /// assert_eq!(update, Ok(Poui(200u8)));
+    /// assert_eq!(progress.load(Ordering::SeqCst), Poui(255u8));
+    /// ```
+    pub fn fetch_update<F: FnMut(Poui<N>) -> Option<Poui<N>>>(
+        &self,
+        set_order: Ordering,
+        fetch_order: Ordering,
+        mut f: F,
+    ) -> Result<Poui<N>, Poui<N>> {
+        N::fetch_update(&self.0, set_order, fetch_order, |n| f(Poui(n)).map(|p| p.0))
+            .map(Poui)
+            .map_err(Poui)
+    }
+}
+
+impl<N: AtomicBacking> Default for AtomicPoui<N> {
+    fn default() -> Self {
+        AtomicPoui::new(Poui(N::zero()))
+    }
+}
+
+impl<N: AtomicBacking> From<Poui<N>> for AtomicPoui<N> {
+    fn from(value: Poui<N>) -> Self {
+        AtomicPoui::new(value)
+    }
+}
+
+impl<N: AtomicBacking + std::fmt::Debug> std::fmt::Debug for AtomicPoui<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_tuple("AtomicPoui")
+            .field(&self.load(Ordering::Relaxed))
+            .finish()
+    }
+}
+
+#[test]
+fn atomic_load_store_swap() {
+    let a = AtomicPoui::new(Poui(5u16));
+    assert_eq!(a.load(Ordering::SeqCst), Poui(5u16));
+    a.store(Poui(7u16), Ordering::SeqCst);
+    assert_eq!(a.swap(Poui(9u16), Ordering::SeqCst), Poui(7u16));
+    assert_eq!(a.into_inner(), Poui(9u16));
+}
+
+#[test]
+fn atomic_fetch_wrapping_add_sub() {
+    let a = AtomicPoui::new(Poui(i8::MAX));
+    assert_eq!(
+        a.fetch_wrapping_add(Poui(1i8), Ordering::SeqCst),
+        Poui(i8::MAX)
+    );
+    assert_eq!(a.load(Ordering::SeqCst), Poui(i8::MIN));
+    assert_eq!(
+        a.fetch_wrapping_sub(Poui(1i8), Ordering::SeqCst),
+        Poui(i8::MIN)
+    );
+    assert_eq!(a.load(Ordering::SeqCst), Poui(i8::MAX));
+}
+
+#[test]
+fn atomic_compare_exchange() {
+    let a = AtomicPoui::new(Poui(1u64));
+    let ord = Ordering::SeqCst;
+    assert_eq!(
+        a.compare_exchange(Poui(2), Poui(3), ord, ord),
+        Err(Poui(1u64))
+    );
+    assert_eq!(
+        a.compare_exchange(Poui(1), Poui(3), ord, ord),
+        Ok(Poui(1u64))
+    );
+    assert_eq!(a.load(ord), Poui(3u64));
+}
+
+#[test]
+fn atomic_fetch_update() {
+    let a = AtomicPoui::new(Poui(10u32));
+    let ord = Ordering::SeqCst;
+    assert_eq!(a.fetch_update(ord, ord, |p| Some(p + p)), Ok(Poui(10u32)));
+    assert_eq!(a.fetch_update(ord, ord, |_| None), Err(Poui(20u32)));
+    assert_eq!(a.load(ord), Poui(20u32));
+}
+
+#[test]
+fn atomic_shared_between_threads() {
+    let phase = std::sync::Arc::new(AtomicPoui::<u32>::default());
+    let threads: Vec<_> = (0..4)
+        .map(|_| {
+            let phase = phase.clone();
+            std::thread::spawn(move || {
+                for _ in 0..1000 {
+                    phase.fetch_wrapping_add(Poui(1 << 22), Ordering::Relaxed);
+                }
+            })
+        })
+        .collect();
+    for thread in threads {
+        thread.join().unwrap();
+    }
+    // 4000 steps of 1/1024 of a turn is 3 turns and 928/1024 of a turn.
+    assert_eq!(phase.load(Ordering::Relaxed), Poui(928 << 22));
+}
@@ -0,0 +1,128 @@
+//! Bézier curves over any [`Lerp`] type, evaluated with de Casteljau's
+//! algorithm.
+//!
+//! Each level of the algorithm lerps between neighbouring points, so the
+//! curves work with anything that can be interpolated, and inherit the
+//! guarantees of its lerp. For `Poui` control points, whose lerp is exact up
+//! to rounding towards its first argument:
+//!
+//! - The curve starts exactly at the first control point.
+//! - Every value lies between the smallest and largest control points, so
+//!   nothing overflows.
+//! - Each level truncates by less than a ULP, and lerps never amplify an
+//!   existing error, so a curve of degree `d` is less than `d` ULPs from
+//!   the exact value.
+//!
+//! ```rust
+//! use poui::bezier;
+//! use poui::Poui;
+//!
+//! let t = Poui(128u8);
+//! assert_eq!(bezier::quadratic(Poui(0u8), Poui(128), Poui(0), t), Poui(64));
+//! assert_eq!(bezier::cubic(Poui(0u8), Poui(0), Poui(255), Poui(255), t), Poui(127));
+//! ```
+
+use crate::Lerp;
+use crate::Poui;
+use num_traits::Num;
+use num_traits::WrappingAdd;
+
+/// Evaluates the quadratic Bézier curve with control points `p0`, `p1`, and
+/// `p2` at `t`.
+pub fn quadratic<N, V>(p0: V, p1: V, p2: V, t: Poui<N>) -> V
+where
+    N: Num + WrappingAdd + Copy,
+    V: Lerp<N> + Clone,
+{
+    let a = p0.lerp(p1.clone(), t);
+    let b = p1.lerp(p2, t);
+    a.lerp(b, t)
+}
+
+/// Evaluates the cubic Bézier curve with control points `p0` to `p3` at
+/// `t`.
+pub fn cubic<N, V>(p0: V, p1: V, p2: V, p3: V, t: Poui<N>) -> V
+where
+    N: Num + WrappingAdd + Copy,
+    V: Lerp<N> + Clone,
+{
+    let a = p0.lerp(p1.clone(), t);
+    let b = p1.lerp(p2.clone(), t);
+    let c = p2.lerp(p3, t);
+    quadratic(a, b, c, t)
+}
+
+/// Evaluates the Bézier curve with the given control points at `t`, or
+/// returns `None` if there are no control points.
+///
+/// The degree of the curve is one less than the number of points.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::bezier;
+/// use poui::Poui;
+///
+/// let points = [Poui(0u16), Poui(0xFFFF), Poui(0), Poui(0xFFFF), Poui(0)];
+/// assert_eq!(bezier::eval(&points, Poui(0)), Some(Poui(0)));
+/// assert_eq!(bezier::eval(&points, Poui(0x8000)), Some(Poui(0x7FFF)));
+/// assert_eq!(bezier::eval::<u16, Poui<u16>>(&[], Poui(0x8000)), None);
+/// ```
+pub fn eval<N, V>(points: &[V], t: Poui<N>) -> Option<V>
+where
+    N: Num + WrappingAdd + Copy,
+    V: Lerp<N> + Clone,
+{
+    let mut points = points.to_vec();
+    for level in (1..points.len()).rev() {
+        for i in 0..level {
+            points[i] = points[i].clone().lerp(points[i + 1].clone(), t);
+        }
+    }
+    points.into_iter().next()
+}
+
+#[cfg(test)]
+fn reference(points: &[u8], t: u8) -> f64 {
+    let t = t as f64 / 256.0;
+    let n = points.len() - 1;
+    let mut binomial = 1.0;
+    let mut sum = 0.0;
+    for (i, &p) in points.iter().enumerate() {
+        sum += binomial * t.powi(i as i32) * (1.0 - t).powi((n - i) as i32) * p as f64;
+        binomial = binomial * (n - i) as f64 / (i + 1) as f64;
+    }
+    sum
+}
+
+#[test]
+fn bezier_error_is_below_degree() {
+    let sets: [&[u8]; 4] = [
+        &[10, 250, 3],
+        &[0, 255, 0, 255],
+        &[255, 0, 128, 64, 255, 1],
+        &[77],
+    ];
+    for points in sets {
+        let pouis: Vec<_> = points.iter().map(|&p| Poui(p)).collect();
+        let lo = *points.iter().min().unwrap();
+        let hi = *points.iter().max().unwrap();
+        for t in 0..=255u8 {
+            let actual = eval(&pouis, Poui(t)).unwrap().0;
+            let error = reference(points, t) - actual as f64;
+            let degree = (points.len() - 1) as f64;
+            assert!(error.abs() < degree.max(1.0), "{points:?} {t}: {error}");
+            assert!((lo..=hi).contains(&actual));
+        }
+        assert_eq!(eval(&pouis, Poui(0)), Some(pouis[0]));
+    }
+}
+
+#[test]
+fn bezier_fixed_degrees_match_eval() {
+    let p = [Poui(0x1234u16), Poui(0xFEDC), Poui(0x0F0F), Poui(0x8000)];
+    for t in (0..=u16::MAX).step_by(251).map(Poui) {
+        assert_eq!(Some(quadratic(p[0], p[1], p[2], t)), eval(&p[..3], t));
+        assert_eq!(Some(cubic(p[0], p[1], p[2], p[3], t)), eval(&p, t));
+    }
+}
@@ -0,0 +1,463 @@
+//! Biquad filters.
+//!
+//! A biquad is a second-order recursive filter, which computes each output
+//! from the last two inputs and outputs:
+//!
+//! ```text
+//! y[n] = b0·x[n] + b1·x[n-1] + b2·x[n-2] − a1·y[n-1] − a2·y[n-2]
+//! ```
+//!
+//! [`Coefficients`] designs the low-pass, high-pass, band-pass and shelving
+//! filters of Robert Bristow-Johnson's *Audio EQ Cookbook* from a
+//! [`NormFreq`] and a Q, entirely in integer arithmetic: the cosine and sine
+//! of the frequency come from CORDIC, and shelf gains from a fixed-point
+//! `exp2`, so the same parameters give the same coefficients on every
+//! platform. A [`Biquad`] then filters signed `Poui` samples with them.
+//!
+//! ```rust
+//! use poui::biquad::Biquad;
+//! use poui::biquad::Coefficients;
+//! use poui::Mixed;
+//! use poui::NormFreq;
+//! use poui::Poui;
+//!
+//! let cutoff = NormFreq::<u32>::from_hz(1_000, 48_000).unwrap();
+//! let butterworth = Mixed::from_f64(std::f64::consts::FRAC_1_SQRT_2).unwrap();
+//! let mut filter = Biquad::new(Coefficients::low_pass(cutoff, butterworth));
+//! let mut output = Poui(0i16);
+//! for _ in 0..1000 {
+//!     output = filter.process(Poui(0x4000));
+//! }
+//! assert!((output.0 - 0x4000).abs() <= 1);
+//! ```
+
+use crate::easing::exp2;
+use crate::probability::LOG2_10;
+use crate::trig::cordic_cos_sin;
+use crate::trig::turn_bits;
+use crate::Mixed;
+use crate::NormFreq;
+use crate::Poui;
+use num_traits::AsPrimitive;
+use num_traits::PrimInt;
+use num_traits::Signed;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+
+/// The number of fractional bits of the filter coefficients.
+pub const COEFFICIENT_BITS: usize = 56;
+
+/// One in the Q56 format of the coefficients, which is also the working
+/// format of the design.
+const ONE: i128 = 1 << COEFFICIENT_BITS;
+
+/// The largest shelf gain, in decibels, in either direction.
+const MAX_GAIN_DB: i128 = 24;
+
+/// The coefficients of a biquad, normalized so that `a0` is 1, as `i64`
+/// values with [`COEFFICIENT_BITS`] fractional bits.
+///
+/// This Q7.56 format has room for the gains of shelves up to 24 dB, and
+/// enough precision for low-pass filters far below a thousandth of the
+/// sample rate, where the coefficients get tiny.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::biquad::Coefficients;
+/// use poui::biquad::COEFFICIENT_BITS;
+/// use poui::Mixed;
+/// use poui::NormFreq;
+/// use poui::Poui;
+///
+/// let quarter = NormFreq::new(Poui(0x4000u16)).unwrap();
+/// let c = Coefficients::band_pass(quarter, Mixed::from_int(1));
+/// // At a quarter of the sample rate, cos ω is 0 and α is 1/2, so b0 is
+/// // α / (1 + α) = 1/3.
+/// assert_eq!(c.b0, (1 << COEFFICIENT_BITS) / 3);
+/// assert_eq!(c.a1, 0);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Coefficients {
+    /// The weight of the current input.
+    pub b0: i64,
+    /// The weight of the previous input.
+    pub b1: i64,
+    /// The weight of the input before that.
+    pub b2: i64,
+    /// The weight of the previous output, subtracted.
+    pub a1: i64,
+    /// The weight of the output before that, subtracted.
+    pub a2: i64,
+}
+
+impl Coefficients {
+    /// A low-pass filter, passing frequencies below `cutoff`.
+    ///
+    /// A `q` of `1/√2` gives a Butterworth response, maximally flat in the
+    /// passband; larger values give a resonant peak at the cutoff.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `q` is less than 1/64.
+    pub fn low_pass<N: PrimInt + Unsigned + WrappingAdd>(
+        cutoff: NormFreq<N>,
+        q: Mixed<i32, u32>,
+    ) -> Self {
+        let (cos, alpha) = warp(cutoff, q);
+        let b1 = ONE - cos;
+        Self::normalize([b1 / 2, b1, b1 / 2], [ONE + alpha, -2 * cos, ONE - alpha])
+    }
+
+    /// A high-pass filter, passing frequencies above `cutoff`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `q` is less than 1/64.
+    pub fn high_pass<N: PrimInt + Unsigned + WrappingAdd>(
+        cutoff: NormFreq<N>,
+        q: Mixed<i32, u32>,
+    ) -> Self {
+        let (cos, alpha) = warp(cutoff, q);
+        let b1 = ONE + cos;
+        Self::normalize([b1 / 2, -b1, b1 / 2], [ONE + alpha, -2 * cos, ONE - alpha])
+    }
+
+    /// A band-pass filter centered on `center`, with a gain of 1 there.
+    ///
+    /// The bandwidth is `center / q`, so a larger `q` gives a narrower band.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `q` is less than 1/64.
+    pub fn band_pass<N: PrimInt + Unsigned + WrappingAdd>(
+        center: NormFreq<N>,
+        q: Mixed<i32, u32>,
+    ) -> Self {
+        let (cos, alpha) = warp(center, q);
+        Self::normalize([alpha, 0, -alpha], [ONE + alpha, -2 * cos, ONE - alpha])
+    }
+
+    /// A low shelf, which scales the frequencies below `corner` by
+    /// `gain_db` decibels and leaves those above it alone.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `q` is less than 1/64, or `gain_db` is more than 24 dB in
+    /// either direction.
+    pub fn low_shelf<N: PrimInt + Unsigned + WrappingAdd>(
+        corner: NormFreq<N>,
+        q: Mixed<i32, u32>,
+        gain_db: Mixed<i32, u32>,
+    ) -> Self {
+        let (cos, alpha) = warp(corner, q);
+        let (a, root) = shelf_gain(gain_db);
+        let (p, m, s) = (a + ONE, a - ONE, 2 * mul(root, alpha));
+        Self::normalize(
+            [
+                mul(a, p - mul(m, cos) + s),
+                2 * mul(a, m - mul(p, cos)),
+                mul(a, p - mul(m, cos) - s),
+            ],
+            [
+                p + mul(m, cos) + s,
+                -2 * (m + mul(p, cos)),
+                p + mul(m, cos) - s,
+            ],
+        )
+    }
+
+    /// A high shelf, which scales the frequencies above `corner` by
+    /// `gain_db` decibels and leaves those below it alone.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `q` is less than 1/64, or `gain_db` is more than 24 dB in
+    /// either direction.
+    pub fn high_shelf<N: PrimInt + Unsigned + WrappingAdd>(
+        corner: NormFreq<N>,
+        q: Mixed<i32, u32>,
+        gain_db: Mixed<i32, u32>,
+    ) -> Self {
+        let (cos, alpha) = warp(corner, q);
+        let (a, root) = shelf_gain(gain_db);
+        let (p, m, s) = (a + ONE, a - ONE, 2 * mul(root, alpha));
+        Self::normalize(
+            [
+                mul(a, p + mul(m, cos) + s),
+                -2 * mul(a, m + mul(p, cos)),
+                mul(a, p + mul(m, cos) - s),
+            ],
+            [
+                p - mul(m, cos) + s,
+                2 * (m - mul(p, cos)),
+                p - mul(m, cos) - s,
+            ],
+        )
+    }
+
+    /// Divides the Q56 cookbook coefficients by `a0`, rounding to nearest.
+    fn normalize(b: [i128; 3], a: [i128; 3]) -> Self {
+        let a0 = a[0];
+        let div = |v: i128| {
+            let n = v << COEFFICIENT_BITS;
+            let half = if n < 0 { -a0 / 2 } else { a0 / 2 };
+            ((n + half) / a0) as i64
+        };
+        Coefficients {
+            b0: div(b[0]),
+            b1: div(b[1]),
+            b2: div(b[2]),
+            a1: div(a[1]),
+            a2: div(a[2]),
+        }
+    }
+}
+
+/// Multiplies two Q56 values.
+fn mul(a: i128, b: i128) -> i128 {
+    (a * b) >> COEFFICIENT_BITS
+}
+
+/// Converts a Q62 value to Q56, rounding to nearest.
+fn from_q62(v: i128) -> i128 {
+    (v + (1 << 5)) >> 6
+}
+
+/// Computes `cos ω` and `α = sin ω / 2Q` in Q56 for the cookbook formulas.
+fn warp<N: PrimInt + Unsigned + WrappingAdd>(
+    freq: NormFreq<N>,
+    q: Mixed<i32, u32>,
+) -> (i128, i128) {
+    let q = ((q.int as i128) << 32) + q.frac.0 as i128;
+    assert!(q >= 1 << 26, "Q must be at least 1/64");
+    let (cos, sin) = cordic_cos_sin(turn_bits(freq.as_poui().0));
+    let sin = from_q62(sin as i128);
+    (from_q62(cos as i128), (sin << 32) / (2 * q))
+}
+
+/// Computes the shelf amplitude `A = 10^(gain / 40)` and its square root in
+/// Q56, for a gain in decibels.
+fn shelf_gain(gain_db: Mixed<i32, u32>) -> (i128, i128) {
+    let gain = ((gain_db.int as i128) << 32) + gain_db.frac.0 as i128;
+    assert!(
+        gain.abs() <= MAX_GAIN_DB << 32,
+        "shelf gain must be at most 24 dB"
+    );
+    // The exponent of 2, gain · log2(10) / 40, in Q62.
+    let exponent = ((gain * LOG2_10) >> 32) / 40;
+    (from_q62(exp2(exponent)), from_q62(exp2(exponent / 2)))
+}
+
+/// The state of a biquad filter of signed `Poui` samples, in direct form I.
+///
+/// Each output is accumulated exactly in 128 bits, from the coefficients and
+/// the previous inputs and outputs. The part of the accumulator below an
+/// ULP of the output is carried over into the next sample instead of being
+/// discarded, so the rounding errors don't build up in the feedback. This
+/// keeps the gain of a low-pass filter at DC exact, where plain truncation
+/// would leave the output of a very low cutoff stuck short of a constant
+/// input. Outputs beyond the range of the samples saturate.
+///
+/// Samples can be at most 64 bits, so that the products fit in the
+/// accumulator.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::biquad::Biquad;
+/// use poui::biquad::Coefficients;
+/// use poui::Mixed;
+/// use poui::NormFreq;
+/// use poui::Poui;
+///
+/// let cutoff = NormFreq::<u32>::from_hz(100, 48_000).unwrap();
+/// let mut hum = Biquad::new(Coefficients::high_pass(cutoff, Mixed::from_int(1)));
+/// let mut output = Poui(0i32);
+/// for _ in 0..10_000 {
+///     output = hum.process(Poui(0x1000_0000));
+/// }
+/// assert!(output.0.abs() <= 1);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Biquad<S: PrimInt + Signed + WrappingAdd> {
+    coefficients: Coefficients,
+    x: [Poui<S>; 2],
+    y: [Poui<S>; 2],
+    /// The part of the last output below an ULP, in units of
+    /// `2^-COEFFICIENT_BITS` ULPs.
+    error: i128,
+}
+
+impl<S> Biquad<S>
+where
+    S: PrimInt + Signed + WrappingAdd + Into<i64> + 'static,
+    i128: AsPrimitive<S>,
+{
+    /// A filter with the given coefficients, at rest.
+    pub fn new(coefficients: Coefficients) -> Self {
+        let zero = Poui(S::zero());
+        Biquad {
+            coefficients,
+            x: [zero; 2],
+            y: [zero; 2],
+            error: 0,
+        }
+    }
+
+    /// The coefficients of the filter.
+    pub fn coefficients(&self) -> Coefficients {
+        self.coefficients
+    }
+
+    /// Changes the coefficients, keeping the state, so the filter can be
+    /// retuned while it runs.
+    pub fn set_coefficients(&mut self, coefficients: Coefficients) {
+        self.coefficients = coefficients;
+    }
+
+    /// Forgets the previous samples, returning the filter to rest.
+    pub fn reset(&mut self) {
+        *self = Self::new(self.coefficients);
+    }
+
+    /// Filters the next sample.
+    pub fn process(&mut self, sample: Poui<S>) -> Poui<S> {
+        let wide = |v: Poui<S>| -> i128 { v.0.into() as i128 };
+        let c = self.coefficients;
+        let sum = c.b0 as i128 * wide(sample)
+            + c.b1 as i128 * wide(self.x[0])
+            + c.b2 as i128 * wide(self.x[1])
+            - c.a1 as i128 * wide(self.y[0])
+            - c.a2 as i128 * wide(self.y[1])
+            + self.error;
+        let output = sum >> COEFFICIENT_BITS;
+        self.error = sum - (output << COEFFICIENT_BITS);
+        let (min, max) = (wide(Poui(S::min_value())), wide(Poui(S::max_value())));
+        let output = Poui(output.clamp(min, max).as_());
+        self.x = [sample, self.x[0]];
+        self.y = [output, self.y[0]];
+        output
+    }
+}
+
+#[cfg(test)]
+fn cookbook(kind: &str, f: f64, q: f64, gain_db: f64) -> [f64; 5] {
+    let w = 2.0 * std::f64::consts::PI * f;
+    let (cos, alpha) = (w.cos(), w.sin() / (2.0 * q));
+    let a = 10f64.powf(gain_db / 40.0);
+    let (p, m, s) = (a + 1.0, a - 1.0, 2.0 * a.sqrt() * alpha);
+    let (b, a) = match kind {
+        "low_pass" => (
+            [(1.0 - cos) / 2.0, 1.0 - cos, (1.0 - cos) / 2.0],
+            [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+        ),
+        "high_pass" => (
+            [(1.0 + cos) / 2.0, -1.0 - cos, (1.0 + cos) / 2.0],
+            [1.0 + alpha, -2.0 * cos, 1.0 - alpha],
+        ),
+        "band_pass" => ([alpha, 0.0, -alpha], [1.0 + alpha, -2.0 * cos, 1.0 - alpha]),
+        "low_shelf" => (
+            [
+                a * (p - m * cos + s),
+                2.0 * a * (m - p * cos),
+                a * (p - m * cos - s),
+            ],
+            [p + m * cos + s, -2.0 * (m + p * cos), p + m * cos - s],
+        ),
+        _ => (
+            [
+                a * (p + m * cos + s),
+                -2.0 * a * (m + p * cos),
+                a * (p + m * cos - s),
+            ],
+            [p - m * cos + s, 2.0 * (m - p * cos), p - m * cos - s],
+        ),
+    };
+    [
+        b[0] / a[0],
+        b[1] / a[0],
+        b[2] / a[0],
+        a[1] / a[0],
+        a[2] / a[0],
+    ]
+}
+
+#[test]
+fn coefficients_match_float_cookbook() {
+    let scale = (1u64 << COEFFICIENT_BITS) as f64;
+    for hz in [20, 1_000, 12_000, 24_000] {
+        let freq = NormFreq::<u32>::from_hz(hz, 48_000).unwrap();
+        let f = freq.as_poui().0 as f64 / 2f64.powi(32);
+        for (q, gain) in [
+            (0.5, -24.0),
+            (std::f64::consts::FRAC_1_SQRT_2, 6.0),
+            (8.0, 24.0),
+        ] {
+            let (mq, mg) = (Mixed::from_f64(q).unwrap(), Mixed::from_f64(gain).unwrap());
+            let designs = [
+                ("low_pass", Coefficients::low_pass(freq, mq)),
+                ("high_pass", Coefficients::high_pass(freq, mq)),
+                ("band_pass", Coefficients::band_pass(freq, mq)),
+                ("low_shelf", Coefficients::low_shelf(freq, mq, mg)),
+                ("high_shelf", Coefficients::high_shelf(freq, mq, mg)),
+            ];
+            for (kind, c) in designs {
+                let expected = cookbook(kind, f, q, gain);
+                let actual = [c.b0, c.b1, c.b2, c.a1, c.a2].map(|v| v as f64 / scale);
+                for (x, y) in actual.iter().zip(expected) {
+                    assert!((x - y).abs() < 1e-9, "{kind} {hz} {q}: {x} {y}");
+                }
+            }
+        }
+    }
+}
+
+#[test]
+fn low_pass_passes_dc_and_blocks_nyquist() {
+    let cutoff = NormFreq::<u32>::from_hz(10, 48_000).unwrap();
+    let mut filter = Biquad::new(Coefficients::low_pass(
+        cutoff,
+        Mixed::from_f64(std::f64::consts::FRAC_1_SQRT_2).unwrap(),
+    ));
+    let mut output = Poui(0i16);
+    for _ in 0..50_000 {
+        output = filter.process(Poui(-0x1234));
+    }
+    assert!((output.0 + 0x1234).abs() <= 1, "{output:?}");
+    let cutoff = NormFreq::<u32>::from_hz(1_000, 48_000).unwrap();
+    let mut filter = Biquad::new(Coefficients::low_pass(cutoff, Mixed::from_int(1)));
+    for i in 0..1000 {
+        output = filter.process(Poui(if i % 2 == 0 { 0x4000 } else { -0x4000 }));
+    }
+    assert!(output.0.abs() <= 1, "{output:?}");
+    filter.reset();
+    assert_eq!(filter.process(Poui(0)), Poui(0));
+}
+
+#[test]
+fn shelves_scale_their_band() {
+    let corner = NormFreq::<u16>::from_hz(1_000, 48_000).unwrap();
+    let q = Mixed::from_f64(std::f64::consts::FRAC_1_SQRT_2).unwrap();
+    let gain = Mixed::from_f64(20.0 * 2f64.log10()).unwrap();
+    let scale = (1u64 << COEFFICIENT_BITS) as f64;
+    let dc = |c: Coefficients| (c.b0 + c.b1 + c.b2) as f64 / (scale + (c.a1 + c.a2) as f64);
+    let nyquist =
+        |c: Coefficients| (c.b0 - c.b1 + c.b2) as f64 / (scale - c.a1 as f64 + c.a2 as f64);
+    let low = Coefficients::low_shelf(corner, q, gain);
+    let high = Coefficients::high_shelf(corner, q, gain);
+    assert!((dc(low) - 2.0).abs() < 1e-6);
+    assert!((nyquist(low) - 1.0).abs() < 1e-6);
+    assert!((dc(high) - 1.0).abs() < 1e-6);
+    assert!((nyquist(high) - 2.0).abs() < 1e-6);
+    let mut boost = Biquad::new(low);
+    let mut output = Poui(0i32);
+    for _ in 0..1000 {
+        output = boost.process(Poui(0x3000_0000));
+    }
+    assert_eq!(output, Poui(0x6000_0000));
+    for _ in 0..1000 {
+        output = boost.process(Poui(0x5000_0000));
+    }
+    assert_eq!(output, Poui(i32::MAX));
+}
@@ -0,0 +1,232 @@
+use crate::Poui;
+
+/// The error returned when a [`Calibration`] can't be built.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CalibrationError {
+    /// The minimum and maximum were equal, or the dead band left no range
+    /// between them.
+    EmptyRange,
+    /// The center wasn't strictly between the minimum and maximum.
+    CenterOutOfRange,
+}
+
+impl std::fmt::Display for CalibrationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CalibrationError::EmptyRange => write!(f, "calibrated range is empty"),
+            CalibrationError::CenterOutOfRange => {
+                write!(f, "center is not strictly between the minimum and maximum")
+            }
+        }
+    }
+}
+
+impl std::error::Error for CalibrationError {}
+
+/// Maps raw sensor counts, e.g. from an ADC, onto the unit interval.
+///
+/// A calibration is built from the smallest and largest readings observed
+/// from a joystick, potentiometer, or throttle, and maps them to 0 and the
+/// largest `Poui`, saturating beyond them. If the minimum is larger than the
+/// maximum, e.g. for a potentiometer wired backwards, the mapping is
+/// reversed.
+///
+/// A center reading, e.g. a self-centering stick at rest, can be given as
+/// well; it maps to exactly one half, and each side of it is scaled
+/// separately. A dead band of a few counts makes readings near the center
+/// and the extremes snap to them exactly, so noise doesn't make them
+/// flicker.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Calibration;
+/// use poui::Poui;
+///
+/// let throttle = Calibration::new(100, 900).unwrap();
+/// assert_eq!(throttle.map(50), Poui(0));
+/// assert_eq!(throttle.map(500), Poui(0x8000));
+/// assert_eq!(throttle.map(1000), Poui(u16::MAX));
+///
+/// let stick = Calibration::new(200, 3800)
+///     .and_then(|c| c.with_center(2100))
+///     .and_then(|c| c.with_dead_band(20))
+///     .unwrap();
+/// assert_eq!(stick.map(2110), Poui(0x8000));
+/// assert_eq!(stick.map(1150), Poui(0x4000));
+/// assert_eq!(stick.map(210), Poui(0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Calibration {
+    min: u16,
+    max: u16,
+    center: Option<u16>,
+    dead_band: u16,
+}
+
+impl Calibration {
+    /// A calibration mapping `min` to 0 and `max` to the largest `Poui`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CalibrationError::EmptyRange`] if `min == max`.
+    pub fn new(min: u16, max: u16) -> Result<Self, CalibrationError> {
+        Calibration {
+            min,
+            max,
+            center: None,
+            dead_band: 0,
+        }
+        .validate()
+    }
+
+    /// Adds a center reading, which maps to exactly one half.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CalibrationError::CenterOutOfRange`] if `center` isn't
+    /// strictly between the minimum and maximum, and
+    /// [`CalibrationError::EmptyRange`] if the dead band leaves no range on
+    /// either side of it.
+    pub fn with_center(self, center: u16) -> Result<Self, CalibrationError> {
+        Calibration {
+            center: Some(center),
+            ..self
+        }
+        .validate()
+    }
+
+    /// Sets the number of counts around the center and the extremes that
+    /// snap to them.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CalibrationError::EmptyRange`] if the dead band leaves no
+    /// range to scale.
+    pub fn with_dead_band(self, dead_band: u16) -> Result<Self, CalibrationError> {
+        Calibration { dead_band, ..self }.validate()
+    }
+
+    /// The reading that maps to 0.
+    pub fn min(&self) -> u16 {
+        self.min
+    }
+
+    /// The reading that maps to the largest `Poui`.
+    pub fn max(&self) -> u16 {
+        self.max
+    }
+
+    /// The reading that maps to one half, if there is one.
+    pub fn center(&self) -> Option<u16> {
+        self.center
+    }
+
+    /// The dead band, in counts.
+    pub fn dead_band(&self) -> u16 {
+        self.dead_band
+    }
+
+    /// Maps a raw reading onto the unit interval.
+    pub fn map(&self, raw: u16) -> Poui<u16> {
+        let (lo, hi, raw) = self.oriented(raw);
+        let db = self.dead_band as i32;
+        let top = u16::MAX as i32;
+        let out = match self.center.map(|c| self.oriented(c).2) {
+            None => segment(raw, lo + db, hi - db, 0, top),
+            Some(c) if raw < c - db => segment(raw, lo + db, c - db, 0, 0x8000),
+            Some(c) if raw > c + db => segment(raw, c + db, hi - db, 0x8000, top),
+            Some(_) => 0x8000,
+        };
+        Poui(out as u16)
+    }
+
+    /// The bounds and `raw`, flipped if necessary so that the bounds are
+    /// increasing.
+    fn oriented(&self, raw: u16) -> (i32, i32, i32) {
+        let [min, max, raw] = [self.min, self.max, raw].map(|v| v as i32);
+        if min <= max {
+            (min, max, raw)
+        } else {
+            let flip = |v: i32| u16::MAX as i32 - v;
+            (flip(min), flip(max), flip(raw))
+        }
+    }
+
+    fn validate(self) -> Result<Self, CalibrationError> {
+        let (lo, hi, _) = self.oriented(0);
+        let db = self.dead_band as i32;
+        match self.center.map(|c| self.oriented(c).2) {
+            Some(c) if c <= lo || c >= hi => Err(CalibrationError::CenterOutOfRange),
+            Some(c) if lo + db >= c - db || c + db >= hi - db => Err(CalibrationError::EmptyRange),
+            None if lo + db >= hi - db => Err(CalibrationError::EmptyRange),
+            _ => Ok(self),
+        }
+    }
+}
+
+/// Maps `raw` from `[a, b]` onto `[ya, yb]`, rounding to nearest and
+/// saturating outside the range.
+fn segment(raw: i32, a: i32, b: i32, ya: i32, yb: i32) -> i32 {
+    let raw = raw.clamp(a, b) as i64;
+    let (a, b, ya, yb) = (a as i64, b as i64, ya as i64, yb as i64);
+    (ya + ((raw - a) * (yb - ya) + (b - a) / 2) / (b - a)) as i32
+}
+
+#[test]
+fn calibration_saturates_and_reverses() {
+    let forward = Calibration::new(1000, 3000).unwrap();
+    let backward = Calibration::new(3000, 1000).unwrap();
+    assert_eq!(forward.map(0), Poui(0));
+    assert_eq!(forward.map(u16::MAX), Poui(u16::MAX));
+    assert_eq!(backward.map(0), Poui(u16::MAX));
+    for raw in (900..3100).step_by(7) {
+        let mirrored = u16::MAX - forward.map(raw).0;
+        assert!(backward.map(raw).0.abs_diff(mirrored) <= 1);
+    }
+    let full = Calibration::new(0, u16::MAX).unwrap();
+    for raw in (0..=u16::MAX).step_by(11) {
+        assert_eq!(full.map(raw), Poui(raw));
+    }
+}
+
+#[test]
+fn calibration_center_and_dead_band() {
+    let stick = Calibration::new(0, 1000).unwrap().with_center(800).unwrap();
+    assert_eq!(stick.map(800), Poui(0x8000));
+    assert_eq!(stick.map(400), Poui(0x4000));
+    assert_eq!(stick.map(900), Poui(0xC000));
+    let stick = stick.with_dead_band(10).unwrap();
+    assert_eq!(stick.map(795), Poui(0x8000));
+    assert_eq!(stick.map(805), Poui(0x8000));
+    assert_eq!(stick.map(995), Poui(u16::MAX));
+    assert_eq!(stick.map(5), Poui(0));
+    let mut last = Poui(0);
+    for raw in 0..=1000 {
+        assert!(stick.map(raw) >= last);
+        last = stick.map(raw);
+    }
+}
+
+#[test]
+fn calibration_errors() {
+    assert_eq!(Calibration::new(5, 5), Err(CalibrationError::EmptyRange));
+    let c = Calibration::new(0, 100).unwrap();
+    assert_eq!(c.with_center(0), Err(CalibrationError::CenterOutOfRange));
+    assert_eq!(c.with_center(100), Err(CalibrationError::CenterOutOfRange));
+    assert_eq!(c.with_dead_band(50), Err(CalibrationError::EmptyRange));
+    assert!(c.with_dead_band(49).is_ok());
+    let centered = c.with_center(10).unwrap();
+    assert_eq!(
+        centered.with_dead_band(5),
+        Err(CalibrationError::EmptyRange)
+    );
+    assert_eq!(
+        Calibration::new(100, 0)
+            .unwrap()
+            .with_center(90)
+            .unwrap()
+            .map(90),
+        Poui(0x8000)
+    );
+}
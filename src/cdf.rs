@@ -0,0 +1,235 @@
+use crate::probability::sums_to_one;
+use crate::Poui;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+
+/// The error returned when the probabilities of a [`Cdf`] are invalid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CdfError {
+    /// There were no probabilities.
+    Empty,
+    /// The probabilities didn't sum to 1, to within one ULP per
+    /// probability.
+    NotNormalized,
+}
+
+impl std::fmt::Display for CdfError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CdfError::Empty => write!(f, "there are no probabilities"),
+            CdfError::NotNormalized => write!(f, "probabilities do not sum to one"),
+        }
+    }
+}
+
+impl std::error::Error for CdfError {}
+
+/// Replaces each value with the sum of it and every value before it,
+/// saturating at the top of the interval.
+///
+/// This turns weights, or the lengths of segments, into the running totals
+/// that a [`Cdf`], gradient stops, or an arc-length table search needs.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::cumsum;
+/// use poui::Poui;
+///
+/// let mut values = [Poui(64u8), Poui(32), Poui(128), Poui(64)];
+/// cumsum(&mut values);
+/// assert_eq!(values, [Poui(64), Poui(96), Poui(224), Poui(255)]);
+/// ```
+pub fn cumsum<N: PrimInt + Unsigned + WrappingAdd>(values: &mut [Poui<N>]) {
+    let mut sum = N::zero();
+    for p in values {
+        sum = sum.saturating_add(p.0);
+        p.0 = sum;
+    }
+}
+
+/// The running totals of the values, as by [`cumsum`], leaving the values
+/// themselves alone.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::cumulative;
+/// use poui::Poui;
+///
+/// let weights = [Poui(10u8); 4];
+/// assert_eq!(cumulative(&weights), [Poui(10), Poui(20), Poui(30), Poui(40)]);
+/// ```
+pub fn cumulative<N: PrimInt + Unsigned + WrappingAdd>(values: &[Poui<N>]) -> Vec<Poui<N>> {
+    let mut sums = values.to_vec();
+    cumsum(&mut sums);
+    sums
+}
+
+/// The cumulative distribution function of a discrete distribution, for
+/// sampling by inversion.
+///
+/// Outcome `i` owns the half-open slice of the unit interval from the sum of
+/// the probabilities before it to the sum including it, so
+/// [`Cdf::quantile`] maps a uniform `Poui` to an outcome with exactly the
+/// given probabilities. Since a `Poui` can't represent 1, probabilities that
+/// are each rounded may not sum to exactly 1, so any slack at the top of
+/// the interval goes to the last outcome with a nonzero probability.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Cdf;
+/// use poui::Poui;
+///
+/// // 1/2, 1/4, and 1/4, which sum to exactly 1.
+/// let cdf = Cdf::new(&[Poui(128u8), Poui(64), Poui(64)]).unwrap();
+/// assert_eq!(cdf.quantile(Poui(0)), 0);
+/// assert_eq!(cdf.quantile(Poui(127)), 0);
+/// assert_eq!(cdf.quantile(Poui(128)), 1);
+/// assert_eq!(cdf.quantile(Poui(255)), 2);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Cdf<N: PrimInt + Unsigned + WrappingAdd> {
+    /// The cumulative probability at the end of each outcome but the last,
+    /// saturating just below 1.
+    boundaries: Vec<Poui<N>>,
+    /// The last outcome with a nonzero probability.
+    last: usize,
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Cdf<N> {
+    /// Builds the distribution of the given probabilities, which must sum to
+    /// 1 up to rounding.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`CdfError::Empty`] if there are no probabilities, and
+    /// [`CdfError::NotNormalized`] if they are further from summing to 1
+    /// than one ULP per probability, which is how far rounding each of them
+    /// can take the sum.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Cdf;
+    /// use poui::CdfError;
+    /// use poui::Poui;
+    ///
+    /// // Thirds round down, and only sum to 255/256.
+    /// assert!(Cdf::new(&[Poui(85u8), Poui(85), Poui(85)]).is_ok());
+    /// assert_eq!(Cdf::new(&[Poui(85u8), Poui(85)]), Err(CdfError::NotNormalized));
+    /// assert_eq!(Cdf::<u8>::new(&[]), Err(CdfError::Empty));
+    /// ```
+    pub fn new(probabilities: &[Poui<N>]) -> Result<Self, CdfError> {
+        if probabilities.is_empty() {
+            return Err(CdfError::Empty);
+        }
+        if !sums_to_one(probabilities, probabilities.len() as u128) {
+            return Err(CdfError::NotNormalized);
+        }
+        let boundaries = cumulative(&probabilities[..probabilities.len() - 1]);
+        let last = probabilities
+            .iter()
+            .rposition(|p| p.0 != N::zero())
+            .unwrap_or(0);
+        Ok(Cdf { boundaries, last })
+    }
+
+    /// The number of outcomes.
+    pub fn len(&self) -> usize {
+        self.boundaries.len() + 1
+    }
+
+    /// Whether there are no outcomes, which a valid distribution always has.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// The cumulative probabilities at the end of every outcome but the last.
+    pub fn boundaries(&self) -> &[Poui<N>] {
+        &self.boundaries
+    }
+
+    /// The outcome whose slice of the unit interval contains `u`, found by
+    /// binary search.
+    ///
+    /// Feeding in uniformly distributed `u` samples the distribution. An
+    /// outcome with zero probability is never returned.
+    pub fn quantile(&self, u: Poui<N>) -> usize {
+        self.boundaries.partition_point(|&b| b <= u).min(self.last)
+    }
+
+    /// Samples an outcome at random, by feeding a uniform `Poui` to
+    /// [`Cdf::quantile`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Cdf;
+    /// use poui::Poui;
+    ///
+    /// let cdf = Cdf::new(&[Poui(0u16), Poui(0x8000), Poui(0x8000)]).unwrap();
+    /// assert_ne!(cdf.sample(&mut rand::thread_rng()), 0);
+    /// ```
+    #[cfg(feature = "rand")]
+    pub fn sample<R: rand::Rng + ?Sized>(&self, rng: &mut R) -> usize
+    where
+        rand::distributions::Standard: rand::distributions::Distribution<N>,
+    {
+        self.quantile(Poui(rng.gen()))
+    }
+}
+
+#[test]
+fn cdf_slices_match_probabilities() {
+    let probabilities = [Poui(10u8), Poui(0), Poui(100), Poui(146), Poui(0)];
+    let cdf = Cdf::new(&probabilities).unwrap();
+    let mut counts = [0u8; 5];
+    for u in 0..=255u8 {
+        counts[cdf.quantile(Poui(u))] += 1;
+    }
+    assert_eq!(counts, [10, 0, 100, 146, 0]);
+    assert_eq!(cdf.len(), 5);
+    assert_eq!(cdf.boundaries(), [Poui(10), Poui(10), Poui(110), Poui(255)]);
+}
+
+#[test]
+fn cdf_tolerates_rounding_only() {
+    let thirds = [Poui(u128::MAX / 3); 3];
+    assert!(Cdf::new(&thirds).is_ok());
+    let halves = [Poui(1u128 << 127); 2];
+    assert_eq!(Cdf::new(&halves).unwrap().quantile(Poui(u128::MAX)), 1);
+    assert!(Cdf::new(&[Poui(u128::MAX); 3]).is_err());
+    assert!(Cdf::new(&[Poui(86u8), Poui(86), Poui(86)]).is_ok());
+    assert!(Cdf::new(&[Poui(87u8), Poui(87), Poui(87)]).is_err());
+    assert_eq!(Cdf::new(&[Poui(255u8)]).unwrap().quantile(Poui(255)), 0);
+}
+
+#[test]
+fn cumsum_saturates_at_the_top() {
+    let mut values = [
+        Poui(u16::MAX / 2),
+        Poui(0),
+        Poui(u16::MAX / 2),
+        Poui(5),
+        Poui(9),
+    ];
+    let sums = cumulative(&values);
+    cumsum(&mut values);
+    assert_eq!(values, sums.as_slice());
+    assert_eq!(
+        sums,
+        [
+            Poui(32767),
+            Poui(32767),
+            Poui(65534),
+            Poui(65535),
+            Poui(65535)
+        ]
+    );
+    let mut empty: [Poui<u8>; 0] = [];
+    cumsum(&mut empty);
+    assert_eq!(cumulative::<u64>(&[]), []);
+}
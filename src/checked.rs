@@ -0,0 +1,149 @@
+use crate::policy::Checked;
+use crate::policy::OverflowPolicy;
+use crate::Poui;
+use crate::Shorten;
+use crate::Widen;
+use num_traits::Num;
+use num_traits::PrimInt;
+use num_traits::WrappingAdd;
+use num_traits::WrappingSub;
+
+/// The error returned when `CheckedPoui` arithmetic leaves the unit interval.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PouiOverflow;
+
+impl std::fmt::Display for PouiOverflow {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "arithmetic overflowed the unit interval")
+    }
+}
+
+impl std::error::Error for PouiOverflow {}
+
+/// A point on the unit interval whose arithmetic reports overflow instead of
+/// wrapping.
+///
+/// In code where silently wrapping around is a correctness bug, e.g. when
+/// adding up probabilities, `CheckedPoui` makes every operation that could
+/// leave the interval return a `Result`, so that overflow has to be handled
+/// explicitly. The `?` operator keeps longer expressions readable.
+///
+/// `CheckedPoui` converts to and from `Poui` for free. Its operators use the
+/// [`Checked`] policy.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::CheckedPoui;
+/// use poui::PouiOverflow;
+///
+/// fn total(a: CheckedPoui<u8>, b: CheckedPoui<u8>, c: CheckedPoui<u8>) -> Result<CheckedPoui<u8>, PouiOverflow> {
+///     (a + b)? + c
+/// }
+///
+/// assert_eq!(total(CheckedPoui(64), CheckedPoui(64), CheckedPoui(64)), Ok(CheckedPoui(192)));
+/// assert_eq!(total(CheckedPoui(64), CheckedPoui(128), CheckedPoui(64)), Err(PouiOverflow));
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[repr(transparent)]
+pub struct CheckedPoui<N: Num + WrappingAdd>(pub N);
+
+impl<N: Num + WrappingAdd> From<Poui<N>> for CheckedPoui<N> {
+    fn from(p: Poui<N>) -> Self {
+        CheckedPoui(p.0)
+    }
+}
+
+impl<N: Num + WrappingAdd> From<CheckedPoui<N>> for Poui<N> {
+    fn from(p: CheckedPoui<N>) -> Self {
+        Poui(p.0)
+    }
+}
+
+impl<N: PrimInt + WrappingAdd + WrappingSub> std::ops::Add for CheckedPoui<N> {
+    type Output = Result<Self, PouiOverflow>;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Checked::add(Poui(self.0), Poui(rhs.0)).map(CheckedPoui::from)
+    }
+}
+
+impl<N: PrimInt + WrappingAdd + WrappingSub> std::ops::Sub for CheckedPoui<N> {
+    type Output = Result<Self, PouiOverflow>;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Checked::sub(Poui(self.0), Poui(rhs.0)).map(CheckedPoui::from)
+    }
+}
+
+/// Multiplication of `CheckedPoui` values.
+///
+/// The product of two points on the unit interval is always on the unit
+/// interval, except for `-1 * -1` with signed backings, which is the only
+/// product that returns an error.
+impl<N, M> std::ops::Mul for CheckedPoui<N>
+where
+    N: PrimInt + WrappingAdd + Widen<Widened = M>,
+    M: PrimInt + WrappingAdd + Shorten<Shortened = N>,
+{
+    type Output = Result<Self, PouiOverflow>;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Checked::mul(Poui(self.0), Poui(rhs.0)).map(CheckedPoui::from)
+    }
+}
+
+#[test]
+fn checked_add() {
+    assert_eq!(
+        CheckedPoui(100u8) + CheckedPoui(155u8),
+        Ok(CheckedPoui(255u8))
+    );
+    assert_eq!(CheckedPoui(100u8) + CheckedPoui(156u8), Err(PouiOverflow));
+    assert_eq!(
+        CheckedPoui(i16::MIN) + CheckedPoui(-1i16),
+        Err(PouiOverflow)
+    );
+}
+
+#[test]
+fn checked_sub() {
+    assert_eq!(CheckedPoui(5u32) - CheckedPoui(5u32), Ok(CheckedPoui(0u32)));
+    assert_eq!(CheckedPoui(5u32) - CheckedPoui(6u32), Err(PouiOverflow));
+    assert_eq!(
+        CheckedPoui(-100i8) - CheckedPoui(28i8),
+        Ok(CheckedPoui(i8::MIN))
+    );
+    assert_eq!(CheckedPoui(-100i8) - CheckedPoui(29i8), Err(PouiOverflow));
+}
+
+#[test]
+fn checked_mul() {
+    assert_eq!(
+        CheckedPoui(128u8) * CheckedPoui(255u8),
+        Ok(CheckedPoui(127u8))
+    );
+    assert_eq!(
+        CheckedPoui(i8::MIN) * CheckedPoui(64i8),
+        Ok(CheckedPoui(-64i8))
+    );
+    assert_eq!(
+        CheckedPoui(i8::MIN) * CheckedPoui(i8::MIN),
+        Err(PouiOverflow)
+    );
+}
+
+#[test]
+fn checked_chains_with_question_mark() {
+    fn sum(values: &[CheckedPoui<u16>]) -> Result<CheckedPoui<u16>, PouiOverflow> {
+        values.iter().try_fold(CheckedPoui(0), |acc, &v| acc + v)
+    }
+    assert_eq!(sum(&[CheckedPoui(1 << 14); 3]), Ok(CheckedPoui(3 << 14)));
+    assert_eq!(sum(&[CheckedPoui(1 << 14); 4]), Err(PouiOverflow));
+}
+
+#[test]
+fn checked_converts_to_and_from_poui() {
+    assert_eq!(CheckedPoui::from(Poui(7i64)), CheckedPoui(7i64));
+    assert_eq!(Poui::from(CheckedPoui(7i64)), Poui(7i64));
+}
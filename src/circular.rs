@@ -0,0 +1,377 @@
+use crate::frac_bits;
+use crate::trig::cordic_atan2;
+use crate::trig::cordic_cos_sin;
+use crate::trig::from_turn_bits;
+use crate::trig::normalize;
+use crate::trig::turn_bits;
+use crate::Poui;
+use crate::StrictWiden;
+use num_traits::AsPrimitive;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+use num_traits::WrappingSub;
+
+/// Which way to travel around the circle between two angles.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TurnDirection {
+    /// Whichever way is shorter, going backwards when the angles are exactly
+    /// opposite, like [`Poui::signed_delta`].
+    #[default]
+    Shortest,
+    /// Forwards, i.e. with increasing values, wrapping past 1 back to 0.
+    Forward,
+    /// Backwards, i.e. with decreasing values, wrapping past 0 back to 1.
+    Backward,
+}
+
+impl<N, M> Poui<N>
+where
+    N: PrimInt + Unsigned + WrappingAdd + WrappingSub + StrictWiden<Widened = M> + 'static,
+    M: PrimInt + AsPrimitive<N>,
+{
+    /// Interpolates between the angles `self` and `other` along the shorter
+    /// arc between them.
+    ///
+    /// Unlike [`Poui::lerp`], this treats the values as fractions of a turn,
+    /// so interpolating between 0.9 and 0.1 passes through 0 rather than 0.5.
+    /// The result is rounded towards `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// let a = Poui(230u8);
+    /// let b = Poui(26u8);
+    /// assert_eq!(a.lerp_circular(b, Poui(128u8)), Poui(0u8));
+    /// assert_eq!(b.lerp_circular(a, Poui(64u8)), Poui(13u8));
+    /// ```
+    pub fn lerp_circular(self, other: Self, t: Self) -> Self {
+        self.lerp_circular_in(other, t, TurnDirection::Shortest)
+    }
+
+    /// Interpolates between the angles `self` and `other`, travelling around
+    /// the circle in the given direction.
+    ///
+    /// This allows deliberately taking the long way around, e.g. to sweep a
+    /// hue through the whole spectrum. If `self == other` the arc has zero
+    /// length in every direction, so the result is always `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    /// use poui::TurnDirection;
+    ///
+    /// let a = Poui(230u8);
+    /// let b = Poui(26u8);
+    /// assert_eq!(a.lerp_circular_in(b, Poui(128u8), TurnDirection::Forward), Poui(0u8));
+    /// assert_eq!(a.lerp_circular_in(b, Poui(128u8), TurnDirection::Backward), Poui(128u8));
+    /// ```
+    pub fn lerp_circular_in(self, other: Self, t: Self, direction: TurnDirection) -> Self {
+        let forward = other.0.wrapping_sub(&self.0);
+        let half = N::one() << (frac_bits::<N>() - 1);
+        let go_forward = match direction {
+            TurnDirection::Shortest => forward < half,
+            TurnDirection::Forward => true,
+            TurnDirection::Backward => false,
+        };
+        let scale =
+            |distance: N| -> N { ((distance.widen() * t.0.widen()) >> frac_bits::<N>()).as_() };
+        if go_forward {
+            Poui(self.0.wrapping_add(&scale(forward)))
+        } else {
+            Poui(self.0.wrapping_sub(&scale(self.0.wrapping_sub(&other.0))))
+        }
+    }
+}
+
+/// The sum of the unit vectors pointing at each angle, in Q62.
+pub(crate) fn resultant<N: PrimInt + Unsigned + WrappingAdd>(angles: &[Poui<N>]) -> (i128, i128) {
+    angles.iter().fold((0, 0), |(x, y), angle| {
+        let (cos, sin) = cordic_cos_sin(turn_bits(angle.0));
+        (x + cos as i128, y + sin as i128)
+    })
+}
+
+/// Whether a resultant of `count` unit vectors is zero, allowing for the
+/// rounding error in each CORDIC evaluation.
+pub(crate) fn is_degenerate((x, y): (i128, i128), count: usize) -> bool {
+    let tolerance = (count as i128) << 8;
+    x.abs() <= tolerance && y.abs() <= tolerance
+}
+
+/// `2 ln 2` in Q62.
+const TWO_LN_2: u128 = 6393154322601327830;
+
+/// `1 / 2π` in Q62.
+const INV_TAU: u128 = 733972625820500307;
+
+/// The length of the mean of `count` unit vectors with the given sum, in Q62.
+fn mean_length((x, y): (i128, i128), count: usize) -> u128 {
+    let (x, y) = (x / count as i128, y / count as i128);
+    ((x * x) as u128 + (y * y) as u128).isqrt()
+}
+
+/// Computes `-log2(r)` in Q62 for `r` in (0, 1] in Q62.
+fn neg_log2(r: u128) -> u128 {
+    let shift = r.leading_zeros() - 65;
+    let mut m = r << shift;
+    let mut log = 0;
+    for _ in 0..62 {
+        m = (m * m) >> 62;
+        log <<= 1;
+        if m >= 2 << 62 {
+            m >>= 1;
+            log |= 1;
+        }
+    }
+    ((shift as u128) << 62) - log
+}
+
+/// Rounds a fixed-point value with `q` fractional bits to a `Poui`,
+/// saturating at the top of the interval.
+fn saturating_fraction<N: PrimInt + Unsigned>(v: u128, q: usize) -> N {
+    let bits = frac_bits::<N>();
+    if v >= 1 << q {
+        return N::max_value();
+    }
+    let scaled = if bits >= q {
+        v << (bits - q)
+    } else {
+        (v + (1 << (q - bits - 1))) >> (q - bits)
+    };
+    N::from(scaled).unwrap_or(N::max_value())
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Poui<N> {
+    /// The circular mean of a collection of angles.
+    ///
+    /// This is the direction of the sum of the unit vectors pointing at each
+    /// angle, which unlike the arithmetic mean is unaffected by where the
+    /// circle wraps: the mean of 0.9 and 0.1 of a turn is 0, not 0.5. The
+    /// vectors are computed and summed in fixed point with
+    /// [`Poui::sin_cos`]'s CORDIC routine, and the direction recovered with
+    /// [`Poui::atan2`].
+    ///
+    /// Returns `None` if `angles` is empty or the vectors cancel out, e.g. for
+    /// two opposite angles, in which case no direction is preferred.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// let winds = [Poui(0xF000u16), Poui(0x1000u16), Poui(0x0400u16), Poui(0xFC00u16)];
+    /// assert_eq!(Poui::circular_mean(&winds), Some(Poui(0u16)));
+    /// assert_eq!(Poui::circular_mean(&[Poui(0x2000u16), Poui(0xA000u16)]), None);
+    /// assert_eq!(Poui::<u16>::circular_mean(&[]), None);
+    /// ```
+    pub fn circular_mean(angles: &[Self]) -> Option<Self> {
+        let sum = resultant(angles);
+        if is_degenerate(sum, angles.len()) {
+            return None;
+        }
+        let (y, x) = normalize(sum.1, sum.0);
+        Some(Poui(from_turn_bits(cordic_atan2(y, x))))
+    }
+
+    /// The mean resultant length of a collection of angles.
+    ///
+    /// This is the length of the mean of the unit vectors pointing at each
+    /// angle: close to 1 when the angles are tightly clustered, and close to 0
+    /// when they are spread evenly around the circle. Since 1 isn't
+    /// representable, identical angles give the largest `Poui` instead.
+    ///
+    /// Returns `None` if `angles` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// let same = [Poui(0x1234u16); 3];
+    /// assert_eq!(Poui::mean_resultant_length(&same), Some(Poui(u16::MAX)));
+    /// let opposite = [Poui(0x1234u16), Poui(0x9234u16)];
+    /// assert_eq!(Poui::mean_resultant_length(&opposite), Some(Poui(0u16)));
+    /// ```
+    pub fn mean_resultant_length(angles: &[Self]) -> Option<Self> {
+        if angles.is_empty() {
+            return None;
+        }
+        let length = mean_length(resultant(angles), angles.len());
+        Some(Poui(saturating_fraction(length, 62)))
+    }
+
+    /// The circular variance of a collection of angles.
+    ///
+    /// This is one minus the [mean resultant
+    /// length](Poui::mean_resultant_length), ranging from 0 for identical
+    /// angles to 1, saturated to the largest `Poui`, for angles spread evenly
+    /// around the circle.
+    ///
+    /// Returns `None` if `angles` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui::circular_variance(&[Poui(7u8); 4]), Some(Poui(0u8)));
+    /// assert_eq!(Poui::circular_variance(&[Poui(0u8), Poui(128u8)]), Some(Poui(255u8)));
+    /// ```
+    pub fn circular_variance(angles: &[Self]) -> Option<Self> {
+        if angles.is_empty() {
+            return None;
+        }
+        let length = mean_length(resultant(angles), angles.len()).min(1 << 62);
+        Some(Poui(saturating_fraction((1 << 62) - length, 62)))
+    }
+
+    /// The circular standard deviation of a collection of angles, as a
+    /// fraction of a turn.
+    ///
+    /// This is `sqrt(-2 ln R)` radians for a mean resultant length `R`, which
+    /// for tightly clustered angles approximates the ordinary standard
+    /// deviation. It grows without bound as the angles spread out, so it
+    /// saturates to the largest `Poui` at a full turn, including when the
+    /// vectors cancel out. The logarithm and square root are computed in
+    /// fixed point with 52 fractional bits.
+    ///
+    /// Returns `None` if `angles` is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui::circular_std_dev(&[Poui(0x8000u16); 2]), Some(Poui(0u16)));
+    /// let spread = [Poui(0xFF00u16), Poui(0x0100u16)];
+    /// assert_eq!(Poui::circular_std_dev(&spread), Some(Poui(0x0100u16)));
+    /// ```
+    pub fn circular_std_dev(angles: &[Self]) -> Option<Self> {
+        if angles.is_empty() {
+            return None;
+        }
+        let length = mean_length(resultant(angles), angles.len()).min(1 << 62);
+        if length == 0 {
+            return Some(Poui(N::max_value()));
+        }
+        let log = ((neg_log2(length) >> 10) * TWO_LN_2) >> 62;
+        let radians = (log << 52).isqrt();
+        Some(Poui(saturating_fraction((radians * INV_TAU) >> 62, 52)))
+    }
+}
+
+#[test]
+fn lerp_circular_takes_shorter_arc() {
+    assert_eq!(Poui(10u8).lerp_circular(Poui(50u8), Poui(64u8)), Poui(20u8));
+    assert_eq!(Poui(50u8).lerp_circular(Poui(10u8), Poui(64u8)), Poui(40u8));
+    assert_eq!(
+        Poui(250u8).lerp_circular(Poui(10u8), Poui(192u8)),
+        Poui(6u8)
+    );
+    assert_eq!(
+        Poui(10u8).lerp_circular(Poui(250u8), Poui(192u8)),
+        Poui(254u8)
+    );
+    assert_eq!(
+        Poui(0u8).lerp_circular(Poui(128u8), Poui(128u8)),
+        Poui(192u8)
+    );
+}
+
+#[test]
+fn lerp_circular_endpoints() {
+    for (a, b) in [(0u16, 0xFFFFu16), (0x1234, 0x9876), (0xF000, 0x0100)] {
+        for direction in [
+            TurnDirection::Shortest,
+            TurnDirection::Forward,
+            TurnDirection::Backward,
+        ] {
+            let (a, b) = (Poui(a), Poui(b));
+            assert_eq!(a.lerp_circular_in(b, Poui(0), direction), a);
+            let near_end = a.lerp_circular_in(b, Poui(u16::MAX), direction);
+            assert!(near_end.signed_delta(b).0.abs() <= 1, "{a:?} {b:?}");
+        }
+    }
+}
+
+#[test]
+fn lerp_circular_long_way() {
+    let a = Poui(0x1000_0000u32);
+    let b = Poui(0xF000_0000u32);
+    assert_eq!(a.lerp_circular(b, Poui(1 << 31)), Poui(0));
+    assert_eq!(
+        a.lerp_circular_in(b, Poui(1 << 31), TurnDirection::Forward),
+        Poui(1 << 31)
+    );
+    assert_eq!(
+        a.lerp_circular_in(a, Poui(1 << 31), TurnDirection::Forward),
+        a
+    );
+}
+
+#[test]
+fn circular_mean_across_wrap() {
+    let angles = [Poui(250u8), Poui(0u8), Poui(6u8), Poui(8u8)];
+    assert_eq!(Poui::circular_mean(&angles), Some(Poui(2u8)));
+    assert_eq!(Poui::circular_mean(&[Poui(100u8)]), Some(Poui(100u8)));
+}
+
+#[test]
+fn circular_mean_matches_float() {
+    let angles: Vec<Poui<u32>> = (0..50u32)
+        .map(|k| Poui(k.wrapping_mul(0x0123_4567) >> 2))
+        .collect();
+    let (x, y) = angles.iter().fold((0.0, 0.0), |(x, y), a| {
+        let radians = a.0 as f64 / 4294967296.0 * std::f64::consts::TAU;
+        (x + radians.cos(), y + radians.sin())
+    });
+    let expected = f64::atan2(y, x) / std::f64::consts::TAU * 4294967296.0;
+    let actual = Poui::circular_mean(&angles).unwrap().0;
+    assert!(
+        (actual as f64 - expected).abs() < 4.0,
+        "{actual} {expected}"
+    );
+}
+
+#[test]
+fn circular_mean_degenerate() {
+    let eighths: Vec<Poui<u8>> = (0..8).map(|k| Poui(k * 32)).collect();
+    assert_eq!(Poui::circular_mean(&eighths), None);
+    let quarters = [0u64, 1 << 62, 2 << 62, 3 << 62].map(Poui);
+    assert_eq!(Poui::circular_mean(&quarters), None);
+}
+
+#[test]
+fn resultant_statistics_match_float() {
+    let angles: Vec<Poui<u32>> = (0..20u32)
+        .map(|k| Poui(k.wrapping_mul(0x0345_6789) >> 3))
+        .collect();
+    let (x, y) = angles.iter().fold((0.0, 0.0), |(x, y), a| {
+        let radians = a.0 as f64 / 4294967296.0 * std::f64::consts::TAU;
+        (x + radians.cos(), y + radians.sin())
+    });
+    let r = (x * x + y * y).sqrt() / angles.len() as f64;
+    let std_dev = (-2.0 * r.ln()).sqrt() / std::f64::consts::TAU;
+    let close = |actual: Option<Poui<u32>>, expected: f64| {
+        let actual = actual.unwrap().0 as f64 / 4294967296.0;
+        assert!((actual - expected).abs() < 1e-8, "{actual} {expected}");
+    };
+    close(Poui::mean_resultant_length(&angles), r);
+    close(Poui::circular_variance(&angles), 1.0 - r);
+    close(Poui::circular_std_dev(&angles), std_dev);
+}
+
+#[test]
+fn resultant_statistics_extremes() {
+    assert_eq!(Poui::<u64>::mean_resultant_length(&[]), None);
+    assert_eq!(Poui::<u64>::circular_variance(&[]), None);
+    assert_eq!(Poui::<u64>::circular_std_dev(&[]), None);
+    let quarters = [0u64, 1 << 62, 2 << 62, 3 << 62].map(Poui);
+    assert_eq!(Poui::circular_std_dev(&quarters), Some(Poui(u64::MAX)));
+    assert_eq!(Poui::circular_variance(&quarters), Some(Poui(u64::MAX)));
+    assert_eq!(Poui::circular_std_dev(&[Poui(5u128); 3]), Some(Poui(0)));
+}
@@ -0,0 +1,287 @@
+//! Compass headings.
+//!
+//! A `Poui<u16>` makes a natural heading: a fraction of a turn measured
+//! clockwise from north, with a resolution of about 0.0055°, and all the
+//! wrap-around arithmetic for free. This module adds the navigation helpers
+//! on top: naming and bucketing headings into sectors, with hysteresis for
+//! noisy sensors, displaying them in degrees, and relative bearings.
+//!
+//! ```rust
+//! use poui::compass::Cardinal;
+//! use poui::compass::Degrees;
+//! use poui::Poui;
+//!
+//! let heading = Poui(0x6000u16);
+//! assert_eq!(Cardinal::from_heading(heading), Cardinal::SouthEast);
+//! assert_eq!(format!("{}", Degrees(heading)), "135°");
+//! ```
+
+use crate::Poui;
+use std::fmt;
+
+/// The sector containing `heading`, when the compass is divided into
+/// `sectors` equal sectors with sector 0 centered on north.
+///
+/// Each sector extends half its width either side of its center, and
+/// headings exactly on a boundary go to the sector clockwise of it.
+///
+/// # Panics
+///
+/// Panics if `sectors` is zero.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::compass::sector;
+/// use poui::Poui;
+///
+/// assert_eq!(sector(Poui(0xFF00), 4), 0);
+/// assert_eq!(sector(Poui(0x2000), 4), 1);
+/// assert_eq!(sector(Poui(0x4000), 4), 1);
+/// assert_eq!(sector(Poui(0x9000), 4), 2);
+/// ```
+pub fn sector(heading: Poui<u16>, sectors: u16) -> u16 {
+    assert!(sectors != 0, "compass needs at least one sector");
+    let scaled = heading.0 as u32 * sectors as u32 + 0x8000;
+    ((scaled >> 16) % sectors as u32) as u16
+}
+
+/// The heading at the center of sector `index` of `sectors`.
+pub fn sector_center(index: u16, sectors: u16) -> Poui<u16> {
+    assert!(sectors != 0, "compass needs at least one sector");
+    Poui(((index as u32 % sectors as u32) * 0x1_0000 / sectors as u32) as u16)
+}
+
+/// The heading pointing the opposite way.
+pub fn reciprocal(heading: Poui<u16>) -> Poui<u16> {
+    heading + Poui(0x8000)
+}
+
+/// The bearing of `target` relative to `heading`: how far to turn, and
+/// which way, to face it.
+///
+/// Positive values are clockwise. This is [`Poui::signed_delta`], so the
+/// result counts half-turns: `Poui(0x4000i16)` is a quarter turn to the
+/// right.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::compass::relative_bearing;
+/// use poui::Poui;
+///
+/// assert_eq!(relative_bearing(Poui(0xF000), Poui(0x1000)), Poui(0x2000));
+/// assert_eq!(relative_bearing(Poui(0x1000), Poui(0xF000)), Poui(-0x2000));
+/// ```
+pub fn relative_bearing(heading: Poui<u16>, target: Poui<u16>) -> Poui<i16> {
+    heading.signed_delta(target)
+}
+
+/// The eight principal compass points.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum Cardinal {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Cardinal {
+    /// All the compass points, clockwise from north.
+    pub const ALL: [Cardinal; 8] = [
+        Cardinal::North,
+        Cardinal::NorthEast,
+        Cardinal::East,
+        Cardinal::SouthEast,
+        Cardinal::South,
+        Cardinal::SouthWest,
+        Cardinal::West,
+        Cardinal::NorthWest,
+    ];
+
+    /// The compass point nearest to `heading`.
+    pub fn from_heading(heading: Poui<u16>) -> Self {
+        Cardinal::ALL[sector(heading, 8) as usize]
+    }
+
+    /// The heading the compass point names.
+    pub fn heading(self) -> Poui<u16> {
+        sector_center(self as u16, 8)
+    }
+
+    /// The usual abbreviation, e.g. `"NE"`.
+    pub fn abbreviation(self) -> &'static str {
+        match self {
+            Cardinal::North => "N",
+            Cardinal::NorthEast => "NE",
+            Cardinal::East => "E",
+            Cardinal::SouthEast => "SE",
+            Cardinal::South => "S",
+            Cardinal::SouthWest => "SW",
+            Cardinal::West => "W",
+            Cardinal::NorthWest => "NW",
+        }
+    }
+}
+
+impl fmt::Display for Cardinal {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.pad(self.abbreviation())
+    }
+}
+
+/// Buckets a stream of headings into sectors, with hysteresis.
+///
+/// A noisy heading that hovers near a sector boundary would flicker between
+/// the two sectors if bucketed with [`sector`] alone. The tracker instead
+/// stays in its current sector until the heading is more than `hysteresis`
+/// past the sector's edge.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::compass::SectorTracker;
+/// use poui::Poui;
+///
+/// let mut tracker = SectorTracker::new(4, Poui(0x0400));
+/// assert_eq!(tracker.update(Poui(0x1F00)), 0);
+/// assert_eq!(tracker.update(Poui(0x2100)), 0);
+/// assert_eq!(tracker.update(Poui(0x1F00)), 0);
+/// assert_eq!(tracker.update(Poui(0x2500)), 1);
+/// assert_eq!(tracker.update(Poui(0x1F00)), 1);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SectorTracker {
+    sectors: u16,
+    hysteresis: Poui<u16>,
+    current: Option<u16>,
+}
+
+impl SectorTracker {
+    /// A tracker dividing the compass into `sectors` sectors as for
+    /// [`sector`], requiring headings to go `hysteresis` past an edge to
+    /// change sector.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sectors` is zero.
+    pub fn new(sectors: u16, hysteresis: Poui<u16>) -> Self {
+        assert!(sectors != 0, "compass needs at least one sector");
+        SectorTracker {
+            sectors,
+            hysteresis,
+            current: None,
+        }
+    }
+
+    /// The current sector, or `None` before the first update.
+    pub fn current(&self) -> Option<u16> {
+        self.current
+    }
+
+    /// Updates the tracker with a new heading, and returns the sector it's in.
+    pub fn update(&mut self, heading: Poui<u16>) -> u16 {
+        let nearest = sector(heading, self.sectors);
+        let sector = match self.current {
+            Some(current) if current != nearest => {
+                let center = sector_center(current, self.sectors);
+                let offset = heading.signed_delta(center).0.unsigned_abs() as u32;
+                let half_width = 0x8000 / self.sectors as u32;
+                if offset > half_width + self.hysteresis.0 as u32 {
+                    nearest
+                } else {
+                    current
+                }
+            }
+            _ => nearest,
+        };
+        self.current = Some(sector);
+        sector
+    }
+}
+
+/// Displays a heading in degrees, e.g. `"045°"`.
+///
+/// The value is rounded to the formatter's precision, which defaults to
+/// whole degrees and is at most 12 decimal places, and zero-padded to three
+/// digits before the decimal point.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::compass::Degrees;
+/// use poui::Poui;
+///
+/// assert_eq!(format!("{}", Degrees(Poui(0x2000))), "045°");
+/// assert_eq!(format!("{:.2}", Degrees(Poui(0x1234))), "025.60°");
+/// assert_eq!(format!("{}", Degrees(Poui(0xFFFF))), "000°");
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Degrees(pub Poui<u16>);
+
+impl fmt::Display for Degrees {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let precision = f.precision().unwrap_or(0).min(12);
+        // At 12 digits the scaled heading no longer fits in 64 bits.
+        let scale = 10u128.pow(precision as u32);
+        let full = 360 * scale;
+        let value = ((self.0 .0 as u128 * full + 0x8000) >> 16) % full;
+        let whole = value / scale;
+        if precision == 0 {
+            write!(f, "{whole:03}°")
+        } else {
+            let fraction = value % scale;
+            write!(f, "{whole:03}.{fraction:0precision$}°")
+        }
+    }
+}
+
+#[test]
+fn sectors_are_centered() {
+    for sectors in [1u16, 3, 8, 16, 360] {
+        for index in 0..sectors {
+            assert_eq!(sector(sector_center(index, sectors), sectors), index);
+        }
+    }
+    assert_eq!(sector(Poui(0xF000), 8), 0);
+    assert_eq!(sector(Poui(0xEFFF), 8), 7);
+}
+
+#[test]
+fn cardinal_points() {
+    for point in Cardinal::ALL {
+        assert_eq!(Cardinal::from_heading(point.heading()), point);
+    }
+    assert_eq!(Cardinal::from_heading(Poui(0xC800)), Cardinal::West);
+    assert_eq!(format!("{:>3}", Cardinal::SouthWest), " SW");
+}
+
+#[test]
+fn sector_tracker_wraps_around_north() {
+    let mut tracker = SectorTracker::new(8, Poui(0x0200));
+    assert_eq!(tracker.current(), None);
+    assert_eq!(tracker.update(Poui(0x0100)), 0);
+    assert_eq!(tracker.update(Poui(0xEE00)), 0);
+    assert_eq!(tracker.update(Poui(0xED00)), 7);
+    assert_eq!(tracker.update(Poui(0xF100)), 7);
+    assert_eq!(tracker.update(Poui(0x8000)), 4);
+}
+
+#[test]
+fn bearings() {
+    assert_eq!(reciprocal(Poui(0x1000)), Poui(0x9000));
+    assert_eq!(relative_bearing(Poui(0), Poui(0x8000)), Poui(i16::MIN));
+    assert_eq!(format!("{:.1}", Degrees(Poui(0x8000))), "180.0°");
+    assert_eq!(
+        format!("{:.12}", Degrees(Poui(0xFFFF))),
+        "359.994506835938°"
+    );
+    assert_eq!(
+        format!("{:.20}", Degrees(Poui(0x0001))),
+        "000.005493164063°"
+    );
+}
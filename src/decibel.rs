@@ -0,0 +1,96 @@
+use crate::easing::exp2;
+use crate::easing::from_q62;
+use crate::frac_bits;
+use crate::probability::log2_q62;
+use crate::probability::LOG2_10;
+use crate::Mixed;
+use crate::Poui;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Poui<N> {
+    /// The amplitude gain of `db` decibels, `10^(db / 20)`, where `db` is a
+    /// signed 32.32 fixed-point number.
+    ///
+    /// This is how faders and meters label gains: -6 dB is about half the
+    /// amplitude, and every further -20 dB is another factor of ten. The
+    /// power of ten is computed as a power of two in Q62, and rounded to the
+    /// nearest `Poui`. Gains of 0 dB and above saturate just below 1, and
+    /// gains too small to represent round to 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Mixed;
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui::<u16>::from_db(Mixed::from_int(-20)), Poui(6554));
+    /// assert_eq!(Poui::<u16>::from_db(Mixed::from_int(-96)), Poui(1));
+    /// assert_eq!(Poui::<u16>::from_db(Mixed::from_int(0)), Poui(u16::MAX));
+    /// ```
+    pub fn from_db(db: Mixed<i32, u32>) -> Self {
+        let db = ((db.int as i128) << 32) + db.frac.0 as i128;
+        if db >= 0 {
+            return Poui(N::max_value());
+        }
+        // 10^(db / 20) = 2^(db · log2(10) / 20).
+        let exponent = ((db * LOG2_10) >> 32) / 20;
+        from_q62(exp2(exponent))
+    }
+
+    /// The gain `self` in decibels, `20 · log10(self)`, as a signed 32.32
+    /// fixed-point number.
+    ///
+    /// This is the inverse of [`Poui::from_db`], to within a few ULPs of the
+    /// 32-bit fraction. Every gain is below 1, so the result is negative.
+    /// A gain of 0 is minus infinity decibels, which is represented by the
+    /// smallest `Mixed`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Mixed;
+    /// use poui::Poui;
+    ///
+    /// let half = Poui(0x8000u16).to_db();
+    /// assert_eq!(half.int, -7);
+    /// assert_eq!(half.frac.0 >> 24, 0xFA); // -6.0206 dB
+    /// assert_eq!(Poui(0u16).to_db(), Mixed::new(i32::MIN, Poui(0)));
+    /// ```
+    pub fn to_db(self) -> Mixed<i32, u32> {
+        if self.0 == N::zero() {
+            return Mixed::new(i32::MIN, Poui(0));
+        }
+        let bits = frac_bits::<N>() as i128;
+        let log2 = log2_q62(self.0.to_u128().unwrap()) - (bits << 62);
+        // 20 · log10(x) = 20 · log2(x) / log2(10), rounded to Q32.
+        let scaled = (log2 * 20) << 32;
+        let v = (scaled - LOG2_10 / 2) / LOG2_10;
+        Mixed::new((v >> 32) as i32, Poui(v as u32))
+    }
+}
+
+#[test]
+fn from_db_matches_float() {
+    for tenths in -960..0 {
+        let db = tenths as f64 / 10.0;
+        let gain = Poui::<u32>::from_db(Mixed::from_f64(db).unwrap());
+        let expected = 10f64.powf(db / 20.0) * 4294967296.0;
+        assert!((gain.0 as f64 - expected).abs() <= 1.0, "{db}");
+    }
+    assert_eq!(Poui::<u8>::from_db(Mixed::from_int(-60)), Poui(0));
+    assert_eq!(Poui::<u64>::from_db(Mixed::from_int(12)), Poui(u64::MAX));
+}
+
+#[test]
+fn to_db_inverts_from_db() {
+    for x in [1u32, 0x0001_0000, 0x1234_5678, 0x8000_0000, u32::MAX] {
+        let db = Poui(x).to_db();
+        let actual = db.int as f64 + db.frac.0 as f64 / 4294967296.0;
+        let expected = 20.0 * (x as f64 / 4294967296.0).log10();
+        assert!((actual - expected).abs() < 1e-8, "{x}");
+        let back = Poui::<u32>::from_db(db).0;
+        assert!(back.abs_diff(x) <= 1 + x / (1 << 26), "{x} {back}");
+    }
+}
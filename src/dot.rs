@@ -0,0 +1,151 @@
+use crate::frac_bits;
+use crate::Poui;
+use crate::StrictWiden;
+use num_traits::AsPrimitive;
+use num_traits::PrimInt;
+use num_traits::WrappingAdd;
+
+/// The dot product of two slices, at the full precision of the widened
+/// type.
+///
+/// Each product of a pair of `Poui`s fits the widened type exactly, so the
+/// result only rounds if it saturates: the sum is clamped to the range of
+/// the widened `Poui`, [0, 1) for unsigned backings and [-1, 1) for signed
+/// ones. Keeping the full precision lets the result be scaled or summed
+/// further before it is finally rounded.
+///
+/// # Panics
+///
+/// Panics if the slices have different lengths.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::dot;
+/// use poui::Poui;
+///
+/// let a = [Poui(0x80u8), Poui(0x40)];
+/// let b = [Poui(0x40u8), Poui(0x20)];
+/// assert_eq!(dot(&a, &b), Poui(0x2800u16));
+///
+/// let signed = [Poui(0x40i8), Poui(-0x40)];
+/// assert_eq!(dot(&signed, &[Poui(0x40), Poui(0x20)]), Poui(0x1000i16));
+/// assert_eq!(dot(&[Poui(0xFFu8); 2], &[Poui(0xFF); 2]), Poui(u16::MAX));
+/// ```
+pub fn dot<N, M>(a: &[Poui<N>], b: &[Poui<N>]) -> Poui<M>
+where
+    N: PrimInt + WrappingAdd + StrictWiden<Widened = M> + 'static,
+    M: PrimInt + WrappingAdd + AsPrimitive<N>,
+{
+    assert_eq!(a.len(), b.len(), "slices differ in length");
+    let sum = accumulate(a.iter().zip(b));
+    // The products have twice the fractional bits of `N`, which for signed
+    // backings is one fewer than the widened `Poui` has.
+    let shift = frac_bits::<M>() - 2 * frac_bits::<N>();
+    let limit = M::max_value() >> shift;
+    Poui(if sum > limit {
+        M::max_value()
+    } else if sum < M::min_value() >> shift {
+        M::min_value()
+    } else {
+        sum << shift
+    })
+}
+
+/// The full convolution of `signal` with `kernel`, each output rounded to
+/// the nearest `Poui`.
+///
+/// The output has `signal.len() + kernel.len() - 1` values, the `n`th being
+/// the sum of `signal[n - k] · kernel[k]` over all `k` in range. Each sum
+/// is accumulated exactly in the widened type and rounded once, saturating
+/// at the ends of the interval, as in [`Fir`](crate::Fir). If either input
+/// is empty, so is the output.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::convolve;
+/// use poui::Poui;
+///
+/// // Blurring an impulse spreads it out by the kernel.
+/// let kernel = [Poui(0x40u8), Poui(0x80), Poui(0x40)];
+/// let blurred = convolve(&[Poui(0), Poui(0xFF), Poui(0)], &kernel);
+/// assert_eq!(blurred, [Poui(0), Poui(0x40), Poui(0x80), Poui(0x40), Poui(0)]);
+/// ```
+pub fn convolve<N, M>(signal: &[Poui<N>], kernel: &[Poui<N>]) -> Vec<Poui<N>>
+where
+    N: PrimInt + WrappingAdd + StrictWiden<Widened = M> + 'static,
+    M: PrimInt + WrappingAdd + AsPrimitive<N>,
+{
+    if signal.is_empty() || kernel.is_empty() {
+        return Vec::new();
+    }
+    let bits = frac_bits::<N>();
+    let (min, max) = (N::min_value().widen(), N::max_value().widen());
+    (0..signal.len() + kernel.len() - 1)
+        .map(|n| {
+            let first = n.saturating_sub(signal.len() - 1);
+            let last = n.min(kernel.len() - 1);
+            let pairs = (first..=last).map(|k| (&signal[n - k], &kernel[k]));
+            let sum = saturating_add(accumulate(pairs), M::one() << (bits - 1));
+            Poui((sum >> bits).clamp(min, max).as_())
+        })
+        .collect()
+}
+
+/// Sums the exact products of the pairs in the widened type, saturating.
+fn accumulate<'a, N, M>(pairs: impl Iterator<Item = (&'a Poui<N>, &'a Poui<N>)>) -> M
+where
+    N: PrimInt + WrappingAdd + StrictWiden<Widened = M> + 'static,
+    M: PrimInt,
+{
+    pairs.fold(M::zero(), |sum, (a, b)| {
+        saturating_add(sum, a.0.widen() * b.0.widen())
+    })
+}
+
+/// Adds two integers, saturating at either end of their range.
+fn saturating_add<M: PrimInt>(a: M, b: M) -> M {
+    a.checked_add(&b).unwrap_or(if b > M::zero() {
+        M::max_value()
+    } else {
+        M::min_value()
+    })
+}
+
+#[test]
+fn dot_is_exact() {
+    let a: Vec<_> = (0..16u32)
+        .map(|i| Poui((i * 40503 % 65536) as u16))
+        .collect();
+    let b: Vec<_> = (0..16u32).map(|i| Poui((i * 7919 % 4096) as u16)).collect();
+    let exact: u64 = a.iter().zip(&b).map(|(x, y)| x.0 as u64 * y.0 as u64).sum();
+    assert_eq!(dot(&a, &b), Poui(exact.min(u32::MAX as u64) as u32));
+    let signed = [Poui(i16::MIN), Poui(i16::MIN)];
+    assert_eq!(dot(&signed, &signed), Poui(i32::MAX));
+    assert_eq!(dot(&signed, &[Poui(i16::MAX); 2]), Poui(i32::MIN));
+    assert_eq!(dot::<u8, u16>(&[], &[]), Poui(0));
+}
+
+#[test]
+fn convolve_matches_direct_sum() {
+    let signal = [
+        Poui(0x12i8),
+        Poui(-0x80),
+        Poui(0x7F),
+        Poui(0x33),
+        Poui(-0x05),
+    ];
+    let kernel = [Poui(0x20i8), Poui(-0x50), Poui(0x10)];
+    let output = convolve(&signal, &kernel);
+    assert_eq!(output.len(), 7);
+    for (n, y) in output.iter().enumerate() {
+        let exact: i32 = (0..kernel.len())
+            .filter(|&k| k <= n && n - k < signal.len())
+            .map(|k| signal[n - k].0 as i32 * kernel[k].0 as i32)
+            .sum();
+        assert_eq!(y.0 as i32, ((exact + 64) >> 7).clamp(-128, 127), "{n}");
+    }
+    assert_eq!(convolve(&signal, &[Poui(i8::MAX)]).len(), signal.len());
+    assert!(convolve::<u8, u16>(&[], &[Poui(1)]).is_empty());
+}
@@ -0,0 +1,637 @@
+//! Easing functions for animation.
+//!
+//! Each function maps a parameter `t` in [0, 1) to an eased progress value,
+//! starting at 0 and approaching 1 as `t` does, following the usual
+//! definitions popularized by Robert Penner. They're evaluated entirely in
+//! fixed point, with 62 fractional bits internally, so they behave the same
+//! on every target. The overshooting curves, `back` and `elastic`, leave the
+//! unit interval part of the way through; since a `Poui` can't represent
+//! that, their results are clamped to the interval.
+//!
+//! The [`Easing`] enum names every function, for choosing one at runtime,
+//! e.g. from a configuration file, and [`CubicBezierEasing`] evaluates the
+//! custom timing curves of CSS.
+//!
+//! ```rust
+//! use poui::easing;
+//! use poui::easing::Easing;
+//! use poui::Poui;
+//!
+//! assert_eq!(easing::quad_in(Poui(128u8)), Poui(64u8));
+//! assert_eq!(easing::cubic_out(Poui(0x8000u16)), Poui(0xE000u16));
+//! assert_eq!(Easing::QuadIn.apply(Poui(128u8)), Poui(64u8));
+//! ```
+
+use crate::frac_bits;
+use crate::trig::cordic_cos_sin;
+use crate::trig::turn_bits;
+use crate::Poui;
+use crate::StrictWiden;
+use num_traits::AsPrimitive;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+
+/// One in Q62.
+const ONE: i128 = 1 << 62;
+
+/// One half in Q62.
+const HALF: i128 = 1 << 61;
+
+/// `ln 2` in Q62.
+pub(crate) const LN_2: i128 = 3196577161300663915;
+
+/// The overshoot of the `back` easings, 1.70158, in Q62.
+const BACK: i128 = 7847152695235674710;
+
+/// The overshoot of [`back_in_out`], 1.70158 × 1.525, in Q62.
+const BACK_IN_OUT: i128 = 11966907860234403932;
+
+/// Converts a `Poui` parameter to Q62.
+pub(crate) fn to_q62<N: PrimInt + Unsigned + WrappingAdd>(t: Poui<N>) -> i128 {
+    (turn_bits(t.0) >> 2) as i128
+}
+
+/// Rounds a Q62 value to the nearest `Poui`, clamping to the interval.
+pub(crate) fn from_q62<N: PrimInt + Unsigned + WrappingAdd>(v: i128) -> Poui<N> {
+    let bits = frac_bits::<N>();
+    if v <= 0 {
+        return Poui(N::zero());
+    }
+    if v >= ONE {
+        return Poui(N::max_value());
+    }
+    let v = v as u128;
+    let scaled = if bits >= 62 {
+        v << (bits - 62)
+    } else {
+        (v + (1 << (61 - bits))) >> (62 - bits)
+    };
+    Poui(N::from(scaled).unwrap_or(N::max_value()))
+}
+
+/// Multiplies two Q62 values.
+fn mul(a: i128, b: i128) -> i128 {
+    (a * b) >> 62
+}
+
+/// Raises a Q62 value to the power `n`.
+fn pow(t: i128, n: u32) -> i128 {
+    (1..n).fold(t, |acc, _| mul(acc, t))
+}
+
+/// Computes `2^x` in Q62 for a Q62 exponent `x` of at most 1.
+pub(crate) fn exp2(x: i128) -> i128 {
+    let whole = x >> 62;
+    let y = mul(x - (whole << 62), LN_2);
+    // e^y by its Taylor series, which for y < ln 2 converges within about 25
+    // terms at this precision.
+    let (mut sum, mut term, mut k) = (ONE, ONE, 1);
+    while term != 0 {
+        term = mul(term, y) / k;
+        sum += term;
+        k += 1;
+    }
+    match whole {
+        0.. => sum << whole,
+        -62.. => sum >> -whole,
+        _ => 0,
+    }
+}
+
+/// The sine of an angle given in Q62 turns, which may be negative.
+fn sin_turns(turns: i128) -> i128 {
+    cordic_cos_sin(((turns as u128) << 2) as u64).1 as i128
+}
+
+/// The cosine of an angle given in Q62 turns.
+fn cos_turns(turns: i128) -> i128 {
+    cordic_cos_sin(((turns as u128) << 2) as u64).0 as i128
+}
+
+/// A power curve accelerating from zero.
+fn power_in(t: i128, n: u32) -> i128 {
+    pow(t, n)
+}
+
+/// A power curve decelerating to one.
+fn power_out(t: i128, n: u32) -> i128 {
+    ONE - pow(ONE - t, n)
+}
+
+/// A power curve accelerating to the midpoint, then decelerating.
+fn power_in_out(t: i128, n: u32) -> i128 {
+    if t < HALF {
+        pow(t, n) << (n - 1)
+    } else {
+        ONE - pow(2 * (ONE - t), n) / 2
+    }
+}
+
+fn bounce(t: i128) -> i128 {
+    let curve = |t: i128, offset: i128| mul(t, t) * 121 / 16 + offset;
+    if t * 11 < 4 * ONE {
+        curve(t, 0)
+    } else if t * 11 < 8 * ONE {
+        curve(t - ONE * 6 / 11, ONE * 3 / 4)
+    } else if t * 11 < 10 * ONE {
+        curve(t - ONE * 9 / 11, ONE * 15 / 16)
+    } else {
+        curve(t - ONE * 21 / 22, ONE * 63 / 64)
+    }
+}
+
+/// The easings in Q62, named after their public wrappers.
+mod q62 {
+    use super::*;
+
+    pub fn linear(t: i128) -> i128 {
+        t
+    }
+
+    pub fn quad_in(t: i128) -> i128 {
+        power_in(t, 2)
+    }
+
+    pub fn quad_out(t: i128) -> i128 {
+        power_out(t, 2)
+    }
+
+    pub fn quad_in_out(t: i128) -> i128 {
+        power_in_out(t, 2)
+    }
+
+    pub fn cubic_in(t: i128) -> i128 {
+        power_in(t, 3)
+    }
+
+    pub fn cubic_out(t: i128) -> i128 {
+        power_out(t, 3)
+    }
+
+    pub fn cubic_in_out(t: i128) -> i128 {
+        power_in_out(t, 3)
+    }
+
+    pub fn quart_in(t: i128) -> i128 {
+        power_in(t, 4)
+    }
+
+    pub fn quart_out(t: i128) -> i128 {
+        power_out(t, 4)
+    }
+
+    pub fn quart_in_out(t: i128) -> i128 {
+        power_in_out(t, 4)
+    }
+
+    pub fn sine_in(t: i128) -> i128 {
+        ONE - cos_turns(t / 4)
+    }
+
+    pub fn sine_out(t: i128) -> i128 {
+        sin_turns(t / 4)
+    }
+
+    pub fn sine_in_out(t: i128) -> i128 {
+        (ONE - cos_turns(t / 2)) / 2
+    }
+
+    pub fn expo_in(t: i128) -> i128 {
+        if t == 0 {
+            0
+        } else {
+            exp2(10 * t - 10 * ONE)
+        }
+    }
+
+    pub fn expo_out(t: i128) -> i128 {
+        ONE - exp2(-10 * t)
+    }
+
+    pub fn expo_in_out(t: i128) -> i128 {
+        if t == 0 {
+            0
+        } else if t < HALF {
+            exp2(20 * t - 10 * ONE) / 2
+        } else {
+            ONE - exp2(10 * ONE - 20 * t) / 2
+        }
+    }
+
+    pub fn back_in(t: i128) -> i128 {
+        mul(mul(t, t), mul(BACK + ONE, t) - BACK)
+    }
+
+    pub fn back_out(t: i128) -> i128 {
+        let u = t - ONE;
+        ONE + mul(mul(u, u), mul(BACK + ONE, u) + BACK)
+    }
+
+    pub fn back_in_out(t: i128) -> i128 {
+        if t < HALF {
+            let u = 2 * t;
+            mul(mul(u, u), mul(BACK_IN_OUT + ONE, u) - BACK_IN_OUT) / 2
+        } else {
+            let u = 2 * t - 2 * ONE;
+            (mul(mul(u, u), mul(BACK_IN_OUT + ONE, u) + BACK_IN_OUT) + 2 * ONE) / 2
+        }
+    }
+
+    pub fn elastic_in(t: i128) -> i128 {
+        if t == 0 {
+            0
+        } else {
+            -mul(
+                exp2(10 * t - 10 * ONE),
+                sin_turns((10 * t - ONE * 43 / 4) / 3),
+            )
+        }
+    }
+
+    pub fn elastic_out(t: i128) -> i128 {
+        if t == 0 {
+            0
+        } else {
+            mul(exp2(-10 * t), sin_turns((10 * t - ONE * 3 / 4) / 3)) + ONE
+        }
+    }
+
+    pub fn elastic_in_out(t: i128) -> i128 {
+        // The sine's period is 4.5 / 20 of the parameter.
+        let phase = (20 * t - ONE * 89 / 8) * 2 / 9;
+        if t == 0 {
+            0
+        } else if t < HALF {
+            -mul(exp2(20 * t - 10 * ONE), sin_turns(phase)) / 2
+        } else {
+            mul(exp2(10 * ONE - 20 * t), sin_turns(phase)) / 2 + ONE
+        }
+    }
+
+    pub fn bounce_in(t: i128) -> i128 {
+        ONE - bounce(ONE - t)
+    }
+
+    pub fn bounce_out(t: i128) -> i128 {
+        bounce(t)
+    }
+
+    pub fn bounce_in_out(t: i128) -> i128 {
+        if t < HALF {
+            (ONE - bounce(ONE - 2 * t)) / 2
+        } else {
+            (ONE + bounce(2 * t - ONE)) / 2
+        }
+    }
+}
+
+/// Defines the public easing functions and the `Easing` enum naming them.
+macro_rules! easings {
+    ($($(#[doc = $doc:literal])* $name:ident => $variant:ident;)*) => {
+        $(
+            $(#[doc = $doc])*
+            pub fn $name<N: PrimInt + Unsigned + WrappingAdd>(t: Poui<N>) -> Poui<N> {
+                from_q62(q62::$name(to_q62(t)))
+            }
+        )*
+
+        /// An easing function, selectable at runtime.
+        ///
+        /// Each variant applies the function of the same name in this module.
+        #[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+        pub enum Easing {
+            #[default]
+            $(
+                $(#[doc = $doc])*
+                $variant,
+            )*
+        }
+
+        impl Easing {
+            /// Every easing function, in the order they're declared.
+            pub const ALL: &'static [Easing] = &[$(Easing::$variant),*];
+
+            /// Applies the easing function to `t`.
+            pub fn apply<N: PrimInt + Unsigned + WrappingAdd>(self, t: Poui<N>) -> Poui<N> {
+                match self {
+                    $(Easing::$variant => $name(t),)*
+                }
+            }
+        }
+    };
+}
+
+easings! {
+    /// No easing: the progress is `t` itself.
+    linear => Linear;
+    /// Accelerates from zero, as `t²`.
+    quad_in => QuadIn;
+    /// Decelerates to one, as `1 - (1 - t)²`.
+    quad_out => QuadOut;
+    /// Accelerates quadratically to the midpoint, then decelerates.
+    quad_in_out => QuadInOut;
+    /// Accelerates from zero, as `t³`.
+    cubic_in => CubicIn;
+    /// Decelerates to one, as `1 - (1 - t)³`.
+    cubic_out => CubicOut;
+    /// Accelerates cubically to the midpoint, then decelerates.
+    cubic_in_out => CubicInOut;
+    /// Accelerates from zero, as `t⁴`.
+    quart_in => QuartIn;
+    /// Decelerates to one, as `1 - (1 - t)⁴`.
+    quart_out => QuartOut;
+    /// Accelerates quartically to the midpoint, then decelerates.
+    quart_in_out => QuartInOut;
+    /// Accelerates from zero along a quarter of a cosine wave.
+    sine_in => SineIn;
+    /// Decelerates to one along a quarter of a sine wave.
+    sine_out => SineOut;
+    /// Follows half a cosine wave, accelerating then decelerating.
+    sine_in_out => SineInOut;
+    /// Accelerates exponentially, as `2^(10t - 10)`, and is exactly zero at
+    /// `t = 0`.
+    expo_in => ExpoIn;
+    /// Decelerates exponentially, as `1 - 2^(-10t)`.
+    expo_out => ExpoOut;
+    /// Accelerates exponentially to the midpoint, then decelerates.
+    expo_in_out => ExpoInOut;
+    /// Pulls back slightly before accelerating. The pull back is clamped to
+    /// zero.
+    back_in => BackIn;
+    /// Overshoots one slightly before settling. The overshoot is clamped to
+    /// one.
+    back_out => BackOut;
+    /// Pulls back, accelerates, then overshoots and settles, with both
+    /// excursions clamped.
+    back_in_out => BackInOut;
+    /// Oscillates with growing amplitude before snapping to one, with the
+    /// negative swings clamped to zero.
+    elastic_in => ElasticIn;
+    /// Snaps past one and oscillates with decaying amplitude, with the
+    /// swings above one clamped.
+    elastic_out => ElasticOut;
+    /// Oscillates in towards the midpoint and out again, with excursions
+    /// clamped.
+    elastic_in_out => ElasticInOut;
+    /// Bounces with growing height before reaching one.
+    bounce_in => BounceIn;
+    /// Reaches one, then bounces with decaying height, like a dropped ball.
+    bounce_out => BounceOut;
+    /// Bounces up to the midpoint, then bounces out to one.
+    bounce_in_out => BounceInOut;
+}
+
+/// A cubic Bézier timing function, as in CSS's `cubic-bezier(x1, y1, x2,
+/// y2)`.
+///
+/// The curve runs from (0, 0) to (1, 1), shaped by the two control points.
+/// Evaluating it at a progress `x` means finding the curve parameter at which
+/// the curve's x coordinate reaches `x`, which is done by binary search over
+/// the parameter, then returning the y coordinate there. Everything is in
+/// fixed point, in the widened type.
+///
+/// CSS allows the control points' y coordinates outside [0, 1] for
+/// overshooting curves, but a `Poui` can't represent them; a coordinate of 1,
+/// as in the standard `ease` curves, is approximated by the largest `Poui`.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::easing::CubicBezierEasing;
+/// use poui::Poui;
+///
+/// let linear = CubicBezierEasing::new((Poui(0x4000u16), Poui(0x4000)), (Poui(0xC000), Poui(0xC000)));
+/// assert!(linear.apply(Poui(0x1234)).0.abs_diff(0x1234) <= 1);
+///
+/// let ease_in = CubicBezierEasing::<u16>::ease_in();
+/// assert!(ease_in.apply(Poui(0x4000)) < Poui(0x2000));
+/// assert!(ease_in.apply(Poui(0xC000)) > Poui(0x9000));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct CubicBezierEasing<N: PrimInt + WrappingAdd> {
+    p1: (Poui<N>, Poui<N>),
+    p2: (Poui<N>, Poui<N>),
+}
+
+impl<N, M> CubicBezierEasing<N>
+where
+    N: PrimInt + Unsigned + WrappingAdd + StrictWiden<Widened = M> + 'static,
+    M: PrimInt + AsPrimitive<N>,
+{
+    /// The timing function with control points `p1` and `p2`, each given as
+    /// `(x, y)`.
+    pub fn new(p1: (Poui<N>, Poui<N>), p2: (Poui<N>, Poui<N>)) -> Self {
+        CubicBezierEasing { p1, p2 }
+    }
+
+    /// CSS's `ease`, `cubic-bezier(0.25, 0.1, 0.25, 1)`.
+    pub fn ease() -> Self {
+        Self::from_percent((25, 10), (25, 100))
+    }
+
+    /// CSS's `ease-in`, `cubic-bezier(0.42, 0, 1, 1)`.
+    pub fn ease_in() -> Self {
+        Self::from_percent((42, 0), (100, 100))
+    }
+
+    /// CSS's `ease-out`, `cubic-bezier(0, 0, 0.58, 1)`.
+    pub fn ease_out() -> Self {
+        Self::from_percent((0, 0), (58, 100))
+    }
+
+    /// CSS's `ease-in-out`, `cubic-bezier(0.42, 0, 0.58, 1)`.
+    pub fn ease_in_out() -> Self {
+        Self::from_percent((42, 0), (58, 100))
+    }
+
+    /// The timing function with control points given in hundredths, each
+    /// rounded to the nearest `Poui`, and 100 to the largest.
+    fn from_percent(p1: (u8, u8), p2: (u8, u8)) -> Self {
+        let p = |percent: u8| {
+            Poui::from_ratio(N::from(percent).unwrap(), N::from(100).unwrap())
+                .unwrap_or(Poui(N::max_value()))
+        };
+        CubicBezierEasing::new((p(p1.0), p(p1.1)), (p(p2.0), p(p2.1)))
+    }
+
+    /// The eased progress at progress `x`.
+    pub fn apply(&self, x: Poui<N>) -> Poui<N> {
+        let f = frac_bits::<N>();
+        let x = x.0.widen();
+        // Find the smallest parameter t at which the curve's x coordinate
+        // reaches x, by finding the largest at which it falls short one bit
+        // at a time.
+        let mut below = M::zero();
+        for bit in (0..f).rev() {
+            let candidate = below | (M::one() << bit);
+            if self.coordinate(self.p1.0, self.p2.0, candidate) < x {
+                below = candidate;
+            }
+        }
+        let t = if x.is_zero() {
+            M::zero()
+        } else {
+            (below + M::one()).min(N::max_value().widen())
+        };
+        let y = self.coordinate(self.p1.1, self.p2.1, t);
+        Poui(y.min(N::max_value().widen()).as_())
+    }
+
+    /// One coordinate of the curve at parameter `t`, by de Casteljau's
+    /// algorithm.
+    fn coordinate(&self, c1: Poui<N>, c2: Poui<N>, t: M) -> M {
+        let f = frac_bits::<N>();
+        let lerp = |a: M, b: M| {
+            if b >= a {
+                a + (((b - a) * t) >> f)
+            } else {
+                a - (((a - b) * t) >> f)
+            }
+        };
+        let mut points = [M::zero(), c1.0.widen(), c2.0.widen(), M::one() << f];
+        for level in (1..4).rev() {
+            for i in 0..level {
+                points[i] = lerp(points[i], points[i + 1]);
+            }
+        }
+        points[0]
+    }
+}
+
+#[cfg(test)]
+fn reference(easing: Easing, t: f64) -> f64 {
+    use std::f64::consts::PI;
+    let (c1, c2) = (1.70158, 1.70158 * 1.525);
+    let (c3, c4, c5) = (c1 + 1.0, 2.0 * PI / 3.0, 2.0 * PI / 4.5);
+    let bounce = |t: f64| {
+        let (n1, d1) = (7.5625, 2.75);
+        if t < 1.0 / d1 {
+            n1 * t * t
+        } else if t < 2.0 / d1 {
+            n1 * (t - 1.5 / d1).powi(2) + 0.75
+        } else if t < 2.5 / d1 {
+            n1 * (t - 2.25 / d1).powi(2) + 0.9375
+        } else {
+            n1 * (t - 2.625 / d1).powi(2) + 0.984375
+        }
+    };
+    let in_out = |n: i32| {
+        if t < 0.5 {
+            2f64.powi(n - 1) * t.powi(n)
+        } else {
+            1.0 - (2.0 - 2.0 * t).powi(n) / 2.0
+        }
+    };
+    let v = match easing {
+        Easing::Linear => t,
+        Easing::QuadIn => t * t,
+        Easing::QuadOut => 1.0 - (1.0 - t).powi(2),
+        Easing::QuadInOut => in_out(2),
+        Easing::CubicIn => t.powi(3),
+        Easing::CubicOut => 1.0 - (1.0 - t).powi(3),
+        Easing::CubicInOut => in_out(3),
+        Easing::QuartIn => t.powi(4),
+        Easing::QuartOut => 1.0 - (1.0 - t).powi(4),
+        Easing::QuartInOut => in_out(4),
+        Easing::SineIn => 1.0 - (t * PI / 2.0).cos(),
+        Easing::SineOut => (t * PI / 2.0).sin(),
+        Easing::SineInOut => (1.0 - (t * PI).cos()) / 2.0,
+        Easing::ExpoIn if t == 0.0 => 0.0,
+        Easing::ExpoIn => 2f64.powf(10.0 * t - 10.0),
+        Easing::ExpoOut => 1.0 - 2f64.powf(-10.0 * t),
+        Easing::ExpoInOut if t == 0.0 => 0.0,
+        Easing::ExpoInOut if t < 0.5 => 2f64.powf(20.0 * t - 10.0) / 2.0,
+        Easing::ExpoInOut => (2.0 - 2f64.powf(-20.0 * t + 10.0)) / 2.0,
+        Easing::BackIn => c3 * t.powi(3) - c1 * t * t,
+        Easing::BackOut => 1.0 + c3 * (t - 1.0).powi(3) + c1 * (t - 1.0).powi(2),
+        Easing::BackInOut if t < 0.5 => (2.0 * t).powi(2) * ((c2 + 1.0) * 2.0 * t - c2) / 2.0,
+        Easing::BackInOut => {
+            ((2.0 * t - 2.0).powi(2) * ((c2 + 1.0) * (2.0 * t - 2.0) + c2) + 2.0) / 2.0
+        }
+        Easing::ElasticIn if t == 0.0 => 0.0,
+        Easing::ElasticIn => -(2f64.powf(10.0 * t - 10.0)) * ((10.0 * t - 10.75) * c4).sin(),
+        Easing::ElasticOut if t == 0.0 => 0.0,
+        Easing::ElasticOut => 2f64.powf(-10.0 * t) * ((10.0 * t - 0.75) * c4).sin() + 1.0,
+        Easing::ElasticInOut if t == 0.0 => 0.0,
+        Easing::ElasticInOut if t < 0.5 => {
+            -(2f64.powf(20.0 * t - 10.0) * ((20.0 * t - 11.125) * c5).sin()) / 2.0
+        }
+        Easing::ElasticInOut => {
+            2f64.powf(-20.0 * t + 10.0) * ((20.0 * t - 11.125) * c5).sin() / 2.0 + 1.0
+        }
+        Easing::BounceIn => 1.0 - bounce(1.0 - t),
+        Easing::BounceOut => bounce(t),
+        Easing::BounceInOut if t < 0.5 => (1.0 - bounce(1.0 - 2.0 * t)) / 2.0,
+        Easing::BounceInOut => (1.0 + bounce(2.0 * t - 1.0)) / 2.0,
+    };
+    v.clamp(0.0, 1.0)
+}
+
+#[test]
+fn easings_match_float_reference() {
+    for &easing in Easing::ALL {
+        for n in (0..=u32::MAX).step_by(0x00F0_F0F1) {
+            let t = n as f64 / 4294967296.0;
+            let expected = reference(easing, t);
+            let actual = easing.apply(Poui(n)).0 as f64 / 4294967296.0;
+            assert!(
+                (actual - expected).abs() < 1e-8,
+                "{easing:?}({t}) = {actual}, not {expected}"
+            );
+        }
+    }
+}
+
+#[test]
+fn easings_start_at_zero() {
+    for &easing in Easing::ALL {
+        assert_eq!(easing.apply(Poui(0u16)), Poui(0), "{easing:?}");
+        assert!(easing.apply(Poui(u16::MAX)) >= Poui(0xFF00), "{easing:?}");
+        assert_eq!(easing.apply(Poui(u128::MAX)).0 >> 120, 0xFF, "{easing:?}");
+    }
+}
+
+#[test]
+fn cubic_bezier_matches_float() {
+    let curves = [
+        ((25, 10), (25, 100)),
+        ((42, 0), (100, 100)),
+        ((0, 0), (58, 100)),
+        ((42, 0), (58, 100)),
+        ((90, 10), (10, 90)),
+    ];
+    for (p1, p2) in curves {
+        let easing = CubicBezierEasing::<u32>::from_percent(p1, p2);
+        let bezier = |a: u8, b: u8, t: f64| {
+            let (a, b) = (a as f64 / 100.0, b as f64 / 100.0);
+            3.0 * (1.0 - t) * (1.0 - t) * t * a + 3.0 * (1.0 - t) * t * t * b + t * t * t
+        };
+        for k in 0..100 {
+            let t = k as f64 / 100.0;
+            let (x, y) = (bezier(p1.0, p2.0, t), bezier(p1.1, p2.1, t));
+            let actual = easing.apply(Poui((x * 4294967296.0) as u32)).0 as f64 / 4294967296.0;
+            assert!((actual - y).abs() < 1e-6, "{p1:?} {p2:?} {x}: {actual} {y}");
+        }
+    }
+}
+
+#[test]
+fn cubic_bezier_endpoints() {
+    let ease = CubicBezierEasing::<u8>::ease();
+    assert_eq!(ease.apply(Poui(0)), Poui(0));
+    assert!(
+        ease.apply(Poui(255)) >= Poui(250),
+        "{:?}",
+        ease.apply(Poui(255))
+    );
+    let preset = CubicBezierEasing::<u64>::ease_out();
+    assert_eq!(preset.apply(Poui(0)), Poui(0));
+    let mut previous = Poui(0u16);
+    let ease_in_out = CubicBezierEasing::<u16>::ease_in_out();
+    for n in (0..=u16::MAX).step_by(7) {
+        let y = ease_in_out.apply(Poui(n));
+        assert!(y >= previous, "{n}");
+        previous = y;
+    }
+}
@@ -0,0 +1,144 @@
+use crate::frac_bits;
+use crate::probability::log2_q62;
+use crate::Mixed;
+use crate::Poui;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+
+/// The Shannon entropy of a distribution, in bits, as a 32.32 fixed-point
+/// number.
+///
+/// Each term `−p · log2 p` uses the same fixed-point logarithm as
+/// [`Poui::logit`], and is rounded down to 32 fractional bits before the
+/// terms are summed, so the result is within one ULP per probability below
+/// the exact value. Zero probabilities contribute nothing. The
+/// probabilities aren't renormalized, so they should sum to 1 for the
+/// result to be meaningful.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::entropy;
+/// use poui::Mixed;
+/// use poui::Poui;
+///
+/// assert_eq!(entropy(&[Poui(64u8); 4]), Mixed::new(2, Poui(0)));
+/// assert_eq!(entropy(&[Poui(0u16), Poui(u16::MAX)]).int, 0);
+/// ```
+pub fn entropy<N: PrimInt + Unsigned + WrappingAdd>(probabilities: &[Poui<N>]) -> Mixed<i32, u32> {
+    let bits = frac_bits::<N>() as i128;
+    let sum = probabilities
+        .iter()
+        .filter(|p| p.0 != N::zero())
+        .map(|&p| {
+            let x = p.0.to_u128().unwrap();
+            p_times(p, ((bits << 62) - log2_q62(x)) >> 30)
+        })
+        .sum();
+    to_mixed(sum)
+}
+
+/// The Kullback–Leibler divergence of `q` from `p`, in bits, as a 32.32
+/// fixed-point number, or `None` if it is infinite.
+///
+/// This is the expected number of extra bits needed to code samples from
+/// `p` with a code optimized for `q`, `Σ p · log2(p / q)`. It is infinite if
+/// some outcome has a nonzero probability in `p` but zero in `q`. Each term
+/// is rounded down to 32 fractional bits, as in [`entropy`], so the result
+/// can be slightly negative when the distributions are nearly identical.
+///
+/// # Panics
+///
+/// Panics if the distributions have different lengths.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::kl_divergence;
+/// use poui::Mixed;
+/// use poui::Poui;
+///
+/// let fair = [Poui(128u8), Poui(128)];
+/// let biased = [Poui(192u8), Poui(64)];
+/// assert_eq!(kl_divergence(&fair, &fair), Some(Mixed::new(0, Poui(0))));
+/// assert!(kl_divergence(&biased, &fair).unwrap() > Mixed::new(0, Poui(0)));
+/// assert_eq!(kl_divergence(&fair, &[Poui(255u8), Poui(0)]), None);
+/// ```
+pub fn kl_divergence<N: PrimInt + Unsigned + WrappingAdd>(
+    p: &[Poui<N>],
+    q: &[Poui<N>],
+) -> Option<Mixed<i32, u32>> {
+    assert_eq!(p.len(), q.len(), "distributions differ in length");
+    let mut sum = 0;
+    for (&p, &q) in p.iter().zip(q).filter(|(p, _)| p.0 != N::zero()) {
+        if q.0 == N::zero() {
+            return None;
+        }
+        let (x, y) = (p.0.to_u128().unwrap(), q.0.to_u128().unwrap());
+        sum += p_times(p, (log2_q62(x) - log2_q62(y)) >> 30);
+    }
+    Some(to_mixed(sum))
+}
+
+/// Multiplies a Q32 value, which fits in 40 bits, by a probability.
+fn p_times<N: PrimInt + Unsigned + WrappingAdd>(p: Poui<N>, v: i128) -> i128 {
+    let bits = frac_bits::<N>();
+    let x = p.0.to_u128().unwrap();
+    // Keep at most 64 bits of the probability, so the product fits.
+    let (x, bits) = if bits > 64 {
+        (x >> (bits - 64), 64)
+    } else {
+        (x, bits)
+    };
+    (x as i128 * v) >> bits
+}
+
+/// Converts a Q32 value to a 32.32 mixed number.
+fn to_mixed(v: i128) -> Mixed<i32, u32> {
+    Mixed::new((v >> 32) as i32, Poui(v as u32))
+}
+
+#[test]
+fn entropy_matches_floats() {
+    let distributions: [&[u16]; 4] = [
+        &[0x8000, 0x8000],
+        &[1, 0xFFFF],
+        &[0x1000, 0x3000, 0x7000, 0x5000],
+        &[0x5555, 0x5555, 0x5556],
+    ];
+    for ps in distributions {
+        let pouis: Vec<_> = ps.iter().map(|&p| Poui(p)).collect();
+        let h = entropy(&pouis);
+        let actual = h.int as f64 + h.frac.0 as f64 / 2f64.powi(32);
+        let expected: f64 = ps
+            .iter()
+            .map(|&p| p as f64 / 65536.0)
+            .map(|p| -p * p.log2())
+            .sum();
+        assert!(
+            (actual - expected).abs() < 1e-8,
+            "{ps:?}: {actual} {expected}"
+        );
+    }
+    let uniform = [Poui(1u128 << 125); 8];
+    assert_eq!(entropy(&uniform), Mixed::new(3, Poui(0)));
+}
+
+#[test]
+fn kl_divergence_matches_floats() {
+    let p = [Poui(0x2000u16), Poui(0x6000), Poui(0x8000)];
+    let q = [Poui(0x5555u16), Poui(0x5555), Poui(0x5556)];
+    let d = kl_divergence(&p, &q).unwrap();
+    let actual = d.int as f64 + d.frac.0 as f64 / 2f64.powi(32);
+    let expected: f64 = p
+        .iter()
+        .zip(&q)
+        .map(|(p, q)| (p.0 as f64 / 65536.0, q.0 as f64 / 65536.0))
+        .map(|(p, q)| p * (p / q).log2())
+        .sum();
+    assert!((actual - expected).abs() < 1e-8, "{actual} {expected}");
+    let zero = [Poui(0u16), Poui(0x8000), Poui(0x8000)];
+    assert!(kl_divergence(&zero, &q).is_some());
+    assert_eq!(kl_divergence(&q, &zero), None);
+}
@@ -0,0 +1,169 @@
+use crate::Poui;
+use crate::StrictWiden;
+use num_traits::AsPrimitive;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+
+/// The error returned when the breakpoints of an [`Envelope`] are invalid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnvelopeError {
+    /// The breakpoint times were not strictly increasing.
+    Unsorted,
+}
+
+impl std::fmt::Display for EnvelopeError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            EnvelopeError::Unsorted => write!(f, "breakpoint times are not strictly increasing"),
+        }
+    }
+}
+
+impl std::error::Error for EnvelopeError {}
+
+/// A piecewise-linear curve on the unit interval.
+///
+/// The curve is defined by `(x, y)` breakpoints with strictly increasing
+/// `x`, and is interpolated linearly in between. Before the first breakpoint
+/// and after the last one, the curve holds their values. This is enough to
+/// describe volume envelopes, sensor calibration curves, and transfer
+/// functions, all without floating point.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Envelope;
+/// use poui::Poui;
+///
+/// let env = Envelope::new(vec![
+///     (Poui(0u8), Poui(0u8)),
+///     (Poui(64u8), Poui(255u8)),
+///     (Poui(192u8), Poui(128u8)),
+/// ])
+/// .unwrap();
+/// assert_eq!(env.eval(Poui(32)), Some(Poui(127)));
+/// assert_eq!(env.eval(Poui(128)), Some(Poui(192)));
+/// assert_eq!(env.eval(Poui(255)), Some(Poui(128)));
+/// assert!(!env.is_non_decreasing());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Envelope<N: PrimInt + Unsigned + WrappingAdd> {
+    points: Vec<(Poui<N>, Poui<N>)>,
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Envelope<N> {
+    /// Creates an envelope from breakpoints sorted by strictly increasing
+    /// `x`.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`EnvelopeError::Unsorted`] if two consecutive breakpoints
+    /// are out of order or share an `x`.
+    pub fn new(points: Vec<(Poui<N>, Poui<N>)>) -> Result<Self, EnvelopeError> {
+        if points.windows(2).any(|w| w[0].0 >= w[1].0) {
+            return Err(EnvelopeError::Unsorted);
+        }
+        Ok(Envelope { points })
+    }
+
+    /// The breakpoints, sorted by `x`.
+    pub fn points(&self) -> &[(Poui<N>, Poui<N>)] {
+        &self.points
+    }
+
+    /// Adds a breakpoint, replacing any existing breakpoint at the same `x`.
+    pub fn insert(&mut self, x: Poui<N>, y: Poui<N>) {
+        match self.points.binary_search_by(|p| p.0.cmp(&x)) {
+            Ok(i) => self.points[i].1 = y,
+            Err(i) => self.points.insert(i, (x, y)),
+        }
+    }
+
+    /// Removes the breakpoint at `x`, returning its `y`, if there is one.
+    pub fn remove(&mut self, x: Poui<N>) -> Option<Poui<N>> {
+        let i = self.points.binary_search_by(|p| p.0.cmp(&x)).ok()?;
+        Some(self.points.remove(i).1)
+    }
+
+    /// Whether the curve never goes down, e.g. as a transfer function that
+    /// preserves order.
+    pub fn is_non_decreasing(&self) -> bool {
+        self.points.windows(2).all(|w| w[0].1 <= w[1].1)
+    }
+
+    /// Whether the curve never goes up.
+    pub fn is_non_increasing(&self) -> bool {
+        self.points.windows(2).all(|w| w[0].1 >= w[1].1)
+    }
+}
+
+impl<N, M> Envelope<N>
+where
+    N: PrimInt + Unsigned + WrappingAdd + StrictWiden<Widened = M> + 'static,
+    M: PrimInt + AsPrimitive<N>,
+{
+    /// The value of the curve at `x`, or `None` if there are no breakpoints.
+    ///
+    /// Within a segment, the position is found with [`Poui::from_ratio`]
+    /// and the value with [`Poui::lerp`], so the result is within a couple
+    /// of ULPs of the exact line.
+    pub fn eval(&self, x: Poui<N>) -> Option<Poui<N>> {
+        let next = self.points.partition_point(|p| p.0 <= x);
+        let (from, to) = match next {
+            0 => return self.points.first().map(|p| p.1),
+            n if n == self.points.len() => return Some(self.points[n - 1].1),
+            n => (self.points[n - 1], self.points[n]),
+        };
+        let t = Poui::from_ratio(x.0 - from.0 .0, to.0 .0 - from.0 .0).unwrap();
+        Some(from.1.lerp(to.1, t))
+    }
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Default for Envelope<N> {
+    fn default() -> Self {
+        Envelope { points: Vec::new() }
+    }
+}
+
+#[test]
+fn envelope_rejects_unsorted() {
+    let p = |x: u8, y: u8| (Poui(x), Poui(y));
+    assert_eq!(
+        Envelope::new(vec![p(10, 0), p(5, 0)]),
+        Err(EnvelopeError::Unsorted)
+    );
+    assert_eq!(
+        Envelope::new(vec![p(10, 0), p(10, 1)]),
+        Err(EnvelopeError::Unsorted)
+    );
+    assert!(Envelope::new(vec![p(10, 0)]).is_ok());
+    assert_eq!(Envelope::<u8>::default().eval(Poui(3)), None);
+}
+
+#[test]
+fn envelope_interpolates_between_points() {
+    let mut env = Envelope::<u16>::default();
+    env.insert(Poui(0x8000), Poui(0x8000));
+    env.insert(Poui(0x4000), Poui(0));
+    assert_eq!(env.eval(Poui(0)), Some(Poui(0)));
+    assert_eq!(env.eval(Poui(0x6000)), Some(Poui(0x4000)));
+    assert_eq!(env.eval(Poui(0xFFFF)), Some(Poui(0x8000)));
+    for x in (0x4000..0x8000u16).step_by(7) {
+        let y = env.eval(Poui(x)).unwrap().0;
+        assert!(y.abs_diff((x - 0x4000) * 2) <= 2, "{x:#x} -> {y:#x}");
+    }
+    assert!(env.is_non_decreasing() && !env.is_non_increasing());
+}
+
+#[test]
+fn envelope_insert_and_remove() {
+    let mut env = Envelope::<u8>::default();
+    env.insert(Poui(100), Poui(1));
+    env.insert(Poui(50), Poui(2));
+    env.insert(Poui(100), Poui(3));
+    assert_eq!(env.points(), &[(Poui(50), Poui(2)), (Poui(100), Poui(3))]);
+    assert_eq!(env.remove(Poui(50)), Some(Poui(2)));
+    assert_eq!(env.remove(Poui(50)), None);
+    assert_eq!(env.eval(Poui(0)), Some(Poui(3)));
+}
@@ -0,0 +1,127 @@
+use crate::frac_bits;
+use crate::Poui;
+use crate::StrictWiden;
+use num_traits::AsPrimitive;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+
+/// An exponentially weighted moving average, for smoothing noisy samples.
+///
+/// Each update moves the average a fraction `alpha` of the way towards the
+/// new sample, so a larger `alpha` follows the samples more closely and a
+/// smaller one smooths them more. The obvious fixed-point update rounds the
+/// step every time, and so stalls up to `1 / alpha` ULPs short of a steady
+/// input. Instead, the average keeps a full extra `Poui` of fraction below
+/// its value, and each update is computed exactly in the widened type, so
+/// the average converges all the way to a constant input from either side.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Ewma;
+/// use poui::Poui;
+///
+/// let mut average = Ewma::new(Poui(0x0100u16), Poui(0));
+/// for _ in 0..4000 {
+///     average.update(Poui(1000));
+/// }
+/// assert_eq!(average.value(), Poui(1000));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ewma<N: PrimInt + Unsigned + WrappingAdd> {
+    alpha: Poui<N>,
+    value: Poui<N>,
+    /// The fraction of an ULP below the value, in units of `2^-bits` ULPs.
+    fraction: N,
+}
+
+impl<N, M> Ewma<N>
+where
+    N: PrimInt + Unsigned + WrappingAdd + StrictWiden<Widened = M> + 'static,
+    M: PrimInt + AsPrimitive<N>,
+{
+    /// An average starting at `initial`, with smoothing factor `alpha`.
+    pub fn new(alpha: Poui<N>, initial: Poui<N>) -> Self {
+        Ewma {
+            alpha,
+            value: initial,
+            fraction: N::zero(),
+        }
+    }
+
+    /// The smoothing factor.
+    pub fn alpha(&self) -> Poui<N> {
+        self.alpha
+    }
+
+    /// Changes the smoothing factor, keeping the current average.
+    pub fn set_alpha(&mut self, alpha: Poui<N>) {
+        self.alpha = alpha;
+    }
+
+    /// The current average, rounded to the nearest `Poui`.
+    pub fn value(&self) -> Poui<N> {
+        let half = N::one() << (frac_bits::<N>() - 1);
+        if self.fraction >= half {
+            Poui(self.value.0.saturating_add(N::one()))
+        } else {
+            self.value
+        }
+    }
+
+    /// Forgets the history, restarting the average at `value`.
+    pub fn reset(&mut self, value: Poui<N>) {
+        self.value = value;
+        self.fraction = N::zero();
+    }
+
+    /// Moves the average towards `sample`, and returns the new average.
+    pub fn update(&mut self, sample: Poui<N>) -> Poui<N> {
+        let bits = frac_bits::<N>();
+        let alpha = self.alpha.0.widen();
+        let keep = (M::one() << bits) - alpha;
+        // The new average, scaled by `2^bits`, is `keep · value + alpha ·
+        // sample` plus `keep · fraction` scaled down once more. The last
+        // term loses only a fraction of a fraction of an ULP.
+        let scaled = keep * self.value.0.widen()
+            + alpha * sample.0.widen()
+            + ((keep * self.fraction.widen()) >> bits);
+        self.value = Poui((scaled >> bits).as_());
+        self.fraction = (scaled & ((M::one() << bits) - M::one())).as_();
+        self.value()
+    }
+}
+
+#[test]
+fn ewma_converges_from_both_sides() {
+    let mut rising = Ewma::new(Poui(3u8), Poui(0));
+    let mut falling = Ewma::new(Poui(3u8), Poui(255));
+    for _ in 0..2000 {
+        rising.update(Poui(200));
+        falling.update(Poui(200));
+    }
+    assert_eq!(rising.value(), Poui(200));
+    assert_eq!(falling.value(), Poui(200));
+    let mut wide = Ewma::new(Poui(1u64 << 60), Poui(u64::MAX));
+    for _ in 0..2000 {
+        wide.update(Poui(0));
+    }
+    assert_eq!(wide.value(), Poui(0));
+}
+
+#[test]
+fn ewma_matches_float_average() {
+    let alpha = Poui(0x1000u16);
+    let mut average = Ewma::new(alpha, Poui(0));
+    let mut expected = 0.0f64;
+    for i in 0..500u32 {
+        let sample = (i * 7919 % 65536) as u16;
+        let actual = average.update(Poui(sample));
+        expected += alpha.0 as f64 / 65536.0 * (sample as f64 - expected);
+        assert!((actual.0 as f64 - expected).abs() <= 0.5 + 1e-6, "{i}");
+    }
+    average.reset(Poui(7));
+    assert_eq!(average.value(), Poui(7));
+    assert_eq!(Ewma::new(Poui(u16::MAX), Poui(0)).update(Poui(9)), Poui(9));
+}
@@ -0,0 +1,137 @@
+use crate::frac_bits;
+use crate::Poui;
+use crate::StrictWiden;
+use num_traits::AsPrimitive;
+use num_traits::PrimInt;
+use num_traits::Signed;
+use num_traits::WrappingAdd;
+
+/// A finite impulse response filter of signed `Poui` samples.
+///
+/// The output is the dot product of the coefficients with the latest
+/// samples, newest first, which are kept in a ring buffer. The products are
+/// accumulated exactly in the widened type, and the sum is rounded to the
+/// nearest sample once at the end, saturating if the filter has gain. This
+/// needs no more than a multiply-accumulate per tap, which suits small
+/// microcontrollers.
+///
+/// The absolute values of the coefficients must add up to less than 2, so
+/// that the sum can't overflow the widened type. That is enough for any
+/// smoothing, decimation or differentiating filter with gain below 2.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Fir;
+/// use poui::Poui;
+///
+/// // A three-tap moving average.
+/// let third = Poui(0x2AABi16);
+/// let mut average = Fir::new(&[third, third, third]);
+/// assert_eq!(average.process(Poui(0x3000)), Poui(0x1000));
+/// assert_eq!(average.process(Poui(0x3000)), Poui(0x2000));
+/// assert_eq!(average.process(Poui(0x3000)), Poui(0x3000));
+/// assert_eq!(average.process(Poui(0)), Poui(0x2000));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Fir<S: PrimInt + Signed + WrappingAdd> {
+    coefficients: Vec<Poui<S>>,
+    /// The latest samples, as a ring buffer.
+    samples: Vec<Poui<S>>,
+    /// The index of the newest sample.
+    newest: usize,
+}
+
+impl<S, W> Fir<S>
+where
+    S: PrimInt + Signed + WrappingAdd + StrictWiden<Widened = W> + 'static,
+    W: PrimInt + AsPrimitive<S>,
+{
+    /// A filter with the given coefficients, with all its samples zero.
+    ///
+    /// The first coefficient weights the newest sample.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no coefficients, or if their absolute values add
+    /// up to 2 or more.
+    pub fn new(coefficients: &[Poui<S>]) -> Self {
+        assert!(!coefficients.is_empty(), "a filter needs coefficients");
+        let gain: u128 = coefficients
+            .iter()
+            .map(|c| c.0.to_i128().unwrap().unsigned_abs())
+            .sum();
+        assert!(
+            gain < 2 << frac_bits::<S>(),
+            "coefficients must add up to less than 2 in absolute value"
+        );
+        Fir {
+            coefficients: coefficients.to_vec(),
+            samples: vec![Poui(S::zero()); coefficients.len()],
+            newest: 0,
+        }
+    }
+
+    /// The coefficients, the first weighting the newest sample.
+    pub fn coefficients(&self) -> &[Poui<S>] {
+        &self.coefficients
+    }
+
+    /// Zeroes the samples, as if the filter had just been created.
+    pub fn reset(&mut self) {
+        self.samples.fill(Poui(S::zero()));
+        self.newest = 0;
+    }
+
+    /// Filters the next sample.
+    pub fn process(&mut self, sample: Poui<S>) -> Poui<S> {
+        let len = self.samples.len();
+        self.newest = (self.newest + 1) % len;
+        self.samples[self.newest] = sample;
+        // Newest first: back from the newest sample, then around from the
+        // end of the buffer.
+        let (front, back) = self.samples.split_at(self.newest + 1);
+        let history = front.iter().rev().chain(back.iter().rev());
+        let sum = self
+            .coefficients
+            .iter()
+            .zip(history)
+            .fold(W::zero(), |sum, (c, x)| sum + c.0.widen() * x.0.widen());
+        let bits = frac_bits::<S>();
+        let rounded = (sum + (W::one() << (bits - 1))) >> bits;
+        let (min, max) = (S::min_value().widen(), S::max_value().widen());
+        Poui(rounded.clamp(min, max).as_())
+    }
+}
+
+#[test]
+fn fir_matches_exact_convolution() {
+    let coefficients = [Poui(0x1234i16), Poui(-0x4000), Poui(0x2F00), Poui(-0x0100)];
+    let mut filter = Fir::new(&coefficients);
+    let signal: Vec<i16> = (0..100).map(|i| (i * 7919 % 65536) as u16 as i16).collect();
+    for n in 0..signal.len() {
+        let exact: i64 = (0..4)
+            .filter(|&k| k <= n)
+            .map(|k| coefficients[k].0 as i64 * signal[n - k] as i64)
+            .sum();
+        let expected = ((exact + (1 << 14)) >> 15).clamp(-0x8000, 0x7FFF) as i16;
+        assert_eq!(filter.process(Poui(signal[n])), Poui(expected), "{n}");
+    }
+    filter.reset();
+    assert_eq!(filter.process(Poui(0)), Poui(0));
+}
+
+#[test]
+fn fir_saturates() {
+    let mut double = Fir::new(&[Poui(i8::MAX), Poui(i8::MAX)]);
+    double.process(Poui(i8::MIN));
+    assert_eq!(double.process(Poui(i8::MIN)), Poui(i8::MIN));
+    assert_eq!(double.process(Poui(i8::MAX)), Poui(-1));
+    assert_eq!(double.process(Poui(i8::MAX)), Poui(i8::MAX));
+}
+
+#[test]
+#[should_panic]
+fn fir_rejects_gain_of_two() {
+    Fir::new(&[Poui(i8::MIN), Poui(i8::MIN)]);
+}
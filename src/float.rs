@@ -0,0 +1,252 @@
+use crate::frac_bits;
+use crate::Poui;
+use num_traits::Float;
+use num_traits::PrimInt;
+use num_traits::WrappingAdd;
+
+/// The error returned when a floating-point number cannot be converted to a
+/// `Poui`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FromFloatError {
+    /// The number was NaN.
+    NaN,
+    /// The number was outside the interval represented by the backing type,
+    /// i.e. [0, 1) for unsigned backings or [-1, 1) for signed backings.
+    OutOfRange,
+}
+
+impl std::fmt::Display for FromFloatError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FromFloatError::NaN => write!(f, "cannot convert NaN to a Poui"),
+            FromFloatError::OutOfRange => {
+                write!(f, "number is outside the interval represented by the Poui")
+            }
+        }
+    }
+}
+
+impl std::error::Error for FromFloatError {}
+
+/// Multiplies `x` by two to the power of `exp`.
+///
+/// The power of two is applied in two halves so that scaling by 2^128 for the
+/// 128-bit backings does not overflow an `f32`.
+fn ldexp<F: Float>(x: F, exp: usize) -> F {
+    let two = F::one() + F::one();
+    let half = exp / 2;
+    x * two.powi(half as i32) * two.powi((exp - half) as i32)
+}
+
+/// The smallest value representable by a `Poui<N>`, as a float.
+fn lower_bound<F: Float, N: PrimInt>() -> F {
+    if N::min_value() < N::zero() {
+        -F::one()
+    } else {
+        F::zero()
+    }
+}
+
+/// Rounds an in-range `x` to the nearest raw value, saturating values that
+/// round up to 1.
+fn saturating_round<F: Float, N: PrimInt>(x: F) -> N {
+    N::from(ldexp(x, frac_bits::<N>()).round()).unwrap_or_else(N::max_value)
+}
+
+/// Reduces `x` modulo the length of the interval and rounds it to the nearest
+/// raw value, wrapping values that round up to 1.
+fn wrapping_round<F: Float, N: PrimInt>(x: F) -> N {
+    if !x.is_finite() {
+        return N::zero();
+    }
+    let reduced = if N::min_value() < N::zero() {
+        let two = F::one() + F::one();
+        x - two * ((x + F::one()) / two).floor()
+    } else {
+        x - x.floor()
+    };
+    N::from(ldexp(reduced, frac_bits::<N>()).round()).unwrap_or_else(N::min_value)
+}
+
+fn try_from_float<F: Float, N: PrimInt>(x: F) -> Result<N, FromFloatError> {
+    if x.is_nan() {
+        Err(FromFloatError::NaN)
+    } else if x < lower_bound::<F, N>() || x >= F::one() {
+        Err(FromFloatError::OutOfRange)
+    } else {
+        Ok(saturating_round(x))
+    }
+}
+
+fn clamped_from_float<F: Float, N: PrimInt>(x: F) -> N {
+    if x.is_nan() {
+        return N::zero();
+    }
+    saturating_round(x.max(lower_bound::<F, N>()).min(F::one()))
+}
+
+/// Converts an `f32` to the nearest point on the unit interval.
+///
+/// Values that are within the interval, but so close to 1 that they round up
+/// to it, saturate to the largest representable value.
+///
+/// # Errors
+///
+/// Returns [`FromFloatError::NaN`] for NaN, and [`FromFloatError::OutOfRange`]
+/// for values outside [0, 1) (or [-1, 1) for signed backings).
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::FromFloatError;
+/// use poui::Poui;
+///
+/// assert_eq!(Poui::try_from(0.25f32), Ok(Poui(64u8)));
+/// assert_eq!(Poui::try_from(-0.5f32), Ok(Poui(-64i8)));
+/// assert_eq!(Poui::<u8>::try_from(1.0f32), Err(FromFloatError::OutOfRange));
+/// assert_eq!(Poui::<u8>::try_from(f32::NAN), Err(FromFloatError::NaN));
+/// ```
+impl<N: PrimInt + WrappingAdd> TryFrom<f32> for Poui<N> {
+    type Error = FromFloatError;
+
+    fn try_from(x: f32) -> Result<Self, Self::Error> {
+        try_from_float(x).map(Poui)
+    }
+}
+
+/// Converts an `f64` to the nearest point on the unit interval.
+///
+/// This behaves exactly like the conversion from `f32`.
+impl<N: PrimInt + WrappingAdd> TryFrom<f64> for Poui<N> {
+    type Error = FromFloatError;
+
+    fn try_from(x: f64) -> Result<Self, Self::Error> {
+        try_from_float(x).map(Poui)
+    }
+}
+
+impl<N: PrimInt + WrappingAdd> Poui<N> {
+    /// Converts an `f32` to the nearest point on the unit interval, clamping
+    /// values outside the interval to its ends.
+    ///
+    /// NaN is converted to zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui::from_f32_clamped(0.5), Poui(128u8));
+    /// assert_eq!(Poui::from_f32_clamped(-3.0), Poui(0u8));
+    /// assert_eq!(Poui::from_f32_clamped(3.0), Poui(255u8));
+    /// ```
+    pub fn from_f32_clamped(x: f32) -> Self {
+        Poui(clamped_from_float(x))
+    }
+
+    /// Converts an `f32` to the nearest point on the unit interval, wrapping
+    /// values outside the interval around it.
+    ///
+    /// This keeps only the fractional part of `x`, which is the natural
+    /// conversion when `x` is an angle measured in turns. Signed backings cover
+    /// [-1, 1), so for them `x` is reduced modulo 2 instead. Infinities and NaN
+    /// are converted to zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui::from_f32_wrapped(1.25), Poui(64u8));
+    /// assert_eq!(Poui::from_f32_wrapped(-0.25), Poui(192u8));
+    /// assert_eq!(Poui::from_f32_wrapped(1.5), Poui(-64i8));
+    /// ```
+    pub fn from_f32_wrapped(x: f32) -> Self {
+        Poui(wrapping_round(x))
+    }
+
+    /// Converts an `f64` to the nearest point on the unit interval, clamping
+    /// values outside the interval to its ends.
+    ///
+    /// NaN is converted to zero.
+    pub fn from_f64_clamped(x: f64) -> Self {
+        Poui(clamped_from_float(x))
+    }
+
+    /// Converts an `f64` to the nearest point on the unit interval, wrapping
+    /// values outside the interval around it.
+    ///
+    /// Infinities and NaN are converted to zero.
+    pub fn from_f64_wrapped(x: f64) -> Self {
+        Poui(wrapping_round(x))
+    }
+}
+
+#[test]
+fn try_from_f32_unsigned() {
+    assert_eq!(Poui::try_from(0.0f32), Ok(Poui(0u8)));
+    assert_eq!(Poui::try_from(0.5f32), Ok(Poui(128u8)));
+    assert_eq!(Poui::try_from(0.5f32), Ok(Poui(32768u16)));
+    assert_eq!(Poui::try_from(0.75f32), Ok(Poui(3u32 << 30)));
+    assert_eq!(Poui::try_from(0.5f32), Ok(Poui(1u64 << 63)));
+    assert_eq!(Poui::try_from(0.5f32), Ok(Poui(1u128 << 127)));
+}
+
+#[test]
+fn try_from_f64_signed() {
+    assert_eq!(Poui::try_from(-1.0f64), Ok(Poui(i8::MIN)));
+    assert_eq!(Poui::try_from(-0.5f64), Ok(Poui(-16384i16)));
+    assert_eq!(Poui::try_from(0.25f64), Ok(Poui(1i32 << 29)));
+}
+
+#[test]
+fn try_from_rounds_to_nearest() {
+    // 0.3 * 256 = 76.8
+    assert_eq!(Poui::try_from(0.3f32), Ok(Poui(77u8)));
+    // 0.1 * 256 = 25.6
+    assert_eq!(Poui::try_from(0.1f64), Ok(Poui(26u8)));
+    // Rounds up to 1, which saturates.
+    assert_eq!(Poui::try_from(0.999f32), Ok(Poui(255u8)));
+}
+
+#[test]
+fn try_from_errors() {
+    assert_eq!(Poui::<u8>::try_from(f32::NAN), Err(FromFloatError::NaN));
+    assert_eq!(
+        Poui::<u8>::try_from(1.0f32),
+        Err(FromFloatError::OutOfRange)
+    );
+    assert_eq!(
+        Poui::<u8>::try_from(-0.1f64),
+        Err(FromFloatError::OutOfRange)
+    );
+    assert_eq!(
+        Poui::<i8>::try_from(-1.1f32),
+        Err(FromFloatError::OutOfRange)
+    );
+    assert_eq!(
+        Poui::<i16>::try_from(f64::INFINITY),
+        Err(FromFloatError::OutOfRange)
+    );
+}
+
+#[test]
+fn from_float_clamped() {
+    assert_eq!(Poui::from_f32_clamped(f32::NAN), Poui(0u8));
+    assert_eq!(Poui::from_f32_clamped(f32::NEG_INFINITY), Poui(0u16));
+    assert_eq!(Poui::from_f32_clamped(f32::INFINITY), Poui(u16::MAX));
+    assert_eq!(Poui::from_f64_clamped(-2.0), Poui(i32::MIN));
+    assert_eq!(Poui::from_f64_clamped(0.5), Poui(1i32 << 30));
+    assert_eq!(Poui::from_f64_clamped(1.0), Poui(i32::MAX));
+}
+
+#[test]
+fn from_float_wrapped() {
+    assert_eq!(Poui::from_f32_wrapped(3.5), Poui(128u8));
+    assert_eq!(Poui::from_f32_wrapped(-0.5), Poui(128u8));
+    assert_eq!(Poui::from_f64_wrapped(0.999), Poui(0u8));
+    assert_eq!(Poui::from_f64_wrapped(f64::NAN), Poui(0u8));
+    assert_eq!(Poui::from_f64_wrapped(1.25), Poui(-96i8));
+    assert_eq!(Poui::from_f64_wrapped(-1.25), Poui(96i8));
+    assert_eq!(Poui::from_f64_wrapped(0.999), Poui(i8::MIN));
+}
@@ -0,0 +1,148 @@
+use crate::frac_bits;
+use crate::nco::fraction_bits;
+use crate::Nco;
+use crate::Poui;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+
+/// The error returned when a frequency can't be a [`NormFreq`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NormFreqError {
+    /// The sample rate was zero.
+    ZeroSampleRate,
+    /// The frequency was above the Nyquist frequency, half the sample rate.
+    AboveNyquist,
+}
+
+impl std::fmt::Display for NormFreqError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            NormFreqError::ZeroSampleRate => write!(f, "sample rate is zero"),
+            NormFreqError::AboveNyquist => write!(f, "frequency is above Nyquist"),
+        }
+    }
+}
+
+impl std::error::Error for NormFreqError {}
+
+/// A normalized frequency: a frequency as a fraction of the sample rate, in
+/// cycles per sample.
+///
+/// This is the frequency that sampled signal processing actually works
+/// with, and the phase increment of an [`Nco`] running at that frequency.
+/// A `NormFreq` is always at most the Nyquist frequency, half the sample
+/// rate, since anything higher would alias, so it is also a valid cutoff
+/// or center frequency for a filter.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::NormFreq;
+/// use poui::NormFreqError;
+/// use poui::Nco;
+/// use poui::Poui;
+///
+/// let a4 = NormFreq::<u32>::from_hz(440, 48_000).unwrap();
+/// assert_eq!(a4.as_poui(), Poui(39370534));
+/// assert_eq!(Nco::from(a4).tuning_word(), a4.as_poui());
+/// assert_eq!(NormFreq::<u32>::from_hz(24_001, 48_000), Err(NormFreqError::AboveNyquist));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct NormFreq<N: PrimInt + Unsigned + WrappingAdd>(Poui<N>);
+
+impl<N: PrimInt + Unsigned + WrappingAdd> NormFreq<N> {
+    /// The frequency `freq_hz / sample_rate`, rounded to the nearest `Poui`
+    /// by exact integer division.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NormFreqError::ZeroSampleRate`] if `sample_rate` is zero,
+    /// and [`NormFreqError::AboveNyquist`] if `freq_hz` is more than half of
+    /// it.
+    pub fn from_hz(freq_hz: u64, sample_rate: u64) -> Result<Self, NormFreqError> {
+        if sample_rate == 0 {
+            return Err(NormFreqError::ZeroSampleRate);
+        }
+        if freq_hz as u128 * 2 > sample_rate as u128 {
+            return Err(NormFreqError::AboveNyquist);
+        }
+        let raw = fraction_bits(freq_hz, sample_rate, frac_bits::<N>());
+        Ok(NormFreq(Poui(N::from(raw).unwrap())))
+    }
+
+    /// A frequency given directly in cycles per sample.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`NormFreqError::AboveNyquist`] if `cycles` is more than 1/2.
+    pub fn new(cycles: Poui<N>) -> Result<Self, NormFreqError> {
+        if cycles > Self::nyquist().0 {
+            return Err(NormFreqError::AboveNyquist);
+        }
+        Ok(NormFreq(cycles))
+    }
+
+    /// The Nyquist frequency, 1/2 cycle per sample.
+    pub fn nyquist() -> Self {
+        NormFreq(Poui(N::one() << (frac_bits::<N>() - 1)))
+    }
+
+    /// The frequency in cycles per sample.
+    pub fn as_poui(self) -> Poui<N> {
+        self.0
+    }
+
+    /// The phase increment per sample of an oscillator at this frequency,
+    /// which is the frequency itself.
+    pub fn phase_increment(self) -> Poui<N> {
+        self.0
+    }
+
+    /// The frequency in hertz at the given sample rate, rounded down.
+    pub fn to_hz(self, sample_rate: u64) -> u64 {
+        ((self.0 .0.to_u128().unwrap() * sample_rate as u128) >> frac_bits::<N>()) as u64
+    }
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> From<NormFreq<N>> for Nco<N> {
+    fn from(freq: NormFreq<N>) -> Self {
+        Nco::new(freq.phase_increment())
+    }
+}
+
+#[test]
+fn norm_freq_matches_nco_tuning() {
+    for (freq, rate) in [
+        (0, 44_100),
+        (440, 44_100),
+        (1_000, 48_000),
+        (22_050, 44_100),
+    ] {
+        let norm = NormFreq::<u32>::from_hz(freq, rate).unwrap();
+        assert_eq!(
+            norm.as_poui(),
+            Nco::<u32>::with_frequency(freq, rate).tuning_word()
+        );
+        assert!(norm.to_hz(rate).abs_diff(freq) <= 1, "{freq}");
+    }
+    assert_eq!(
+        NormFreq::<u16>::from_hz(24_000, 48_000),
+        Ok(NormFreq::nyquist())
+    );
+    assert_eq!(
+        NormFreq::<u16>::from_hz(1, 0),
+        Err(NormFreqError::ZeroSampleRate)
+    );
+    assert_eq!(
+        NormFreq::<u64>::from_hz(u64::MAX, u64::MAX),
+        Err(NormFreqError::AboveNyquist)
+    );
+}
+
+#[test]
+fn norm_freq_rejects_frequencies_above_nyquist() {
+    assert_eq!(NormFreq::new(Poui(128u8)), Ok(NormFreq::nyquist()));
+    assert_eq!(NormFreq::new(Poui(129u8)), Err(NormFreqError::AboveNyquist));
+    assert!(NormFreq::new(Poui(u128::MAX)).is_err());
+}
@@ -0,0 +1,277 @@
+//! Fuzzy logic.
+//!
+//! A `Poui` makes a natural fuzzy truth value: 0 is false, the largest
+//! value is as true as it gets, and everything in between is partly true.
+//! This module provides the standard Zadeh operators, [`and`], [`or`], and
+//! [`not`], some [`Membership`] functions for turning crisp inputs into
+//! truth values, and [`mamdani`] for inferring a crisp output from rules.
+//!
+//! ```rust
+//! use poui::fuzzy;
+//! use poui::fuzzy::Membership;
+//! use poui::fuzzy::Trapezoidal;
+//! use poui::fuzzy::Triangular;
+//! use poui::Poui;
+//!
+//! // Fan speed from temperature, both as fractions of their ranges.
+//! let cold = Trapezoidal::new(Poui(0u8), Poui(0), Poui(64), Poui(128));
+//! let hot = Trapezoidal::new(Poui(128u8), Poui(192), Poui(255), Poui(255));
+//! let slow = Triangular::new(Poui(0u8), Poui(32), Poui(128));
+//! let fast = Triangular::new(Poui(128u8), Poui(224), Poui(255));
+//!
+//! let temperature = Poui(160);
+//! let rules = [(cold.degree(temperature), slow), (hot.degree(temperature), fast)];
+//! let speed = fuzzy::mamdani(&rules, 64).unwrap();
+//! assert!(speed > Poui(128));
+//! assert_eq!(fuzzy::and(cold.degree(temperature), fuzzy::not(hot.degree(temperature))), Poui(0));
+//! ```
+
+use crate::easing::exp2;
+use crate::easing::from_q62;
+use crate::easing::to_q62;
+use crate::probability::ratio;
+use crate::probability::LOG2_E;
+use crate::weighted_mean;
+use crate::Poui;
+use crate::StrictWiden;
+use num_traits::AsPrimitive;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+
+/// The fuzzy conjunction of two truth values, their minimum.
+pub fn and<N: PrimInt + Unsigned + WrappingAdd>(a: Poui<N>, b: Poui<N>) -> Poui<N> {
+    a.min(b)
+}
+
+/// The fuzzy disjunction of two truth values, their maximum.
+pub fn or<N: PrimInt + Unsigned + WrappingAdd>(a: Poui<N>, b: Poui<N>) -> Poui<N> {
+    a.max(b)
+}
+
+/// The fuzzy negation of a truth value, its complement.
+///
+/// The complement of 0 is the largest `Poui`, and vice versa, so negating
+/// twice always gives back the original value.
+pub fn not<N: PrimInt + Unsigned + WrappingAdd>(a: Poui<N>) -> Poui<N> {
+    Poui(N::max_value() - a.0)
+}
+
+/// A fuzzy set over the unit interval, giving how much each point belongs
+/// to it.
+pub trait Membership<N: PrimInt + Unsigned + WrappingAdd> {
+    /// The degree to which `x` belongs to the set, as a truth value.
+    fn degree(&self, x: Poui<N>) -> Poui<N>;
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd, T: Membership<N> + ?Sized> Membership<N> for &T {
+    fn degree(&self, x: Poui<N>) -> Poui<N> {
+        (**self).degree(x)
+    }
+}
+
+/// A membership function rising linearly from 0 to full membership, staying
+/// there, then falling linearly back to 0.
+///
+/// The edges are exact, with each degree computed as a rounded ratio of the
+/// distances along the slope. A slope of zero width is a vertical edge, so
+/// with the first two points at 0 the set is a left shoulder, and with the
+/// last two at the largest `Poui`, a right shoulder.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Trapezoidal<N: PrimInt + Unsigned + WrappingAdd> {
+    points: [Poui<N>; 4],
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Trapezoidal<N> {
+    /// The set rising from `a` to `b`, full from `b` to `c`, and falling
+    /// from `c` to `d`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the points are out of order.
+    pub fn new(a: Poui<N>, b: Poui<N>, c: Poui<N>, d: Poui<N>) -> Self {
+        assert!(a <= b && b <= c && c <= d, "points are out of order");
+        Trapezoidal {
+            points: [a, b, c, d],
+        }
+    }
+
+    /// The corners of the trapezoid.
+    pub fn points(&self) -> [Poui<N>; 4] {
+        self.points
+    }
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Membership<N> for Trapezoidal<N> {
+    fn degree(&self, x: Poui<N>) -> Poui<N> {
+        let [a, b, c, d] = self.points.map(|p| p.0.to_u128().unwrap());
+        let x = x.0.to_u128().unwrap();
+        if x < a {
+            Poui(N::zero())
+        } else if x < b {
+            ratio(x - a, b - a)
+        } else if x <= c {
+            Poui(N::max_value())
+        } else if x < d {
+            ratio(d - x, d - c)
+        } else {
+            Poui(N::zero())
+        }
+    }
+}
+
+/// A membership function rising linearly from 0 to full membership at a
+/// single peak, then falling linearly back to 0.
+///
+/// This is a [`Trapezoidal`] set with a flat top of zero width.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Triangular<N: PrimInt + Unsigned + WrappingAdd> {
+    trapezoid: Trapezoidal<N>,
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Triangular<N> {
+    /// The set rising from `left` to `peak`, and falling to `right`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the points are out of order.
+    pub fn new(left: Poui<N>, peak: Poui<N>, right: Poui<N>) -> Self {
+        Triangular {
+            trapezoid: Trapezoidal::new(left, peak, peak, right),
+        }
+    }
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Membership<N> for Triangular<N> {
+    fn degree(&self, x: Poui<N>) -> Poui<N> {
+        self.trapezoid.degree(x)
+    }
+}
+
+/// A bell-shaped membership function, `exp(−d² / 2σ²)` at a distance `d`
+/// from its center.
+///
+/// The bell is evaluated in Q62, and its tails are flushed to 0 once they
+/// fall below `2^-62`, about nine widths from the center. Unlike the other
+/// sets, it gives some membership to every point near it, so its rules
+/// always fire a little.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Gaussian<N: PrimInt + Unsigned + WrappingAdd> {
+    center: Poui<N>,
+    width: Poui<N>,
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Gaussian<N> {
+    /// The bell centered on `center`, with standard deviation `width`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `width` is zero.
+    pub fn new(center: Poui<N>, width: Poui<N>) -> Self {
+        assert!(width.0 != N::zero(), "a Gaussian needs a nonzero width");
+        Gaussian { center, width }
+    }
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Membership<N> for Gaussian<N> {
+    fn degree(&self, x: Poui<N>) -> Poui<N> {
+        let d = Poui(x.0.max(self.center.0) - x.0.min(self.center.0));
+        let width = to_q62(self.width).max(1);
+        let r = (to_q62(d) << 62) / width;
+        // The bell is below 2^-62 from about 9.3 widths out, and bailing out
+        // early keeps the square in range.
+        if r >= 10 << 62 {
+            return Poui(N::zero());
+        }
+        let half_r2 = ((r >> 31) * (r >> 31)) >> 1;
+        let exponent = (half_r2 >> 31) * (LOG2_E >> 31);
+        if exponent > 62 << 62 {
+            return Poui(N::zero());
+        }
+        from_q62(exp2(-exponent))
+    }
+}
+
+/// Infers a crisp output from fuzzy rules, by Mamdani's method.
+///
+/// Each rule pairs the truth of its condition, usually built from input
+/// memberships with [`and`], [`or`], and [`not`], with the fuzzy set of its
+/// conclusion. Each conclusion is clipped to the truth of its condition,
+/// the clipped sets are combined with [`or`], and the result is the
+/// centroid of the combination, sampled at the midpoints of `resolution`
+/// equal slices of the unit interval. Returns `None` if no rule fires.
+///
+/// # Panics
+///
+/// Panics if `resolution` is zero.
+pub fn mamdani<N, M, S>(rules: &[(Poui<N>, S)], resolution: u32) -> Option<Poui<N>>
+where
+    N: PrimInt + Unsigned + WrappingAdd + StrictWiden<Widened = M> + 'static,
+    M: PrimInt + AsPrimitive<N>,
+    S: Membership<N>,
+{
+    assert!(resolution != 0, "cannot sample at a resolution of zero");
+    let points: Vec<_> = (0..resolution)
+        .map(|k| Poui::detent(2 * k + 1, 2 * resolution))
+        .collect();
+    let weights: Vec<_> = points
+        .iter()
+        .map(|&y| {
+            rules
+                .iter()
+                .fold(Poui(N::zero()), |combined, (truth, set)| {
+                    or(combined, and(*truth, set.degree(y)))
+                })
+        })
+        .collect();
+    weighted_mean(&points, &weights)
+}
+
+#[test]
+fn fuzzy_operators_obey_de_morgan() {
+    for a in (0..=255u8).step_by(5) {
+        for b in (0..=255u8).step_by(7) {
+            let (a, b) = (Poui(a), Poui(b));
+            assert_eq!(not(and(a, b)), or(not(a), not(b)));
+            assert_eq!(not(not(a)), a);
+        }
+    }
+    assert_eq!(not(Poui(0u32)), Poui(u32::MAX));
+}
+
+#[test]
+fn membership_functions_have_exact_edges() {
+    let trapezoid = Trapezoidal::new(Poui(10u8), Poui(20), Poui(30), Poui(50));
+    let degrees: Vec<_> = [0u8, 10, 15, 20, 25, 30, 40, 50, 60]
+        .map(|x| trapezoid.degree(Poui(x)).0)
+        .to_vec();
+    assert_eq!(degrees, [0, 0, 128, 255, 255, 255, 128, 0, 0]);
+    let shoulder = Trapezoidal::new(Poui(0u16), Poui(0), Poui(100), Poui(200));
+    assert_eq!(shoulder.degree(Poui(0)), Poui(u16::MAX));
+    let peak = Triangular::new(Poui(0u16), Poui(1000), Poui(3000));
+    assert_eq!(peak.degree(Poui(1000)), Poui(u16::MAX));
+    assert_eq!(peak.degree(Poui(2000)), Poui(0x8000));
+    let bell = Gaussian::new(Poui(1u32 << 31), Poui(1 << 28));
+    for sigmas in 0..8u32 {
+        let x = Poui((1u32 << 31) + sigmas * (1 << 28));
+        let expected = (-(sigmas as f64).powi(2) / 2.0).exp() * 4294967296.0;
+        let actual = bell.degree(x).0 as f64;
+        assert!(
+            (actual - expected.min(u32::MAX as f64)).abs() < 2.0,
+            "{sigmas}"
+        );
+    }
+}
+
+#[test]
+fn mamdani_interpolates_between_rules() {
+    let low = Triangular::new(Poui(0u16), Poui(0x2000), Poui(0x4000));
+    let high = Triangular::new(Poui(0xC000u16), Poui(0xE000), Poui(0xFFFF));
+    let both = [(Poui(u16::MAX), low), (Poui(u16::MAX), high)];
+    let balanced = mamdani(&both, 256).unwrap();
+    assert!(balanced.0.abs_diff(0x8000) < 0x100, "{balanced:?}");
+    let mostly_high = [(Poui(0x1000), low), (Poui(u16::MAX), high)];
+    assert!(mamdani(&mostly_high, 256).unwrap() > Poui(0xC000));
+    let none = [(Poui(0u16), low)];
+    assert_eq!(mamdani(&none, 16), None);
+}
@@ -0,0 +1,143 @@
+use crate::frac_bits;
+use crate::Poui;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+use std::hash::Hash;
+use std::hash::Hasher;
+
+/// A 64-bit FNV-1a hasher whose result is scrambled by the SplitMix64
+/// finalizer, so every bit of it depends on every input byte.
+///
+/// Unlike the standard library's default hasher, whose algorithm may change
+/// between releases, this gives the same hash of the same bytes forever,
+/// which is what sticky sampling and bucketing decisions need. Integers are
+/// hashed as little-endian bytes, and `usize` as a `u64`, so the hash of an
+/// integer doesn't depend on the platform either. Which bytes other keys
+/// write is up to their `Hash` impls, though, and std doesn't promise to
+/// keep those the same across Rust versions for `str`, slices, tuples or
+/// derived impls.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct StableHasher(u64);
+
+impl StableHasher {
+    pub(crate) fn new() -> Self {
+        StableHasher(0xCBF2_9CE4_8422_2325)
+    }
+}
+
+impl Hasher for StableHasher {
+    fn write(&mut self, bytes: &[u8]) {
+        for &byte in bytes {
+            self.0 = (self.0 ^ byte as u64).wrapping_mul(0x0000_0100_0000_01B3);
+        }
+    }
+
+    fn write_u16(&mut self, n: u16) {
+        self.write(&n.to_le_bytes());
+    }
+
+    fn write_u32(&mut self, n: u32) {
+        self.write(&n.to_le_bytes());
+    }
+
+    fn write_u64(&mut self, n: u64) {
+        self.write(&n.to_le_bytes());
+    }
+
+    fn write_u128(&mut self, n: u128) {
+        self.write(&n.to_le_bytes());
+    }
+
+    fn write_usize(&mut self, n: usize) {
+        self.write_u64(n as u64);
+    }
+
+    fn finish(&self) -> u64 {
+        mix(self.0)
+    }
+}
+
+/// The SplitMix64 finalizer.
+fn mix(mut x: u64) -> u64 {
+    x = (x ^ (x >> 30)).wrapping_mul(0xBF58_476D_1CE4_E5B9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94D0_49BB_1331_11EB);
+    x ^ (x >> 31)
+}
+
+/// Hashes `key` to a `Poui` that is uniformly distributed, as far as the
+/// hash is, and always the same for the same key.
+pub(crate) fn hash_poui<N: PrimInt + Unsigned + WrappingAdd>(key: impl Hash) -> Poui<N> {
+    let mut hasher = StableHasher::new();
+    key.hash(&mut hasher);
+    let hash = hasher.finish();
+    let bits = frac_bits::<N>();
+    let raw = if bits <= 64 {
+        (hash >> (64 - bits)) as u128
+    } else {
+        (hash as u128) << 64 | mix(hash ^ 0x9E37_79B9_7F4A_7C15) as u128
+    };
+    Poui(N::from(raw).unwrap())
+}
+
+/// Decides whether to sample the item identified by `key`, at the given
+/// rate, without any random state.
+///
+/// The key is hashed to a uniform `Poui`, and the item is sampled if that is
+/// below `rate`, so across many keys the fraction sampled is `rate`, and
+/// the decision for each key is sticky: the same key always gets the same
+/// answer, and raising the rate only ever adds keys. This suits tracing and
+/// telemetry, where every span of a trace should make the same decision.
+///
+/// The hash is a fixed function of the bytes that the key's `Hash` impl
+/// writes, the same on every platform and in every process, so the
+/// decisions survive restarts and agree between services built with the
+/// same compiler version. The standard library doesn't promise that
+/// strings, slices, tuples and derived impls write the same bytes in every
+/// Rust version, so for them a decision can change when the compiler is
+/// upgraded.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::should_sample;
+/// use poui::Poui;
+///
+/// let rate = Poui(0x1999_999Au32); // 10%
+/// let sampled = (0..10_000).filter(|id| should_sample(id, rate)).count();
+/// assert!((900..1100).contains(&sampled));
+/// assert_eq!(should_sample("trace-42", rate), should_sample("trace-42", rate));
+/// assert!(!should_sample("trace-42", Poui(0u32)));
+/// ```
+pub fn should_sample<N: PrimInt + Unsigned + WrappingAdd>(key: impl Hash, rate: Poui<N>) -> bool {
+    hash_poui::<N>(key) < rate
+}
+
+#[test]
+fn stable_hasher_is_fnv1a_then_mixed() {
+    let mut hasher = StableHasher::new();
+    hasher.write(b"a");
+    // FNV-1a of "a" is 0xAF63DC4C8601EC8C.
+    assert_eq!(hasher.finish(), mix(0xAF63_DC4C_8601_EC8C));
+    assert_eq!(hash_poui::<u8>("key"), hash_poui::<u8>("key"));
+    assert_eq!(
+        hash_poui::<u8>(7u32).0 as u64,
+        hash_poui::<u64>(7u32).0 >> 56
+    );
+}
+
+#[test]
+fn should_sample_rate_is_accurate_and_monotone() {
+    let rates = [Poui(0u16), Poui(0x4000), Poui(0xC000), Poui(u16::MAX)];
+    let mut previous = 0;
+    for rate in rates {
+        let sampled: Vec<_> = (0..20_000u32).filter(|&i| should_sample(i, rate)).collect();
+        let expected = 20_000.0 * rate.0 as f64 / 65536.0;
+        assert!((sampled.len() as f64 - expected).abs() < 400.0, "{rate:?}");
+        assert!(sampled.len() >= previous);
+        previous = sampled.len();
+    }
+    let narrow = Poui(0x4000u16);
+    let wide = Poui(0x4000u128 << 112);
+    assert!((0..1000u32).all(|i| should_sample(i, narrow) == should_sample(i, wide)));
+}
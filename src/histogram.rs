@@ -0,0 +1,200 @@
+use crate::Poui;
+use crate::Simplex;
+use crate::StrictWiden;
+use num_traits::AsPrimitive;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+use std::marker::PhantomData;
+
+/// Counts of `Poui` samples in equal bins over the unit interval.
+///
+/// A linear histogram's bin `k` holds the samples in `[k / bins, (k + 1) /
+/// bins)`. A circular histogram, for angles, centers its bins on the
+/// multiples of `1 / bins` instead, so bin 0 straddles 0 and collects
+/// samples from both ends of the interval. Either way, the bin of a sample
+/// is computed exactly, as by [`Poui::quantize_floor`] and
+/// [`Poui::quantize`].
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Histogram;
+/// use poui::Poui;
+///
+/// let mut linear = Histogram::new(4);
+/// let mut circular = Histogram::circular(4);
+/// for x in [Poui(250u8), Poui(10), Poui(100)] {
+///     linear.insert(x);
+///     circular.insert(x);
+/// }
+/// assert_eq!(linear.counts(), [1, 1, 0, 1]);
+/// assert_eq!(circular.counts(), [2, 0, 1, 0]);
+/// assert_eq!(circular.mode(), Some(0));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Histogram<N: PrimInt + Unsigned + WrappingAdd> {
+    counts: Vec<u64>,
+    circular: bool,
+    samples: PhantomData<N>,
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Histogram<N> {
+    /// An empty histogram with `bins` equal bins, starting at 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bins` is zero.
+    pub fn new(bins: u32) -> Self {
+        assert!(bins != 0, "a histogram needs at least one bin");
+        Histogram {
+            counts: vec![0; bins as usize],
+            circular: false,
+            samples: PhantomData,
+        }
+    }
+
+    /// An empty circular histogram with `bins` equal bins, centered on the
+    /// multiples of `1 / bins`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bins` is zero.
+    pub fn circular(bins: u32) -> Self {
+        Histogram {
+            circular: true,
+            ..Self::new(bins)
+        }
+    }
+
+    /// The number of bins.
+    pub fn bins(&self) -> u32 {
+        self.counts.len() as u32
+    }
+
+    /// Whether the bins wrap around the end of the interval.
+    pub fn is_circular(&self) -> bool {
+        self.circular
+    }
+
+    /// The bin that `x` falls into.
+    pub fn bin(&self, x: Poui<N>) -> usize {
+        if self.circular {
+            x.quantize(self.bins()).1 as usize % self.counts.len()
+        } else {
+            x.quantize_floor(self.bins()).1 as usize
+        }
+    }
+
+    /// Counts a sample.
+    pub fn insert(&mut self, x: Poui<N>) {
+        let bin = self.bin(x);
+        self.counts[bin] += 1;
+    }
+
+    /// Adds the counts of another histogram with the same bins.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the histograms have different bins.
+    pub fn merge(&mut self, other: &Self) {
+        assert!(
+            self.bins() == other.bins() && self.circular == other.circular,
+            "histograms have different bins"
+        );
+        for (count, other) in self.counts.iter_mut().zip(&other.counts) {
+            *count += other;
+        }
+    }
+
+    /// The number of samples in each bin.
+    pub fn counts(&self) -> &[u64] {
+        &self.counts
+    }
+
+    /// The total number of samples.
+    pub fn total(&self) -> u64 {
+        self.counts.iter().sum()
+    }
+
+    /// The bin with the most samples, preferring the first on ties, or
+    /// `None` if the histogram is empty.
+    pub fn mode(&self) -> Option<usize> {
+        let max = *self.counts.iter().max()?;
+        (max != 0).then(|| self.counts.iter().position(|&c| c == max).unwrap())
+    }
+
+    /// Forgets every sample.
+    pub fn clear(&mut self) {
+        self.counts.fill(0);
+    }
+}
+
+impl<N, M> Histogram<N>
+where
+    N: PrimInt + Unsigned + WrappingAdd + StrictWiden<Widened = M> + 'static,
+    M: PrimInt + AsPrimitive<N>,
+{
+    /// The fraction of the samples in each bin, normalized to sum to exactly
+    /// one, or `None` if the histogram is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Histogram;
+    /// use poui::Poui;
+    ///
+    /// let mut histogram = Histogram::new(2);
+    /// histogram.extend([Poui(10u8), Poui(20), Poui(200)]);
+    /// let frequencies = histogram.frequencies().unwrap();
+    /// assert_eq!(frequencies.as_slice(), [Poui(171), Poui(85)]);
+    /// ```
+    pub fn frequencies(&self) -> Option<Simplex<N>> {
+        Simplex::from_weights(&self.counts)
+    }
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Extend<Poui<N>> for Histogram<N> {
+    fn extend<I: IntoIterator<Item = Poui<N>>>(&mut self, samples: I) {
+        for x in samples {
+            self.insert(x);
+        }
+    }
+}
+
+#[test]
+fn histogram_bins_are_exact() {
+    let mut linear = Histogram::new(3);
+    let mut circular = Histogram::circular(3);
+    for x in 0..=u16::MAX {
+        linear.insert(Poui(x));
+        circular.insert(Poui(x));
+    }
+    // The bins start at the first sample at or above k / 3.
+    assert_eq!(linear.counts(), [21846, 21845, 21845]);
+    assert_eq!(circular.counts(), [21845, 21845, 21846]);
+    assert_eq!(linear.bin(Poui(21845)), 0);
+    assert_eq!(linear.bin(Poui(21846)), 1);
+    assert_eq!(circular.bin(Poui(u16::MAX)), 0);
+    assert_eq!(linear.total(), 65536);
+    assert_eq!(Histogram::<u16>::new(5).mode(), None);
+}
+
+#[test]
+fn histogram_merge_adds_counts() {
+    let mut a = Histogram::new(4);
+    let mut b = Histogram::new(4);
+    a.extend([Poui(0u32), Poui(1 << 31)]);
+    b.extend([Poui(1 << 31), Poui(u32::MAX)]);
+    a.merge(&b);
+    assert_eq!(a.counts(), [1, 0, 2, 1]);
+    assert_eq!(a.mode(), Some(2));
+    a.clear();
+    assert_eq!(a.total(), 0);
+}
+
+#[test]
+#[should_panic]
+fn histogram_merge_rejects_different_bins() {
+    Histogram::<u8>::new(4).merge(&Histogram::circular(4));
+}
@@ -0,0 +1,95 @@
+use crate::Poui;
+use crate::StrictWiden;
+use num_traits::AsPrimitive;
+use num_traits::Num;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+use num_traits::WrappingSub;
+
+/// A hue, as a fraction of the way around the color wheel from red.
+///
+/// Hues are angles, so blending them along the integer line goes the wrong
+/// way around near red: halfway between magenta and orange is red, not
+/// green. `Hue` blends and measures distances around the wheel instead.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Hue;
+/// use poui::Poui;
+///
+/// let magenta = Hue(Poui(0xD555u16));
+/// let orange = Hue(Poui(0x1555u16));
+/// assert_eq!(magenta.blend(orange, Poui(0x8000)), Hue(Poui(0xF555)));
+/// assert_eq!(magenta.distance(orange), Poui(0x4000));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+#[repr(transparent)]
+pub struct Hue<N: Num + WrappingAdd>(pub Poui<N>);
+
+impl<N, M> Hue<N>
+where
+    N: PrimInt + Unsigned + WrappingAdd + WrappingSub + StrictWiden<Widened = M> + 'static,
+    M: PrimInt + AsPrimitive<N>,
+{
+    /// Interpolates between `self` and `other` along the shorter way around
+    /// the color wheel, as [`Poui::lerp_circular`].
+    pub fn blend(self, other: Self, t: Poui<N>) -> Self {
+        Hue(self.0.lerp_circular(other.0, t))
+    }
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd + WrappingSub> Hue<N> {
+    /// The distance between `self` and `other` the shorter way around the
+    /// color wheel, which is at most half a turn.
+    pub fn distance(self, other: Self) -> Poui<N> {
+        let forward = other.0 .0.wrapping_sub(&self.0 .0);
+        let backward = self.0 .0.wrapping_sub(&other.0 .0);
+        Poui(forward.min(backward))
+    }
+
+    /// The hue on the opposite side of the color wheel.
+    pub fn complement(self) -> Self {
+        let half = N::one() << (N::zero().count_zeros() as usize - 1);
+        Hue(Poui(self.0 .0.wrapping_add(&half)))
+    }
+}
+
+impl<N: Num + WrappingAdd> From<Poui<N>> for Hue<N> {
+    fn from(p: Poui<N>) -> Self {
+        Hue(p)
+    }
+}
+
+impl<N: Num + WrappingAdd> From<Hue<N>> for Poui<N> {
+    fn from(hue: Hue<N>) -> Self {
+        hue.0
+    }
+}
+
+#[test]
+fn hue_blend_never_crosses_green() {
+    let green = Hue(Poui(85u8));
+    for t in 0..=255u8 {
+        let blended = Hue(Poui(230u8)).blend(Hue(Poui(20u8)), Poui(t));
+        assert!(blended.distance(green) > Poui(64), "{t}");
+    }
+}
+
+#[test]
+fn hue_distance_is_symmetric() {
+    for (a, b) in [
+        (0u16, 0x8000u16),
+        (0x1000, 0xF000),
+        (0x1234, 0x1234),
+        (0xFFFF, 0),
+    ] {
+        assert_eq!(
+            Hue(Poui(a)).distance(Hue(Poui(b))),
+            Hue(Poui(b)).distance(Hue(Poui(a)))
+        );
+    }
+    assert_eq!(Hue(Poui(0u16)).distance(Hue(Poui(0x8000))), Poui(0x8000));
+    assert_eq!(Hue(Poui(1u64)).complement(), Hue(Poui((1 << 63) + 1)));
+}
@@ -0,0 +1,107 @@
+use crate::Poui;
+use num_traits::Num;
+use num_traits::WrappingAdd;
+
+/// A Schmitt trigger, turning a noisy signal into clean on/off decisions.
+///
+/// The output turns on when the signal rises to the high threshold, and
+/// only turns off again when it falls to the low threshold, so noise smaller
+/// than the gap between them can't make the output chatter.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Hysteresis;
+/// use poui::Poui;
+///
+/// let mut trigger = Hysteresis::new(Poui(0x4000u16), Poui(0xC000u16));
+/// assert!(!trigger.feed(Poui(0xB000)));
+/// assert!(trigger.feed(Poui(0xC000)));
+/// assert!(trigger.feed(Poui(0x5000)));
+/// assert!(!trigger.feed(Poui(0x4000)));
+/// assert!(!trigger.feed(Poui(0xB000)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hysteresis<N: Num + WrappingAdd> {
+    low: Poui<N>,
+    high: Poui<N>,
+    state: bool,
+}
+
+impl<N: Num + WrappingAdd + Ord + Copy> Hysteresis<N> {
+    /// A trigger that is initially off, turning on at `high` and off at
+    /// `low`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `low > high`.
+    pub fn new(low: Poui<N>, high: Poui<N>) -> Self {
+        assert!(
+            low <= high,
+            "low threshold must not exceed the high threshold"
+        );
+        Hysteresis {
+            low,
+            high,
+            state: false,
+        }
+    }
+
+    /// Sets the state of the output, e.g. to start a trigger on.
+    pub fn with_state(self, state: bool) -> Self {
+        Hysteresis { state, ..self }
+    }
+
+    /// The threshold the signal must fall to to turn the output off.
+    pub fn low(&self) -> Poui<N> {
+        self.low
+    }
+
+    /// The threshold the signal must rise to to turn the output on.
+    pub fn high(&self) -> Poui<N> {
+        self.high
+    }
+
+    /// The current output.
+    pub fn state(&self) -> bool {
+        self.state
+    }
+
+    /// Updates the trigger with a new reading, and returns the output.
+    pub fn feed(&mut self, x: Poui<N>) -> bool {
+        if x >= self.high {
+            self.state = true;
+        } else if x <= self.low {
+            self.state = false;
+        }
+        self.state
+    }
+}
+
+#[test]
+fn hysteresis_ignores_noise_between_thresholds() {
+    let mut trigger = Hysteresis::new(Poui(100u8), Poui(150u8));
+    let noisy = [120, 149, 101, 150, 101, 149, 100, 149, 120];
+    let out: Vec<bool> = noisy.iter().map(|&x| trigger.feed(Poui(x))).collect();
+    assert_eq!(
+        out,
+        [false, false, false, true, true, true, false, false, false]
+    );
+}
+
+#[test]
+fn hysteresis_edge_cases() {
+    let mut on = Hysteresis::new(Poui(-10i8), Poui(10i8)).with_state(true);
+    assert!(on.feed(Poui(0)));
+    assert!(!on.feed(Poui(-128)));
+    let mut comparator = Hysteresis::new(Poui(50u8), Poui(50u8));
+    assert!(comparator.feed(Poui(50)));
+    assert!(!comparator.feed(Poui(49)));
+    assert_eq!((comparator.low(), comparator.high()), (Poui(50), Poui(50)));
+}
+
+#[test]
+#[should_panic]
+fn hysteresis_rejects_inverted_thresholds() {
+    Hysteresis::new(Poui(2u8), Poui(1u8));
+}
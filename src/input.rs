@@ -0,0 +1,261 @@
+use crate::frac_bits;
+use crate::Envelope;
+use crate::Poui;
+use crate::StrictWiden;
+use crate::ToSigned;
+use crate::ToUnsigned;
+use num_traits::AsPrimitive;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+
+/// The response curve of an [`InputCurve`], mapping the stick's deflection
+/// past the deadzone to its output.
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub enum InputResponse {
+    /// The output is proportional to the deflection.
+    #[default]
+    Linear,
+    /// The output is the square of the deflection, for finer control near
+    /// the center.
+    Squared,
+    /// The output follows an envelope over the deflection.
+    Custom(Envelope<u16>),
+}
+
+/// Cleans up raw gamepad stick readings.
+///
+/// Sticks rarely rest exactly at the center, and their useful range rarely
+/// feels linear. An `InputCurve` ignores deflections within a deadzone,
+/// rescales the rest so the output still covers the whole range, then
+/// shapes it with a [`InputResponse`] curve. The sign of the input is kept.
+///
+/// A stick's two axes can be cleaned up independently, or with *radial*
+/// scaling, where the deadzone and response apply to the distance from the
+/// center and the direction is kept. Radial scaling avoids the axes
+/// snapping to zero when the stick is pushed nearly along the other axis.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::InputCurve;
+/// use poui::InputResponse;
+/// use poui::Poui;
+///
+/// let curve = InputCurve::new(Poui(0x2000), InputResponse::Linear);
+/// assert_eq!(curve.apply(Poui(0x0800)), Poui(0));
+/// assert_eq!(curve.apply(Poui(-0x4800)), Poui(-0x4000));
+/// assert_eq!(curve.apply(Poui(i16::MAX)), Poui(i16::MAX));
+///
+/// let radial = curve.with_radial(true);
+/// assert_eq!(radial.apply_stick(Poui(0x0A00), Poui(0x0A00)), (Poui(0), Poui(0)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct InputCurve {
+    deadzone: Poui<u16>,
+    response: InputResponse,
+    radial: bool,
+}
+
+impl InputCurve {
+    /// A curve ignoring deflections up to `deadzone`, and shaping the rest
+    /// with `response`. Radial scaling is off.
+    pub fn new(deadzone: Poui<u16>, response: InputResponse) -> Self {
+        InputCurve {
+            deadzone,
+            response,
+            radial: false,
+        }
+    }
+
+    /// Turns radial scaling on or off for [`InputCurve::apply_stick`].
+    pub fn with_radial(self, radial: bool) -> Self {
+        InputCurve { radial, ..self }
+    }
+
+    /// The deadzone.
+    pub fn deadzone(&self) -> Poui<u16> {
+        self.deadzone
+    }
+
+    /// The response curve.
+    pub fn response(&self) -> &InputResponse {
+        &self.response
+    }
+
+    /// Whether radial scaling is on.
+    pub fn is_radial(&self) -> bool {
+        self.radial
+    }
+
+    /// Cleans up a reading from a single axis.
+    pub fn apply(&self, x: Poui<i16>) -> Poui<i16> {
+        let out = self.shape(x.0.unsigned_abs() as u32 * 2);
+        Poui(signed(out, x.0 < 0))
+    }
+
+    /// Cleans up a reading from a stick's two axes.
+    pub fn apply_stick(&self, x: Poui<i16>, y: Poui<i16>) -> (Poui<i16>, Poui<i16>) {
+        if !self.radial {
+            return (self.apply(x), self.apply(y));
+        }
+        let (x, y) = (x.0 as i64, y.0 as i64);
+        // The distance from the center, with 16 fractional bits.
+        let distance = ((x * x + y * y) as u64).isqrt() * 2;
+        if distance == 0 {
+            return (Poui(0), Poui(0));
+        }
+        let out = self.shape(distance.min(1 << 16) as u32) as i64;
+        let scale = |v: i64| {
+            let v = v * out / distance as i64;
+            Poui(v.clamp(i16::MIN as i64, i16::MAX as i64) as i16)
+        };
+        (scale(x), scale(y))
+    }
+
+    /// Applies the deadzone and response to a deflection in [0, 1], with
+    /// 16 fractional bits.
+    fn shape(&self, deflection: u32) -> u32 {
+        let deadzone = self.deadzone.0 as u32;
+        if deflection <= deadzone {
+            return 0;
+        }
+        let range = (1 << 16) - deadzone as u64;
+        let r = ((((deflection - deadzone) as u64) << 16) + range / 2) / range;
+        match &self.response {
+            InputResponse::Linear => r as u32,
+            InputResponse::Squared => ((r * r) >> 16) as u32,
+            InputResponse::Custom(envelope) => {
+                let r = Poui(r.min(u16::MAX as u64) as u16);
+                envelope.eval(r).map_or(0, |y| y.0 as u32)
+            }
+        }
+    }
+}
+
+impl<S, U, M> Poui<S>
+where
+    S: PrimInt + WrappingAdd + StrictWiden<Widened = M> + ToUnsigned<Unsigned = U> + 'static,
+    U: PrimInt + Unsigned + WrappingAdd + ToSigned<Signed = S>,
+    M: PrimInt + AsPrimitive<S>,
+{
+    /// The "expo" stick curve of RC transmitters, `(1 - amount) x + amount
+    /// x³`, which softens the response near the center while still reaching
+    /// full deflection.
+    ///
+    /// With `amount` at 0 the response is linear, and towards 1 it
+    /// approaches a cubic. The curve is computed on the magnitude and the
+    /// sign is reapplied, so it is exactly symmetric, and 0 and -1 map to
+    /// themselves.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// let half = Poui(0x8000u16);
+    /// assert_eq!(Poui(0x4000i16).expo(half), Poui(0x2800));
+    /// assert_eq!(Poui(-0x4000i16).expo(half), Poui(-0x2800));
+    /// assert_eq!(Poui(i16::MIN).expo(half), Poui(i16::MIN));
+    /// assert_eq!(Poui(0x1234i16).expo(Poui(0)), Poui(0x1234));
+    /// ```
+    pub fn expo(self, amount: Poui<U>) -> Self {
+        let f = frac_bits::<S>();
+        let bits = f + 1;
+        let x = self.0.widen();
+        let m = if x < M::zero() { M::zero() - x } else { x };
+        let cube = (((m * m) >> f) * m) >> f;
+        let mask = (M::one() << bits) - M::one();
+        let amount = amount.0.to_signed().widen() & mask;
+        let out = m - (((m - cube) * amount) >> bits);
+        Poui(if x < M::zero() { M::zero() - out } else { out }.as_())
+    }
+}
+
+/// Converts a magnitude in [0, 1], with 16 fractional bits, to a signed
+/// backing with the given sign, saturating at the ends.
+fn signed(magnitude: u32, negative: bool) -> i16 {
+    let v = (magnitude / 2) as i32;
+    if negative {
+        (-v).max(i16::MIN as i32) as i16
+    } else {
+        v.min(i16::MAX as i32) as i16
+    }
+}
+
+#[test]
+fn input_curve_deadzone_rescales() {
+    let curve = InputCurve::new(Poui(0x4000), InputResponse::Linear);
+    assert_eq!(curve.apply(Poui(0x2000)), Poui(0));
+    assert_eq!(curve.apply(Poui(-0x2000)), Poui(0));
+    assert_eq!(curve.apply(Poui(0x5000)), Poui(0x4000));
+    assert_eq!(curve.apply(Poui(i16::MIN)), Poui(i16::MIN));
+    let mut last = 0;
+    for x in 0..=i16::MAX {
+        let y = curve.apply(Poui(x)).0;
+        assert!(y >= last);
+        assert_eq!(curve.apply(Poui(-x)).0, -y);
+        last = y;
+    }
+}
+
+#[test]
+fn input_curve_responses() {
+    let squared = InputCurve::new(Poui(0), InputResponse::Squared);
+    assert_eq!(squared.apply(Poui(0x4000)), Poui(0x2000));
+    assert_eq!(squared.apply(Poui(-0x4000)), Poui(-0x2000));
+    let envelope = Envelope::new(vec![(Poui(0), Poui(0x8000)), (Poui(0x8000), Poui(0xFFFF))]);
+    let custom = InputCurve::new(Poui(0), InputResponse::Custom(envelope.unwrap()));
+    assert_eq!(custom.apply(Poui(0x2000)), Poui(0x5FFF));
+    assert_eq!(custom.apply(Poui(i16::MIN)), Poui(-0x7FFF));
+}
+
+#[test]
+fn input_curve_radial() {
+    let curve = InputCurve::new(Poui(0x2000), InputResponse::Linear).with_radial(true);
+    // A reading along a diagonal keeps its direction.
+    let (x, y) = curve.apply_stick(Poui(0x3000), Poui(0x3000));
+    assert_eq!(x, y);
+    assert!(x.0 > 0);
+    // Per-axis, the small axis would be lost in the deadzone.
+    let (x, y) = curve.apply_stick(Poui(0x6000), Poui(0x0E00));
+    assert!(x.0 > 0x5000 && y.0 > 0);
+    let (x, y) = curve
+        .clone()
+        .with_radial(false)
+        .apply_stick(Poui(0x6000), Poui(0x0E00));
+    assert!(x.0 > 0x5000 && y.0 == 0);
+    // Corners are pulled onto the unit circle.
+    let (x, y) = curve.apply_stick(Poui(i16::MIN), Poui(i16::MIN));
+    assert!((x.0 as i32 + 0x5A82).abs() <= 2 && x == y, "{x:?}");
+}
+
+#[test]
+fn expo_is_symmetric_with_exact_endpoints() {
+    for amount in [0u8, 1, 77, 128, 255] {
+        assert_eq!(Poui(0i8).expo(Poui(amount)), Poui(0));
+        assert_eq!(Poui(i8::MIN).expo(Poui(amount)), Poui(i8::MIN));
+        for x in 1..=i8::MAX {
+            let y = Poui(x).expo(Poui(amount)).0;
+            assert_eq!(Poui(-x).expo(Poui(amount)).0, -y);
+        }
+    }
+    assert_eq!(Poui(i64::MIN).expo(Poui(u64::MAX)), Poui(i64::MIN));
+    assert_eq!(Poui(-5i64).expo(Poui(0)), Poui(-5));
+}
+
+#[test]
+fn expo_matches_reference() {
+    for amount in [0u16, 0x4000, 0xC000, 0xFFFF] {
+        let a = amount as f64 / 65536.0;
+        for x in (i16::MIN..=i16::MAX).step_by(13) {
+            let v = x as f64 / 32768.0;
+            let expected = ((1.0 - a) * v + a * v * v * v) * 32768.0;
+            let actual = Poui(x).expo(Poui(amount)).0 as f64;
+            assert!(
+                (actual - expected).abs() <= 2.0,
+                "{x} {amount}: {actual} {expected}"
+            );
+        }
+    }
+}
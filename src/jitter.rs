@@ -0,0 +1,181 @@
+use crate::frac_bits;
+use crate::probability::mul_hi;
+use crate::Poui;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+#[cfg(feature = "rand")]
+use num_traits::WrappingSub;
+#[cfg(feature = "rand")]
+use rand::Rng;
+use std::time::Duration;
+
+/// A random multiplier for the full jitter backoff strategy, uniform over
+/// the whole unit interval.
+///
+/// Multiplying the exponentially growing delay by this spreads retries out
+/// the most, which is usually the best way to avoid retrying in lockstep.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::full_jitter;
+/// use poui::Poui;
+/// use std::time::Duration;
+///
+/// let delay = Duration::from_millis(800) * full_jitter::<u32, _>(&mut rand::thread_rng());
+/// assert!(delay < Duration::from_millis(800));
+/// ```
+#[cfg(feature = "rand")]
+pub fn full_jitter<N, R>(rng: &mut R) -> Poui<N>
+where
+    N: PrimInt + Unsigned + WrappingAdd,
+    R: Rng + ?Sized,
+{
+    uniform(rng)
+}
+
+/// A random multiplier for the equal jitter backoff strategy, uniform over
+/// the upper half of the unit interval.
+///
+/// This keeps at least half of each delay, trading some spread for never
+/// retrying too soon.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::equal_jitter;
+/// use poui::Poui;
+///
+/// let m = equal_jitter::<u8, _>(&mut rand::thread_rng());
+/// assert!(m >= Poui(128));
+/// ```
+#[cfg(feature = "rand")]
+pub fn equal_jitter<N, R>(rng: &mut R) -> Poui<N>
+where
+    N: PrimInt + Unsigned + WrappingAdd,
+    R: Rng + ?Sized,
+{
+    let half = N::one() << (frac_bits::<N>() - 1);
+    Poui(half | uniform::<N, R>(rng).0 >> 1)
+}
+
+/// The next multiplier for the decorrelated jitter backoff strategy,
+/// uniform from `prev` up to three times `prev`, saturating at the top of
+/// the interval.
+///
+/// Unlike the other strategies, each delay depends on the one before it,
+/// rather than on the number of attempts. As multipliers of the maximal
+/// delay, the first should be the fraction of it to start from, and the
+/// delays then grow at random until they saturate at the maximum. A
+/// `prev` of zero stays zero.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::decorrelated_jitter;
+/// use poui::Poui;
+///
+/// let rng = &mut rand::thread_rng();
+/// let first = Poui(0x0100u16);
+/// let second = decorrelated_jitter(first, rng);
+/// assert!(first <= second && second < Poui(0x0300));
+/// ```
+#[cfg(feature = "rand")]
+pub fn decorrelated_jitter<N, R>(prev: Poui<N>, rng: &mut R) -> Poui<N>
+where
+    N: PrimInt + Unsigned + WrappingAdd + WrappingSub,
+    R: Rng + ?Sized,
+{
+    let three = N::one() + N::one() + N::one();
+    let high = prev.0.checked_mul(&three).unwrap_or(N::max_value());
+    if high == prev.0 {
+        return prev;
+    }
+    rng.gen_range(prev..Poui(high))
+}
+
+/// A uniformly random `Poui`, for unsigned backings.
+#[cfg(feature = "rand")]
+fn uniform<N, R>(rng: &mut R) -> Poui<N>
+where
+    N: PrimInt + Unsigned + WrappingAdd,
+    R: Rng + ?Sized,
+{
+    Poui(N::from(rng.gen::<u128>() >> (128 - frac_bits::<N>())).unwrap())
+}
+
+/// Scales a duration by a fraction, rounding down to the nanosecond.
+///
+/// The product is computed exactly, so e.g. a backoff delay can be
+/// multiplied by a jitter multiplier directly.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Poui;
+/// use std::time::Duration;
+///
+/// assert_eq!(Duration::from_secs(3) * Poui(0x4000u16), Duration::from_millis(750));
+/// assert_eq!(Poui(0x8000u16) * Duration::from_nanos(3), Duration::from_nanos(1));
+/// ```
+impl<N: PrimInt + Unsigned + WrappingAdd> std::ops::Mul<Poui<N>> for Duration {
+    type Output = Duration;
+
+    fn mul(self, rhs: Poui<N>) -> Duration {
+        let fraction = rhs.0.to_u128().unwrap() << (128 - frac_bits::<N>());
+        let nanos = mul_hi(self.as_nanos(), fraction);
+        Duration::new(
+            (nanos / 1_000_000_000) as u64,
+            (nanos % 1_000_000_000) as u32,
+        )
+    }
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> std::ops::Mul<Duration> for Poui<N> {
+    type Output = Duration;
+
+    fn mul(self, rhs: Duration) -> Duration {
+        rhs * self
+    }
+}
+
+#[test]
+fn duration_scaling_is_exact() {
+    let max = Duration::MAX;
+    assert_eq!(max * Poui(0u8), Duration::ZERO);
+    assert_eq!(
+        max * Poui(u128::MAX),
+        Duration::from_nanos(0) + (max - Duration::from_nanos(1))
+    );
+    assert_eq!(
+        Duration::from_nanos(1000) * Poui(u64::MAX),
+        Duration::from_nanos(999)
+    );
+    assert_eq!(
+        Duration::from_secs(10) * Poui(0x8000_0000u32),
+        Duration::from_secs(5)
+    );
+}
+
+#[cfg(feature = "rand")]
+#[test]
+fn jitter_multipliers_stay_in_range() {
+    let mut rng = crate::random::test_rng();
+    for _ in 0..1000 {
+        assert!(equal_jitter::<u16, _>(&mut rng) >= Poui(0x8000));
+        let m = decorrelated_jitter(Poui(1000u16), &mut rng);
+        assert!((1000..3000).contains(&m.0), "{m:?}");
+    }
+    assert_eq!(decorrelated_jitter(Poui(0u8), &mut rng), Poui(0));
+    assert_eq!(decorrelated_jitter(Poui(u8::MAX), &mut rng), Poui(u8::MAX));
+    let mut prev = Poui(1u32);
+    for _ in 0..100 {
+        prev = decorrelated_jitter(prev, &mut rng);
+    }
+    assert!(prev > Poui(u32::MAX / 3));
+    let sum: u64 = (0..10_000)
+        .map(|_| full_jitter::<u8, _>(&mut rng).0 as u64)
+        .sum();
+    assert!((sum / 10_000).abs_diff(128) < 5);
+}
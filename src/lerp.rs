@@ -0,0 +1,143 @@
+use crate::frac_bits;
+use crate::Poui;
+use crate::StrictWiden;
+use num_traits::AsPrimitive;
+use num_traits::Num;
+use num_traits::PrimInt;
+use num_traits::WrappingAdd;
+
+/// Types that can be linearly interpolated by a `Poui` parameter.
+///
+/// `a.lerp(b, t)` should be `a` at `t == 0`, and approach `b` as `t`
+/// approaches 1. For a signed `N`, a negative `t` should extrapolate
+/// backwards, away from `b`, as [`catmull_rom`](crate::catmull_rom) relies
+/// on. The animation machinery in this crate, such as
+/// [`Track`](crate::Track) and the [`bezier`](crate::bezier) curves, works
+/// with any type implementing this trait.
+///
+/// The trait is implemented for `Poui` itself, for the integer primitives of
+/// up to 64 bits with any parameter, and componentwise for arrays and
+/// tuples of up to four elements, so that e.g. an RGB color as `[u8; 3]` or
+/// a point as `(i32, i32)` can be animated directly.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Lerp;
+/// use poui::Poui;
+///
+/// assert_eq!(10i32.lerp(20, Poui(128u8)), 15);
+/// assert_eq!([0u8, 100, 200].lerp([200, 100, 0], Poui(64u8)), [50, 100, 150]);
+/// assert_eq!((0u16, -8i64).lerp((1000, 8), Poui(0xC000u16)), (750, 4));
+///
+/// // Signed parameters extrapolate backwards.
+/// assert_eq!(10i32.lerp(20, Poui(-64i8)), 5);
+/// ```
+pub trait Lerp<N: Num + WrappingAdd> {
+    /// Interpolates between `self` and `other` at parameter `t`.
+    fn lerp(self, other: Self, t: Poui<N>) -> Self;
+}
+
+impl<N, M> Lerp<N> for Poui<N>
+where
+    N: PrimInt + WrappingAdd + StrictWiden<Widened = M> + 'static,
+    M: PrimInt + AsPrimitive<N>,
+{
+    fn lerp(self, other: Self, t: Poui<N>) -> Self {
+        Poui::lerp(self, other, t)
+    }
+}
+
+/// Converts a parameter to Q62, keeping its sign.
+fn param_q62<N: PrimInt + WrappingAdd>(t: Poui<N>) -> i128 {
+    let bits = t.0.count_zeros() as usize + t.0.count_ones() as usize;
+    let drop = bits.saturating_sub(64);
+    let q = (t.0 >> drop).to_i128().unwrap();
+    let frac = frac_bits::<N>() - drop;
+    if frac >= 62 {
+        q >> (frac - 62)
+    } else {
+        q << (62 - frac)
+    }
+}
+
+macro_rules! impl_lerp_int {
+    ($($t:ty),*) => {
+        $(
+            /// Interpolates the integers, rounding towards `self`, and
+            /// saturating if a signed parameter extrapolates out of range.
+            impl<N: PrimInt + WrappingAdd> Lerp<N> for $t {
+                fn lerp(self, other: Self, t: Poui<N>) -> Self {
+                    let a = self as i128;
+                    let b = other as i128;
+                    let t = param_q62(t);
+                    let result = if b >= a {
+                        a + (((b - a) * t) >> 62)
+                    } else {
+                        a - (((a - b) * t) >> 62)
+                    };
+                    result.clamp(<$t>::MIN as i128, <$t>::MAX as i128) as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_lerp_int!(u8, u16, u32, u64, usize, i8, i16, i32, i64, isize);
+
+impl<N: Num + WrappingAdd + Copy, V: Lerp<N>, const K: usize> Lerp<N> for [V; K] {
+    fn lerp(self, other: Self, t: Poui<N>) -> Self {
+        let mut other = other.into_iter();
+        self.map(|a| a.lerp(other.next().unwrap(), t))
+    }
+}
+
+macro_rules! impl_lerp_tuple {
+    ($(($($v:ident $i:tt),*)),*) => {
+        $(
+            impl<N: Num + WrappingAdd + Copy, $($v: Lerp<N>),*> Lerp<N> for ($($v,)*) {
+                fn lerp(self, other: Self, t: Poui<N>) -> Self {
+                    ($(self.$i.lerp(other.$i, t),)*)
+                }
+            }
+        )*
+    };
+}
+
+impl_lerp_tuple!((A 0), (A 0, B 1), (A 0, B 1, C 2), (A 0, B 1, C 2, D 3));
+
+#[test]
+fn lerp_integers_round_towards_self() {
+    assert_eq!(0u8.lerp(255, Poui(u8::MAX)), 254);
+    assert_eq!(255u8.lerp(0, Poui(u8::MAX)), 1);
+    assert_eq!(i64::MIN.lerp(i64::MAX, Poui(1u64 << 63)), -1);
+    assert_eq!(u64::MAX.lerp(0, Poui(1u128 << 127)), 1 << 63);
+    assert_eq!(7usize.lerp(7, Poui(0x1234u16)), 7);
+    assert_eq!((-3i16).lerp(5, Poui(0u32)), -3);
+}
+
+#[test]
+fn lerp_integers_extrapolate_and_saturate() {
+    assert_eq!(100u8.lerp(200, Poui(i16::MIN)), 0);
+    assert_eq!(100i32.lerp(200, Poui(-(1i64 << 62))), 50);
+    assert_eq!(i64::MAX.lerp(i64::MIN, Poui(-1i32 << 30)), i64::MAX);
+    assert_eq!((-100i8).lerp(100, Poui(i8::MIN)), -128);
+}
+
+#[test]
+fn lerp_composites() {
+    let t = Poui(0x4000u16);
+    assert_eq!(
+        [Poui(0u16), Poui(0x8000)].lerp([Poui(0x8000), Poui(0)], t),
+        [Poui(0x2000), Poui(0x6000)]
+    );
+    assert_eq!((4u8,).lerp((8,), t), (5,));
+    assert_eq!(
+        (0u32, 0i8, [0u64; 2], Poui(0u16)).lerp((4, -4, [4, 8], Poui(4)), t),
+        (1, -1, [1, 2], Poui(1))
+    );
+    assert_eq!(
+        crate::bezier::quadratic([0i32, 0], [100, 0], [100, 100], Poui(128u8)),
+        [75, 25]
+    );
+}
@@ -0,0 +1,227 @@
+use crate::prng::XorShift;
+use crate::Nco;
+use crate::Poui;
+use crate::ToSigned;
+use num_traits::AsPrimitive;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+
+/// The waveform of an [`Lfo`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum LfoShape {
+    /// A sine wave, starting at 0 and rising.
+    #[default]
+    Sine,
+    /// A triangle wave, starting at -1, as for [`Poui::to_triangle`].
+    Triangle,
+    /// A rising sawtooth, starting at -1, as for [`Poui::to_sawtooth`].
+    Sawtooth,
+    /// A square wave, high for the first half of each cycle.
+    Square,
+    /// A random level, chosen afresh at the start of each cycle.
+    SampleHold,
+}
+
+/// A low-frequency oscillator, for modulating synthesizer parameters or
+/// animating lights.
+///
+/// An `Lfo` drives one of the standard [`LfoShape`]s from an [`Nco`], and
+/// yields a bipolar signed `Poui` each tick. The shape can be shifted by a
+/// phase offset, e.g. to run several LFOs in quadrature from one rate, and
+/// [`Lfo::retrigger`] restarts the cycle, e.g. on each new note. Rates are
+/// set in millihertz, since LFOs often run well below 1 Hz.
+///
+/// The random levels of [`LfoShape::SampleHold`] come from a small built-in
+/// generator, so they're repeatable for a given seed.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Lfo;
+/// use poui::Poui;
+/// use poui::LfoShape;
+///
+/// // A 0.5 Hz square wave, ticked at 4 Hz.
+/// let mut lfo = Lfo::<u16>::with_rate(LfoShape::Square, 500, 4);
+/// let levels: Vec<_> = (0..8).map(|_| lfo.tick()).collect();
+/// assert_eq!(levels, [[Poui(i16::MAX); 4], [Poui(i16::MIN); 4]].concat());
+///
+/// lfo.set_shape(LfoShape::Sawtooth);
+/// lfo.set_phase_offset(Poui(0x8000));
+/// lfo.retrigger();
+/// assert_eq!(lfo.tick(), Poui(0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Lfo<U, S = <U as ToSigned>::Signed>
+where
+    U: PrimInt + Unsigned + WrappingAdd + ToSigned<Signed = S>,
+    S: PrimInt + WrappingAdd,
+{
+    nco: Nco<U>,
+    shape: LfoShape,
+    phase_offset: Poui<U>,
+    held: Poui<S>,
+    rng: XorShift,
+}
+
+impl<U, S> Lfo<U, S>
+where
+    U: PrimInt + Unsigned + WrappingAdd + ToSigned<Signed = S>,
+    S: PrimInt + WrappingAdd + 'static,
+    i128: AsPrimitive<S>,
+{
+    /// An LFO with the given shape, driven by `nco`.
+    pub fn new(shape: LfoShape, nco: Nco<U>) -> Self {
+        let mut lfo = Lfo {
+            nco,
+            shape,
+            phase_offset: Poui(U::zero()),
+            held: Poui(S::zero()),
+            rng: XorShift::new(0),
+        };
+        lfo.roll();
+        lfo
+    }
+
+    /// An LFO with the given shape and rate, as for [`Lfo::set_rate`].
+    pub fn with_rate(shape: LfoShape, millihertz: u64, sample_rate: u64) -> Self {
+        let mut lfo = Lfo::new(shape, Nco::new(Poui(U::zero())));
+        lfo.set_rate(millihertz, sample_rate);
+        lfo
+    }
+
+    /// The waveform.
+    pub fn shape(&self) -> LfoShape {
+        self.shape
+    }
+
+    /// Changes the waveform, keeping the phase.
+    pub fn set_shape(&mut self, shape: LfoShape) {
+        self.shape = shape;
+    }
+
+    /// Sets the rate to `millihertz` thousandths of a cycle per second, when
+    /// ticked `sample_rate` times per second.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sample_rate` is zero, or so large that it overflows when
+    /// multiplied by 1000.
+    pub fn set_rate(&mut self, millihertz: u64, sample_rate: u64) {
+        let sample_rate = sample_rate
+            .checked_mul(1000)
+            .expect("sample rate too large");
+        self.nco.set_frequency(millihertz, sample_rate);
+    }
+
+    /// The phase offset added before shaping.
+    pub fn phase_offset(&self) -> Poui<U> {
+        self.phase_offset
+    }
+
+    /// Sets the phase offset added before shaping.
+    pub fn set_phase_offset(&mut self, offset: Poui<U>) {
+        self.phase_offset = offset;
+    }
+
+    /// Reseeds the generator for [`LfoShape::SampleHold`] and picks a new level.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = XorShift::new(seed);
+        self.roll();
+    }
+
+    /// The phase accumulator driving the LFO.
+    pub fn nco(&self) -> &Nco<U> {
+        &self.nco
+    }
+
+    /// The phase accumulator driving the LFO, e.g. to retune it.
+    pub fn nco_mut(&mut self) -> &mut Nco<U> {
+        &mut self.nco
+    }
+
+    /// Restarts the cycle, picking a new level for [`LfoShape::SampleHold`].
+    pub fn retrigger(&mut self) {
+        self.nco.set_phase(Poui(U::zero()));
+        self.roll();
+    }
+
+    /// Returns the output at the current phase, and advances to the next
+    /// phase.
+    pub fn tick(&mut self) -> Poui<S> {
+        let phase = self.nco.tick() + self.phase_offset;
+        let next = self.nco.phase() + self.phase_offset;
+        let value = match self.shape {
+            LfoShape::Sine => phase.sin(),
+            LfoShape::Triangle => phase.to_triangle(),
+            LfoShape::Sawtooth => phase.to_sawtooth(),
+            LfoShape::Square if phase.pulse(half()) => Poui(S::max_value()),
+            LfoShape::Square => Poui(S::min_value()),
+            LfoShape::SampleHold => self.held,
+        };
+        if next < phase {
+            self.roll();
+        }
+        value
+    }
+
+    fn roll(&mut self) {
+        self.held = self.rng.next_poui::<U>().to_signed();
+    }
+}
+
+impl<U, S> Iterator for Lfo<U, S>
+where
+    U: PrimInt + Unsigned + WrappingAdd + ToSigned<Signed = S>,
+    S: PrimInt + WrappingAdd + 'static,
+    i128: AsPrimitive<S>,
+{
+    type Item = Poui<S>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.tick())
+    }
+}
+
+/// One half, as a `Poui`.
+fn half<U: PrimInt + Unsigned + WrappingAdd>() -> Poui<U> {
+    Poui(U::one() << (U::zero().count_zeros() as usize - 1))
+}
+
+#[test]
+fn lfo_shapes_follow_the_phase() {
+    let mut lfo = Lfo::<u8>::new(LfoShape::Sine, Nco::new(Poui(64)));
+    let sine: Vec<i8> = lfo.by_ref().take(4).map(|p| p.0).collect();
+    assert_eq!(sine, [0, 127, 0, -128]);
+    lfo.set_shape(LfoShape::Triangle);
+    let triangle: Vec<i8> = lfo.by_ref().take(4).map(|p| p.0).collect();
+    assert_eq!(triangle, [-128, 0, 126, -2]);
+    lfo.set_shape(LfoShape::Sawtooth);
+    lfo.set_phase_offset(Poui(32));
+    let saw: Vec<i8> = lfo.take(4).map(|p| p.0).collect();
+    assert_eq!(saw, [-96, -32, 32, 96]);
+}
+
+#[test]
+fn lfo_sample_hold_changes_once_per_cycle() {
+    let mut lfo = Lfo::<u32>::with_rate(LfoShape::SampleHold, 250_000, 1000);
+    let levels: Vec<_> = lfo.by_ref().take(12).collect();
+    for cycle in levels.chunks(4) {
+        assert!(cycle.iter().all(|&l| l == cycle[0]));
+    }
+    assert!(levels[0] != levels[4] && levels[4] != levels[8]);
+    let mut same_seed = Lfo::<u32>::with_rate(LfoShape::SampleHold, 250_000, 1000);
+    assert_eq!(same_seed.by_ref().take(12).collect::<Vec<_>>(), levels);
+    same_seed.set_seed(1);
+    assert_ne!(same_seed.tick(), levels[0]);
+}
+
+#[test]
+fn lfo_retrigger_restarts_the_cycle() {
+    let mut lfo = Lfo::<u16>::with_rate(LfoShape::Sawtooth, 1_000, 100);
+    lfo.by_ref().take(37).for_each(drop);
+    lfo.retrigger();
+    assert_eq!(lfo.tick(), Poui(i16::MIN));
+    assert_eq!(lfo.nco().tuning_word(), Poui(655));
+}
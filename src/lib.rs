@@ -2,7 +2,200 @@ use num_traits::AsPrimitive;
 use num_traits::Bounded;
 use num_traits::Float;
 use num_traits::Num;
+use num_traits::PrimInt;
 use num_traits::WrappingAdd;
+use num_traits::WrappingSub;
+
+mod adsr;
+mod alias;
+mod arc;
+mod atomic;
+pub mod bezier;
+pub mod biquad;
+mod calibration;
+mod cdf;
+mod checked;
+mod circular;
+pub mod compass;
+mod decibel;
+mod dot;
+pub mod easing;
+mod entropy;
+mod envelope;
+mod ewma;
+mod fir;
+mod float;
+mod freq;
+pub mod fuzzy;
+mod hash;
+mod histogram;
+mod hue;
+mod hysteresis;
+mod input;
+mod jitter;
+mod lerp;
+mod lfo;
+mod literal;
+mod markov;
+mod mixed;
+mod nco;
+mod nonzero;
+mod one_pole;
+mod pan;
+mod pm;
+pub mod policy;
+mod precision;
+mod prng;
+mod probability;
+mod quadrature;
+mod quantile;
+mod quantize;
+mod ramp;
+#[cfg(feature = "rand")]
+mod random;
+mod ratio;
+mod remap;
+mod resample;
+pub mod rollout;
+mod rotation;
+mod sample_hold;
+mod saturating;
+mod sequence;
+mod sigma_delta;
+mod sign;
+mod simplex;
+mod slew;
+mod smooth;
+mod smooth_damp;
+mod sobol;
+mod spline;
+mod stats;
+mod taper;
+mod timestep;
+mod track;
+mod trig;
+mod trig_table;
+mod turns;
+mod tween;
+mod unit_circle;
+mod wave;
+mod wavetable;
+
+pub use adsr::Adsr;
+pub use adsr::AdsrStage;
+pub use alias::AliasTable;
+pub use arc::Arc;
+pub use arc::ArcSet;
+pub use atomic::AtomicBacking;
+pub use atomic::AtomicPoui;
+pub use calibration::Calibration;
+pub use calibration::CalibrationError;
+pub use cdf::cumsum;
+pub use cdf::cumulative;
+pub use cdf::Cdf;
+pub use cdf::CdfError;
+pub use checked::CheckedPoui;
+pub use checked::PouiOverflow;
+pub use circular::TurnDirection;
+pub use dot::convolve;
+pub use dot::dot;
+pub use entropy::entropy;
+pub use entropy::kl_divergence;
+pub use envelope::Envelope;
+pub use envelope::EnvelopeError;
+pub use ewma::Ewma;
+pub use fir::Fir;
+pub use float::FromFloatError;
+pub use freq::NormFreq;
+pub use freq::NormFreqError;
+pub use hash::should_sample;
+pub use histogram::Histogram;
+pub use hue::Hue;
+pub use hysteresis::Hysteresis;
+pub use input::InputCurve;
+pub use input::InputResponse;
+#[cfg(feature = "rand")]
+pub use jitter::decorrelated_jitter;
+#[cfg(feature = "rand")]
+pub use jitter::equal_jitter;
+#[cfg(feature = "rand")]
+pub use jitter::full_jitter;
+pub use lerp::Lerp;
+pub use lfo::Lfo;
+pub use lfo::LfoShape;
+pub use markov::TransitionMatrix;
+pub use markov::TransitionMatrixError;
+pub use mixed::Mixed;
+pub use nco::Nco;
+pub use nonzero::NonZeroBacking;
+pub use nonzero::NonZeroPoui;
+pub use one_pole::OnePole;
+pub use pan::crossfade;
+pub use pan::pan;
+pub use pan::PanLaw;
+pub use pm::PmOperator;
+pub use probability::choose_weighted;
+pub use probability::posterior;
+pub use quadrature::QuadratureCordic;
+pub use quadrature::QuadratureOsc;
+pub use quadrature::QuadratureSinCos;
+pub use quantile::median;
+pub use quantile::percentiles;
+pub use quantile::quantile;
+pub use quantile::QuantileMethod;
+pub use ramp::Ramp;
+#[cfg(feature = "rand")]
+pub use random::sample_weighted;
+#[cfg(feature = "rand")]
+pub use random::stratified;
+#[cfg(feature = "rand")]
+pub use random::stratified_2d;
+#[cfg(feature = "rand")]
+pub use random::UniformPoui;
+pub use ratio::FromRatioError;
+pub use remap::Remap;
+pub use resample::FractionalDelay;
+pub use resample::ResampleRatio;
+pub use rotation::rotate;
+pub use rotation::Rotation;
+pub use sample_hold::SampleHold;
+pub use saturating::SatPoui;
+pub use sequence::GoldenRatioSeq;
+pub use sequence::Halton;
+pub use sequence::HaltonPoints;
+pub use sequence::R2Seq;
+pub use sequence::VanDerCorput;
+pub use sigma_delta::SigmaDelta;
+pub use sign::ToSigned;
+pub use sign::ToUnsigned;
+pub use simplex::Simplex;
+pub use slew::SlewLimiter;
+pub use smooth_damp::SmoothDamp;
+pub use sobol::Sobol;
+pub use spline::catmull_rom;
+pub use stats::weighted_blend;
+pub use stats::weighted_mean;
+pub use stats::OnlineStats;
+pub use taper::audio_taper;
+pub use taper::audio_taper_inverse;
+pub use timestep::FixedTimestep;
+pub use track::Keyframe;
+pub use track::Track;
+pub use trig_table::TrigTable;
+pub use turns::Turns;
+pub use tween::Timeline;
+pub use tween::Tween;
+pub use tween::TweenRepeat;
+pub use unit_circle::UnitCirclePoint;
+pub use wave::PulseWave;
+pub use wavetable::SampleInterpolation;
+pub use wavetable::Wavetable;
+
+#[doc(hidden)]
+pub mod __private {
+    pub use crate::literal::float_literal_bits;
+    pub use crate::literal::ratio_literal_bits;
+}
 
 /// A point on the unit interval.
 ///
@@ -85,9 +278,29 @@ use num_traits::WrappingAdd;
 /// let c = a + b;
 /// assert_eq!(c, Poui(-128i8));
 /// ```
-#[derive(Debug, PartialEq, Clone, Copy)]
+///
+/// `Poui<N>` is guaranteed to have the same memory layout as `N`. With the
+/// `bytemuck` feature enabled, it implements `bytemuck::Pod`, so slices of
+/// `Poui` can be cast to slices of the underlying integers and back, e.g. for
+/// uploading to a GPU.
+///
+/// With the `rand` feature enabled, `Poui` implements the standard
+/// distribution of the `rand` crate, so a uniformly distributed `Poui` is
+/// just `rng.gen()`.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[repr(transparent)]
 pub struct Poui<N: Num + WrappingAdd>(pub N);
 
+// SAFETY: `Poui` is `#[repr(transparent)]` over `N`, so it has the same layout
+// and validity as `N`, and every bit pattern of `N` is a valid `Poui<N>`.
+#[cfg(feature = "bytemuck")]
+unsafe impl<N: Num + WrappingAdd + bytemuck::Zeroable> bytemuck::Zeroable for Poui<N> {}
+
+// SAFETY: As above, `Poui<N>` has the same layout as `N` and contains no
+// padding, so it is `Pod` whenever `N` is.
+#[cfg(feature = "bytemuck")]
+unsafe impl<N: Num + WrappingAdd + bytemuck::Pod> bytemuck::Pod for Poui<N> {}
+
 impl<N: Num + WrappingAdd> std::ops::Add for Poui<N> {
     type Output = Self;
 
@@ -171,6 +384,30 @@ impl Widen for i128 {
     }
 }
 
+/// A backing whose [`Widen`] is strictly wider than itself, so that the
+/// product of two values, or a value shifted left by its fractional bits,
+/// fits in the widened type.
+///
+/// This is every backing but the 128-bit ones, which widen to themselves.
+/// Bounding on it rather than on [`Widen`] turns what would be an overflow
+/// at run time into a compile error:
+///
+/// ```compile_fail
+/// use poui::Poui;
+///
+/// Poui(0u128).lerp(Poui(u128::MAX), Poui(1 << 127));
+/// ```
+pub trait StrictWiden: Widen {}
+
+impl StrictWiden for u8 {}
+impl StrictWiden for u16 {}
+impl StrictWiden for u32 {}
+impl StrictWiden for u64 {}
+impl StrictWiden for i8 {}
+impl StrictWiden for i16 {}
+impl StrictWiden for i32 {}
+impl StrictWiden for i64 {}
+
 pub trait Shorten {
     type Shortened;
     fn shorten(self) -> Self::Shortened;
@@ -256,18 +493,114 @@ impl Shorten for i8 {
 /// is shortened back to the original type, e.g. `u16` is shortened to `u8`,
 /// taking the 8 most significant bits of the result.
 ///
+/// Signed backings have one fewer fractional bit, because the top bit is the
+/// sign, so their product is shifted left by one before it is shortened. This
+/// makes `Poui(64i8) * Poui(64i8)` equal to `Poui(32i8)`, i.e. one half times
+/// one half is one quarter. The only product that doesn't fit is `-1 * -1`,
+/// which wraps around to `-1`.
+///
 /// This method provably avoids overflow, but it may lose precision. For
 /// example, multiplying `Poui(1u8)` by `Poui(1u8)` results in `Poui(0u8)`,
 /// because the product is `1/512`, which is rounded down to `0`.
 impl<N, M> std::ops::Mul for Poui<N>
 where
     N: Num + WrappingAdd + Widen<Widened = M>,
-    M: Num + WrappingAdd + std::ops::Mul + Shorten<Shortened = N>,
+    M: PrimInt + WrappingAdd + Shorten<Shortened = N>,
 {
     type Output = Self;
 
     fn mul(self, rhs: Self) -> Self::Output {
-        Poui((self.0.widen() * rhs.0.widen()).shorten())
+        let product = self.0.widen() * rhs.0.widen();
+        let product = if M::min_value() < M::zero() {
+            product << 1
+        } else {
+            product
+        };
+        Poui(product.shorten())
+    }
+}
+
+/// Implements `const fn` arithmetic for concrete backings.
+///
+/// Trait methods can't be called in a `const` context, so the generic
+/// operator impls above can't be used to build constants. These inherent
+/// methods duplicate them for each primitive backing instead.
+macro_rules! impl_const_arithmetic {
+    ($($n:ty),*) => {
+        $(
+            impl Poui<$n> {
+                /// Adds two points, wrapping around the end of the interval.
+                ///
+                /// This is the same as the `+` operator, but usable in a
+                /// `const` context.
+                pub const fn wrapping_add(self, rhs: Self) -> Self {
+                    Poui(self.0.wrapping_add(rhs.0))
+                }
+
+                /// Subtracts two points, wrapping around the start of the
+                /// interval.
+                pub const fn wrapping_sub(self, rhs: Self) -> Self {
+                    Poui(self.0.wrapping_sub(rhs.0))
+                }
+            }
+        )*
+    };
+}
+
+impl_const_arithmetic!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+/// Implements `const fn` multiplication for concrete backings that have a
+/// wider type to multiply in.
+macro_rules! impl_const_mul {
+    ($($n:ty => $w:ty, $frac:expr);*) => {
+        $(
+            impl Poui<$n> {
+                /// Multiplies two points.
+                ///
+                /// This is the same as the `*` operator, but usable in a
+                /// `const` context.
+                #[allow(clippy::should_implement_trait)]
+                pub const fn mul(self, rhs: Self) -> Self {
+                    Poui(((self.0 as $w * rhs.0 as $w) >> $frac) as $n)
+                }
+            }
+        )*
+    };
+}
+
+impl_const_mul!(
+    u8 => u16, 8;
+    u16 => u32, 16;
+    u32 => u64, 32;
+    u64 => u128, 64;
+    i8 => i16, 7;
+    i16 => i32, 15;
+    i32 => i64, 31;
+    i64 => i128, 63
+);
+
+impl<N: Num + WrappingAdd> Poui<N> {
+    /// Creates a point on the unit interval from its raw representation.
+    ///
+    /// This is the same as the tuple constructor, but usable as a function
+    /// value and in a `const` context.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// const QUARTERS: [Poui<u8>; 4] = [
+    ///     Poui::new(0),
+    ///     Poui::new(64),
+    ///     Poui::new(64u8).wrapping_add(Poui::new(64)),
+    ///     Poui::new(0u8).wrapping_sub(Poui::new(64)),
+    /// ];
+    /// assert_eq!(QUARTERS[2], Poui(128u8));
+    /// assert_eq!(QUARTERS[3], Poui(192u8));
+    /// ```
+    pub const fn new(n: N) -> Self {
+        Poui(n)
     }
 }
 
@@ -296,6 +629,269 @@ where
     }
 }
 
+/// The number of bits after the binary point in the representation of a
+/// `Poui<N>`.
+///
+/// For unsigned backings every bit is fractional. For signed backings the top
+/// bit is the sign, so `Poui(64i8)` is `0.5` rather than `0.25`.
+fn frac_bits<N: PrimInt>() -> usize {
+    let bits = N::zero().count_zeros() as usize;
+    if N::min_value() < N::zero() {
+        bits - 1
+    } else {
+        bits
+    }
+}
+
+impl<N, M> Poui<N>
+where
+    N: PrimInt + WrappingAdd + StrictWiden<Widened = M> + 'static,
+    M: PrimInt + AsPrimitive<N>,
+{
+    /// Linearly interpolates between `self` and `other`.
+    ///
+    /// A `t` of zero yields `self`, and as `t` approaches one the result
+    /// approaches `other`. The interpolation runs along the integer line, not
+    /// around the circle, so `lerp` between `Poui(250u8)` and `Poui(10u8)`
+    /// passes through `Poui(128u8)`.
+    ///
+    /// The difference between the endpoints is computed in the widened type,
+    /// so this never overflows, and the result is rounded towards `self`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// let a = Poui(64u8);
+    /// let b = Poui(192u8);
+    /// assert_eq!(a.lerp(b, Poui(0u8)), a);
+    /// assert_eq!(a.lerp(b, Poui(128u8)), Poui(128u8));
+    /// assert_eq!(b.lerp(a, Poui(64u8)), Poui(160u8));
+    /// ```
+    pub fn lerp(self, other: Self, t: Self) -> Self {
+        let f = frac_bits::<N>();
+        let a = self.0.widen();
+        let b = other.0.widen();
+        let t = t.0.widen();
+        let result = if b >= a {
+            a + (((b - a) * t) >> f)
+        } else {
+            a - (((a - b) * t) >> f)
+        };
+        Poui(result.as_())
+    }
+
+    /// Finds the parameter `t` such that `self.lerp(other, t) == x`.
+    ///
+    /// This is the inverse of [`Poui::lerp`], and is useful for normalizing a
+    /// value that is known to lie between two bounds, e.g. converting a sensor
+    /// reading back to a position on the unit interval.
+    ///
+    /// Returns `None` if `self == other`, or if `x` does not lie in the
+    /// half-open range from `self` (inclusive) to `other` (exclusive). The
+    /// range may run in either direction.
+    ///
+    /// The quotient is computed with widened integer division and rounded up,
+    /// which for unsigned backings guarantees that `lerp` maps the result back
+    /// to exactly `x`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// let a = Poui(64u8);
+    /// let b = Poui(192u8);
+    /// let t = a.inverse_lerp(b, Poui(128u8)).unwrap();
+    /// assert_eq!(t, Poui(128u8));
+    /// assert_eq!(a.lerp(b, t), Poui(128u8));
+    /// assert_eq!(a.inverse_lerp(b, Poui(200u8)), None);
+    /// ```
+    pub fn inverse_lerp(self, other: Self, x: Self) -> Option<Self> {
+        let f = frac_bits::<N>();
+        let a = self.0.widen();
+        let b = other.0.widen();
+        let x = x.0.widen();
+        let (num, den) = if a < b && a <= x && x < b {
+            (x - a, b - a)
+        } else if b < a && b < x && x <= a {
+            (a - x, a - b)
+        } else {
+            return None;
+        };
+        let num = num << f;
+        let t = (num + den - M::one()) / den;
+        let max = N::max_value().widen();
+        Some(Poui(t.min(max).as_()))
+    }
+}
+
+/// Maps a raw value to a key whose signed-or-unsigned ordering matches the
+/// *unsigned* ordering of its bit pattern.
+///
+/// Circular comparisons need to treat offsets around the circle as unsigned
+/// quantities. For unsigned backings this is the identity, and for signed
+/// backings flipping the sign bit turns unsigned order into signed order.
+fn unsigned_key<N: PrimInt>(n: N) -> N {
+    n ^ N::min_value()
+}
+
+impl<N: PrimInt + WrappingAdd + WrappingSub> Poui<N> {
+    /// Returns the smaller of `self` and `other`.
+    pub fn min(self, other: Self) -> Self {
+        if other < self {
+            other
+        } else {
+            self
+        }
+    }
+
+    /// Returns the larger of `self` and `other`.
+    pub fn max(self, other: Self) -> Self {
+        if other > self {
+            other
+        } else {
+            self
+        }
+    }
+
+    /// Restricts `self` to the closed range `[lo, hi]`.
+    ///
+    /// This is the linear version of clamping: the range does not wrap around
+    /// the end of the interval. See [`Poui::clamp_circular`] for a version that
+    /// does.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `lo > hi`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// let lo = Poui(26u8);
+    /// let hi = Poui(230u8);
+    /// assert_eq!(Poui(10u8).clamp_to(lo, hi), lo);
+    /// assert_eq!(Poui(100u8).clamp_to(lo, hi), Poui(100u8));
+    /// assert_eq!(Poui(250u8).clamp_to(lo, hi), hi);
+    /// ```
+    pub fn clamp_to(self, lo: Self, hi: Self) -> Self {
+        assert!(lo <= hi, "clamp_to called with lo > hi");
+        self.max(lo).min(hi)
+    }
+
+    /// Restricts `self` to the arc running forward from `lo` to `hi`,
+    /// inclusive.
+    ///
+    /// The arc may wrap around the end of the interval: if `lo > hi` then the
+    /// arc covers `[lo, 1)` and `[0, hi]`. If `self` lies outside the arc, the
+    /// result is whichever endpoint is circularly closer, preferring `lo` when
+    /// both are equally far away.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// // The arc from 0.9 to 0.1 of a turn, passing through 0.
+    /// let lo = Poui(230u8);
+    /// let hi = Poui(26u8);
+    /// assert_eq!(Poui(5u8).clamp_circular(lo, hi), Poui(5u8));
+    /// assert_eq!(Poui(240u8).clamp_circular(lo, hi), Poui(240u8));
+    /// assert_eq!(Poui(40u8).clamp_circular(lo, hi), hi);
+    /// assert_eq!(Poui(200u8).clamp_circular(lo, hi), lo);
+    /// ```
+    pub fn clamp_circular(self, lo: Self, hi: Self) -> Self {
+        let arc = unsigned_key(hi.0.wrapping_sub(&lo.0));
+        let offset = unsigned_key(self.0.wrapping_sub(&lo.0));
+        if offset <= arc {
+            return self;
+        }
+        let past_hi = unsigned_key(self.0.wrapping_sub(&hi.0));
+        let before_lo = unsigned_key(lo.0.wrapping_sub(&self.0));
+        if past_hi < before_lo {
+            hi
+        } else {
+            lo
+        }
+    }
+
+    /// Whether `self` lies on the arc running forward from `lo` (inclusive)
+    /// to `hi` (exclusive).
+    ///
+    /// The arc may wrap around the end of the interval: if `lo > hi` then it
+    /// covers `[lo, 1)` and `[0, hi)`, so the same call checks both wrapped
+    /// and unwrapped ranges. If `lo == hi` the arc is empty, matching
+    /// [`Arc::new`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// // The arc from 0.9 to 0.1 of a turn, passing through 0.
+    /// let lo = Poui(230u8);
+    /// let hi = Poui(26u8);
+    /// assert!(Poui(230u8).is_between(lo, hi));
+    /// assert!(Poui(5u8).is_between(lo, hi));
+    /// assert!(!Poui(26u8).is_between(lo, hi));
+    /// assert!(!Poui(128u8).is_between(lo, hi));
+    /// assert!(Poui(128u8).is_between(hi, lo));
+    /// ```
+    pub fn is_between(self, lo: Self, hi: Self) -> bool {
+        let arc = unsigned_key(hi.0.wrapping_sub(&lo.0));
+        unsigned_key(self.0.wrapping_sub(&lo.0)) < arc
+    }
+
+    /// Returns the next representable point after `self`, wrapping around
+    /// from the largest value to the smallest.
+    ///
+    /// This is analogous to [`f32::next_up`], but since the unit interval is
+    /// circular, the successor of the largest value is zero (or `-1.0` for
+    /// signed backings).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui(7u8).next_up(), Poui(8u8));
+    /// assert_eq!(Poui(255u8).next_up(), Poui(0u8));
+    /// ```
+    pub fn next_up(self) -> Self {
+        Poui(self.0.wrapping_add(&N::one()))
+    }
+
+    /// Returns the previous representable point before `self`, wrapping around
+    /// from the smallest value to the largest.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui(8u8).next_down(), Poui(7u8));
+    /// assert_eq!(Poui(0u8).next_down(), Poui(255u8));
+    /// ```
+    pub fn next_down(self) -> Self {
+        Poui(self.0.wrapping_sub(&N::one()))
+    }
+
+    /// Returns the next representable point after `self`, or `None` if `self`
+    /// is the largest representable value.
+    pub fn checked_next_up(self) -> Option<Self> {
+        self.0.checked_add(&N::one()).map(Poui)
+    }
+
+    /// Returns the previous representable point before `self`, or `None` if
+    /// `self` is the smallest representable value.
+    pub fn checked_next_down(self) -> Option<Self> {
+        self.0.checked_sub(&N::one()).map(Poui)
+    }
+}
+
 #[test]
 fn basic_arithmetic_i8() {
     let a = Poui(1i8);
@@ -602,3 +1198,296 @@ fn convert_to_f64_signed() {
     let f: f64 = a.as_();
     assert_eq!(f, 0.5);
 }
+
+#[test]
+fn lerp_endpoints_u8() {
+    let a = Poui(10u8);
+    let b = Poui(250u8);
+    assert_eq!(a.lerp(b, Poui(0u8)), a);
+    assert_eq!(a.lerp(b, Poui(255u8)), Poui(249u8));
+}
+
+#[test]
+fn lerp_halfway_u16() {
+    let a = Poui(1000u16);
+    let b = Poui(3000u16);
+    assert_eq!(a.lerp(b, Poui(32768u16)), Poui(2000u16));
+    assert_eq!(b.lerp(a, Poui(32768u16)), Poui(2000u16));
+}
+
+#[test]
+fn lerp_signed() {
+    let a = Poui(-100i8);
+    let b = Poui(100i8);
+    assert_eq!(a.lerp(b, Poui(64i8)), Poui(0i8));
+    assert_eq!(b.lerp(a, Poui(64i8)), Poui(0i8));
+}
+
+#[test]
+fn inverse_lerp_u8() {
+    let a = Poui(64u8);
+    let b = Poui(192u8);
+    assert_eq!(a.inverse_lerp(b, Poui(64u8)), Some(Poui(0u8)));
+    assert_eq!(a.inverse_lerp(b, Poui(96u8)), Some(Poui(64u8)));
+    assert_eq!(b.inverse_lerp(a, Poui(96u8)), Some(Poui(192u8)));
+}
+
+#[test]
+fn inverse_lerp_out_of_range() {
+    let a = Poui(64u8);
+    let b = Poui(192u8);
+    assert_eq!(a.inverse_lerp(b, Poui(192u8)), None);
+    assert_eq!(a.inverse_lerp(b, Poui(63u8)), None);
+    assert_eq!(b.inverse_lerp(a, Poui(64u8)), None);
+    assert_eq!(a.inverse_lerp(a, Poui(64u8)), None);
+}
+
+#[test]
+fn inverse_lerp_round_trip_u8() {
+    let a = Poui(3u8);
+    let b = Poui(250u8);
+    for x in 3u8..250 {
+        let t = a.inverse_lerp(b, Poui(x)).unwrap();
+        assert_eq!(a.lerp(b, t), Poui(x));
+    }
+}
+
+#[test]
+fn inverse_lerp_round_trip_u32() {
+    let a = Poui(12345u32);
+    let b = Poui(4000000000u32);
+    for x in [12345u32, 12346, 1 << 30, 3999999999] {
+        let t = a.inverse_lerp(b, Poui(x)).unwrap();
+        assert_eq!(a.lerp(b, t), Poui(x));
+    }
+}
+
+#[test]
+fn inverse_lerp_signed() {
+    let a = Poui(-64i8);
+    let b = Poui(64i8);
+    assert_eq!(a.inverse_lerp(b, Poui(0i8)), Some(Poui(64i8)));
+    assert_eq!(a.inverse_lerp(b, Poui(63i8)), Some(Poui(127i8)));
+}
+
+#[test]
+fn ordering_unsigned() {
+    assert!(Poui(1u8) < Poui(2u8));
+    assert!(Poui(255u16) > Poui(0u16));
+}
+
+#[test]
+fn ordering_signed() {
+    assert!(Poui(-1i8) < Poui(0i8));
+    assert!(Poui(i32::MIN) < Poui(i32::MAX));
+}
+
+#[test]
+fn min_max() {
+    assert_eq!(Poui(3u8).min(Poui(7u8)), Poui(3u8));
+    assert_eq!(Poui(3u8).max(Poui(7u8)), Poui(7u8));
+    assert_eq!(Poui(-3i8).min(Poui(7i8)), Poui(-3i8));
+    assert_eq!(Poui(-3i8).max(Poui(-7i8)), Poui(-3i8));
+}
+
+#[test]
+fn clamp_to_u16() {
+    let lo = Poui(6554u16);
+    let hi = Poui(58982u16);
+    assert_eq!(Poui(0u16).clamp_to(lo, hi), lo);
+    assert_eq!(Poui(30000u16).clamp_to(lo, hi), Poui(30000u16));
+    assert_eq!(Poui(65535u16).clamp_to(lo, hi), hi);
+}
+
+#[test]
+fn clamp_to_signed() {
+    let lo = Poui(-10i8);
+    let hi = Poui(10i8);
+    assert_eq!(Poui(-128i8).clamp_to(lo, hi), lo);
+    assert_eq!(Poui(5i8).clamp_to(lo, hi), Poui(5i8));
+    assert_eq!(Poui(127i8).clamp_to(lo, hi), hi);
+}
+
+#[test]
+#[should_panic]
+fn clamp_to_inverted_bounds() {
+    Poui(5u8).clamp_to(Poui(10u8), Poui(0u8));
+}
+
+#[test]
+fn clamp_circular_unwrapped_arc() {
+    let lo = Poui(100u8);
+    let hi = Poui(150u8);
+    assert_eq!(Poui(120u8).clamp_circular(lo, hi), Poui(120u8));
+    assert_eq!(Poui(90u8).clamp_circular(lo, hi), lo);
+    assert_eq!(Poui(160u8).clamp_circular(lo, hi), hi);
+    // 10 is 90 below `lo` but 116 above `hi`.
+    assert_eq!(Poui(10u8).clamp_circular(lo, hi), lo);
+    // 250 is 106 below `lo` but 100 above `hi`.
+    assert_eq!(Poui(250u8).clamp_circular(lo, hi), hi);
+}
+
+#[test]
+fn clamp_circular_wrapped_arc() {
+    let lo = Poui(200u8);
+    let hi = Poui(50u8);
+    assert_eq!(Poui(200u8).clamp_circular(lo, hi), Poui(200u8));
+    assert_eq!(Poui(0u8).clamp_circular(lo, hi), Poui(0u8));
+    assert_eq!(Poui(50u8).clamp_circular(lo, hi), Poui(50u8));
+    assert_eq!(Poui(100u8).clamp_circular(lo, hi), hi);
+    assert_eq!(Poui(150u8).clamp_circular(lo, hi), lo);
+}
+
+#[test]
+fn clamp_circular_signed() {
+    // The arc from 0.75 to -0.75 passes through -1.
+    let lo = Poui(96i8);
+    let hi = Poui(-96i8);
+    assert_eq!(Poui(-128i8).clamp_circular(lo, hi), Poui(-128i8));
+    assert_eq!(Poui(120i8).clamp_circular(lo, hi), Poui(120i8));
+    assert_eq!(Poui(10i8).clamp_circular(lo, hi), lo);
+    assert_eq!(Poui(-10i8).clamp_circular(lo, hi), hi);
+}
+
+#[test]
+fn next_up_down_u8() {
+    assert_eq!(Poui(0u8).next_up(), Poui(1u8));
+    assert_eq!(Poui(1u8).next_down(), Poui(0u8));
+    assert_eq!(Poui(u8::MAX).next_up(), Poui(0u8));
+    assert_eq!(Poui(0u8).next_down(), Poui(u8::MAX));
+}
+
+#[test]
+fn next_up_down_u64() {
+    assert_eq!(Poui(u64::MAX).next_up(), Poui(0u64));
+    assert_eq!(Poui(0u64).next_down(), Poui(u64::MAX));
+    assert_eq!(Poui(41u64).next_up().next_down(), Poui(41u64));
+}
+
+#[test]
+fn next_up_down_signed() {
+    assert_eq!(Poui(-1i16).next_up(), Poui(0i16));
+    assert_eq!(Poui(i16::MAX).next_up(), Poui(i16::MIN));
+    assert_eq!(Poui(i16::MIN).next_down(), Poui(i16::MAX));
+}
+
+#[test]
+fn checked_next_up_down() {
+    assert_eq!(Poui(254u8).checked_next_up(), Some(Poui(255u8)));
+    assert_eq!(Poui(255u8).checked_next_up(), None);
+    assert_eq!(Poui(1u8).checked_next_down(), Some(Poui(0u8)));
+    assert_eq!(Poui(0u8).checked_next_down(), None);
+    assert_eq!(Poui(i8::MAX).checked_next_up(), None);
+    assert_eq!(Poui(i8::MIN).checked_next_down(), None);
+    assert_eq!(Poui(0i8).checked_next_down(), Some(Poui(-1i8)));
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn cast_slice_to_integers() {
+    let pouis = [Poui(1u8), Poui(128u8), Poui(255u8)];
+    let bytes: &[u8] = bytemuck::cast_slice(&pouis);
+    assert_eq!(bytes, &[1u8, 128, 255]);
+}
+
+#[cfg(feature = "bytemuck")]
+#[test]
+fn cast_slice_from_integers() {
+    let ints = [-1i32, 0, i32::MAX];
+    let pouis: &[Poui<i32>] = bytemuck::cast_slice(&ints);
+    assert_eq!(pouis, &[Poui(-1i32), Poui(0i32), Poui(i32::MAX)]);
+    let zeroed: Poui<u64> = bytemuck::Zeroable::zeroed();
+    assert_eq!(zeroed, Poui(0u64));
+}
+
+#[test]
+fn same_layout_as_backing() {
+    assert_eq!(std::mem::size_of::<Poui<u16>>(), std::mem::size_of::<u16>());
+    assert_eq!(
+        std::mem::align_of::<Poui<u128>>(),
+        std::mem::align_of::<u128>()
+    );
+}
+
+#[test]
+fn half_times_half_i8() {
+    let a = Poui(64i8);
+    let b = Poui(64i8);
+    assert_eq!(a * b, Poui(32i8));
+}
+
+#[test]
+fn half_times_half_i32() {
+    let a = Poui(1i32 << 30);
+    let b = Poui(1i32 << 30);
+    assert_eq!(a * b, Poui(1i32 << 29));
+}
+
+#[test]
+fn negative_times_positive_i16() {
+    let a = Poui(-16384i16);
+    let b = Poui(16384i16);
+    assert_eq!(a * b, Poui(-8192i16));
+}
+
+#[test]
+fn minus_one_times_minus_one_wraps_i8() {
+    assert_eq!(Poui(i8::MIN) * Poui(i8::MIN), Poui(i8::MIN));
+}
+
+#[test]
+fn const_arithmetic_in_static() {
+    static CONST_TABLE: [Poui<u16>; 3] = [
+        Poui::new(0x4000),
+        Poui::new(0x4000u16).mul(Poui::new(0x8000)),
+        Poui::new(0x4000u16).wrapping_sub(Poui::new(0x8000)),
+    ];
+    assert_eq!(CONST_TABLE[1], Poui(0x2000u16));
+    assert_eq!(CONST_TABLE[2], Poui(0xC000u16));
+}
+
+#[test]
+fn const_mul_matches_operator() {
+    for a in [i8::MIN, -100, -1, 0, 1, 64, i8::MAX] {
+        for b in [i8::MIN, -64, -1, 0, 1, 100, i8::MAX] {
+            assert_eq!(Poui(a).mul(Poui(b)), Poui(a) * Poui(b));
+        }
+    }
+    for a in [0u32, 1, 12345, 1 << 31, u32::MAX] {
+        for b in [0u32, 1, 54321, 1 << 31, u32::MAX] {
+            assert_eq!(Poui(a).mul(Poui(b)), Poui(a) * Poui(b));
+        }
+    }
+}
+
+#[test]
+fn const_wrapping_add_sub() {
+    const A: Poui<i64> = Poui::new(i64::MAX).wrapping_add(Poui::new(1));
+    const B: Poui<u128> = Poui::new(0u128).wrapping_sub(Poui::new(1));
+    assert_eq!(A, Poui(i64::MIN));
+    assert_eq!(B, Poui(u128::MAX));
+}
+
+#[test]
+fn is_between_matches_arc() {
+    for lo in (0..=255u8).step_by(15) {
+        for hi in (0..=255u8).step_by(17) {
+            let arc = Arc::new(Poui(lo), Poui(hi));
+            for x in 0..=255u8 {
+                assert_eq!(
+                    Poui(x).is_between(Poui(lo), Poui(hi)),
+                    arc.contains(Poui(x))
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn is_between_signed() {
+    assert!(Poui(-128i8).is_between(Poui(100), Poui(-100)));
+    assert!(Poui(127i8).is_between(Poui(100), Poui(-100)));
+    assert!(!Poui(0i8).is_between(Poui(100), Poui(-100)));
+    assert!(Poui(0i8).is_between(Poui(-100), Poui(100)));
+    assert!(!Poui(5i64).is_between(Poui(5), Poui(5)));
+}
@@ -0,0 +1,191 @@
+/// Creates a `Poui` from a float or rational literal at compile time.
+///
+/// The first argument is the backing type, and the second is either a float
+/// literal or a ratio of two integer literals. The literal is converted to the
+/// nearest representable point while compiling, so there is no runtime cost
+/// and no risk of getting the scaling wrong by hand.
+///
+/// ```rust
+/// use poui::poui;
+/// use poui::Poui;
+///
+/// assert_eq!(poui!(u8: 0.25), Poui(64u8));
+/// assert_eq!(poui!(u16: 0.3), Poui(19661u16));
+/// assert_eq!(poui!(u16: 1 / 3), Poui(21845u16));
+/// assert_eq!(poui!(i8: -0.5), Poui(-64i8));
+/// assert_eq!(poui!(i32: -1 / 4), Poui(-(1i32 << 29)));
+///
+/// // The result is a constant, so it can be used in constants and statics.
+/// const THRESHOLD: Poui<u32> = poui!(u32: 0.9);
+/// ```
+///
+/// Literals outside the interval represented by the backing type, i.e. [0, 1)
+/// for unsigned backings or [-1, 1) for signed backings, are rejected with a
+/// compile error:
+///
+/// ```compile_fail
+/// let p = poui::poui!(u8: 1.0);
+/// ```
+///
+/// ```compile_fail
+/// let p = poui::poui!(u8: -0.5);
+/// ```
+///
+/// ```compile_fail
+/// let p = poui::poui!(i16: 3 / 2);
+/// ```
+///
+/// ```compile_fail
+/// let p = poui::poui!(u16: 1 / 0);
+/// ```
+///
+/// Values that are within the interval but round up to 1 saturate to the
+/// largest representable value, just like the `TryFrom<f64>` conversion.
+#[macro_export]
+macro_rules! poui {
+    ($t:ty: $num:literal / $den:literal) => {
+        const {
+            $crate::Poui::<$t>($crate::__private::ratio_literal_bits(
+                $num,
+                $den,
+                <$t>::BITS,
+                <$t>::MIN != 0,
+            ) as $t)
+        }
+    };
+    ($t:ty: $x:literal) => {
+        const {
+            $crate::Poui::<$t>(
+                $crate::__private::float_literal_bits($x, <$t>::BITS, <$t>::MIN != 0) as $t,
+            )
+        }
+    };
+}
+
+/// The largest raw value of a backing with the given width and signedness.
+const fn max_raw(bits: u32, signed: bool) -> u128 {
+    if signed {
+        (1 << (bits - 1)) - 1
+    } else {
+        u128::MAX >> (128 - bits)
+    }
+}
+
+/// Converts a float literal to the bit pattern of the nearest `Poui` with the
+/// given backing, as a `u128` to be truncated by the caller.
+pub const fn float_literal_bits(x: f64, bits: u32, signed: bool) -> u128 {
+    let lo = if signed { -1.0 } else { 0.0 };
+    if !(x >= lo && x < 1.0) {
+        panic!("poui! literal is outside the interval represented by the backing type");
+    }
+    let frac = if signed { bits - 1 } else { bits };
+    // Scale in two halves, so that 2^128 doesn't overflow.
+    let scaled = x * (1u128 << (frac / 2)) as f64 * (1u128 << (frac - frac / 2)) as f64;
+    if scaled >= 0.0 {
+        // Float to integer casts truncate and saturate.
+        let magnitude = (scaled + 0.5) as u128;
+        if magnitude > max_raw(bits, signed) {
+            max_raw(bits, signed)
+        } else {
+            magnitude
+        }
+    } else {
+        let rounded = -scaled - 0.5;
+        let magnitude = rounded as u128;
+        let magnitude = if (magnitude as f64) < rounded {
+            magnitude + 1
+        } else {
+            magnitude
+        };
+        magnitude.wrapping_neg()
+    }
+}
+
+/// Converts a ratio literal to the bit pattern of the nearest `Poui` with the
+/// given backing, as a `u128` to be truncated by the caller.
+pub const fn ratio_literal_bits(num: i128, den: i128, bits: u32, signed: bool) -> u128 {
+    if den == 0 {
+        panic!("poui! literal has a zero denominator");
+    }
+    let negative = num != 0 && (num < 0) != (den < 0);
+    let n = num.unsigned_abs();
+    let d = den.unsigned_abs();
+    if (negative && !signed) || n > d || (n == d && !negative) {
+        panic!("poui! literal is outside the interval represented by the backing type");
+    }
+    let frac = if signed { bits - 1 } else { bits };
+    if n == d {
+        return (1u128 << frac).wrapping_neg();
+    }
+    // Long division, one bit at a time, so that the numerator never has to be
+    // shifted past 128 bits.
+    let mut quotient = 0u128;
+    let mut remainder = n;
+    let mut i = 0;
+    while i < frac {
+        remainder <<= 1;
+        quotient <<= 1;
+        if remainder >= d {
+            remainder -= d;
+            quotient |= 1;
+        }
+        i += 1;
+    }
+    if remainder << 1 >= d && quotient < max_raw(bits, signed) {
+        quotient += 1;
+    }
+    if negative {
+        quotient.wrapping_neg()
+    } else {
+        quotient
+    }
+}
+
+#[test]
+fn float_literals_unsigned() {
+    use crate::Poui;
+    assert_eq!(poui!(u8: 0.0), Poui(0u8));
+    assert_eq!(poui!(u8: 0.5), Poui(128u8));
+    assert_eq!(poui!(u8: 0.3), Poui(77u8));
+    assert_eq!(poui!(u32: 0.75), Poui(3u32 << 30));
+    assert_eq!(poui!(u64: 0.5), Poui(1u64 << 63));
+    assert_eq!(poui!(u128: 0.5), Poui(1u128 << 127));
+}
+
+#[test]
+fn float_literals_signed() {
+    use crate::Poui;
+    assert_eq!(poui!(i8: -1.0), Poui(i8::MIN));
+    assert_eq!(poui!(i8: 0.5), Poui(64i8));
+    assert_eq!(poui!(i16: -0.3), Poui(-9830i16));
+    assert_eq!(poui!(i128: -1.0), Poui(i128::MIN));
+}
+
+#[test]
+fn float_literals_saturate() {
+    use crate::Poui;
+    assert_eq!(poui!(u8: 0.999), Poui(255u8));
+    assert_eq!(poui!(i8: 0.999), Poui(127i8));
+}
+
+#[test]
+fn ratio_literals() {
+    use crate::Poui;
+    assert_eq!(poui!(u8: 1 / 3), Poui(85u8));
+    assert_eq!(poui!(u8: 2 / 3), Poui(171u8));
+    assert_eq!(poui!(u32: 113 / 355), Poui(1367130435u32));
+    assert_eq!(poui!(u128: 1 / 3), Poui(u128::MAX / 3));
+    assert_eq!(poui!(i8: -1 / 1), Poui(i8::MIN));
+    assert_eq!(poui!(i16: 1 / -2), Poui(-16384i16));
+    assert_eq!(poui!(i64: -1 / -3), Poui(i64::MAX / 3 + 1));
+    assert_eq!(poui!(u8: 1000 / 1001), Poui(255u8));
+}
+
+#[test]
+fn literals_match_runtime_conversions() {
+    use crate::Poui;
+    assert_eq!(poui!(u16: 0.1), Poui::try_from(0.1f64).unwrap());
+    assert_eq!(poui!(i32: -0.7), Poui::try_from(-0.7f64).unwrap());
+    assert_eq!(poui!(u16: 7 / 9), Poui::from_ratio(7u16, 9).unwrap());
+    assert_eq!(poui!(i16: -5 / 7), Poui::from_ratio(-5i16, 7).unwrap());
+}
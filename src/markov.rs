@@ -0,0 +1,231 @@
+use crate::frac_bits;
+use crate::Poui;
+use crate::Simplex;
+use crate::StrictWiden;
+use num_traits::AsPrimitive;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+
+/// The error returned when the rows of a [`TransitionMatrix`] are invalid.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TransitionMatrixError {
+    /// A row didn't have one probability per row, or there were no rows.
+    NotSquare,
+}
+
+impl std::fmt::Display for TransitionMatrixError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TransitionMatrixError::NotSquare => write!(f, "transition matrix is not square"),
+        }
+    }
+}
+
+impl std::error::Error for TransitionMatrixError {}
+
+/// The transition probabilities of a Markov chain with finitely many
+/// states.
+///
+/// Row `i` is the distribution of the next state after state `i`, so every
+/// row is a [`Simplex`] and the matrix is always stochastic.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Poui;
+/// use poui::Simplex;
+/// use poui::TransitionMatrix;
+///
+/// // Sunny days are followed by sunny days 3/4 of the time, and rainy days
+/// // by rainy days 1/2 of the time.
+/// let weather = TransitionMatrix::new(vec![
+///     Simplex::<u16>::from_weights(&[3u8, 1]).unwrap(),
+///     Simplex::from_weights(&[1u8, 1]).unwrap(),
+/// ])
+/// .unwrap();
+/// let sunny = Simplex::from_weights(&[1u8, 0]).unwrap();
+/// let tomorrow = weather.evolve(&sunny, 1);
+/// assert_eq!(tomorrow.as_slice(), [Poui(0xC000), Poui(0x4000)]);
+/// let forecast = weather.stationary(1000);
+/// assert_eq!(forecast.as_slice(), [Poui(0xAAAB), Poui(0x5555)]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransitionMatrix<N: PrimInt + Unsigned + WrappingAdd> {
+    rows: Vec<Simplex<N>>,
+}
+
+impl<N, M> TransitionMatrix<N>
+where
+    N: PrimInt + Unsigned + WrappingAdd + StrictWiden<Widened = M> + 'static,
+    M: PrimInt + AsPrimitive<N>,
+{
+    /// Creates a transition matrix from its rows.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`TransitionMatrixError::NotSquare`] if there are no rows, or
+    /// any row doesn't have one probability per row.
+    pub fn new(rows: Vec<Simplex<N>>) -> Result<Self, TransitionMatrixError> {
+        if rows.is_empty() || rows.iter().any(|row| row.len() != rows.len()) {
+            return Err(TransitionMatrixError::NotSquare);
+        }
+        Ok(TransitionMatrix { rows })
+    }
+
+    /// The number of states.
+    pub fn len(&self) -> usize {
+        self.rows.len()
+    }
+
+    /// Whether there are no states, which is never the case.
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// The rows of the matrix.
+    pub fn rows(&self) -> &[Simplex<N>] {
+        &self.rows
+    }
+
+    /// The distribution of the state `steps` steps after it is distributed
+    /// as `distribution`.
+    ///
+    /// Each step multiplies the distribution by the matrix. The products and
+    /// their sums are accumulated exactly in the widened type, and only the
+    /// result of each step is rounded, by normalizing it to a [`Simplex`].
+    /// Certain outcomes, which a `Simplex` stores one ULP short, count as
+    /// exactly 1, so absorbing states don't leak.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `distribution` doesn't have one probability per state.
+    pub fn evolve(&self, distribution: &Simplex<N>, steps: u32) -> Simplex<N> {
+        assert_eq!(
+            distribution.len(),
+            self.len(),
+            "distribution has the wrong length"
+        );
+        let bits = frac_bits::<N>();
+        let rows: Vec<_> = self.rows.iter().map(Simplex::shares).collect();
+        (0..steps).fold(distribution.clone(), |current, _| {
+            let current = current.shares();
+            let quotas = (0..self.len()).map(|j| {
+                // The products and their sum only reach `2^(2 bits)`, and
+                // overflow, when all of the mass moves to `j`.
+                let sum = rows
+                    .iter()
+                    .zip(&current)
+                    .try_fold(M::zero(), |sum, (row, &p)| {
+                        p.checked_mul(&row[j]).and_then(|x| sum.checked_add(&x))
+                    });
+                match sum.map(|sum| sum.to_u128().unwrap()) {
+                    Some(sum) => (sum >> bits, sum & ((1 << bits) - 1)),
+                    None => (1 << bits, 0),
+                }
+            });
+            Simplex::apportion(quotas.collect())
+        })
+    }
+
+    /// Estimates the stationary distribution of the chain by power
+    /// iteration, starting from the uniform distribution.
+    ///
+    /// Each iteration takes half a step, mixing the current estimate evenly
+    /// with its successor. This lazy chain has the same stationary
+    /// distribution, but it converges even when the chain itself is
+    /// periodic. Iteration stops once the estimate no longer changes, or
+    /// after `max_iterations`.
+    pub fn stationary(&self, max_iterations: u32) -> Simplex<N> {
+        let half = Poui(N::one() << (frac_bits::<N>() - 1));
+        let mut estimate = Simplex::uniform(self.len()).unwrap();
+        for _ in 0..max_iterations {
+            let next = estimate.mix(&self.evolve(&estimate, 1), half);
+            if next == estimate {
+                break;
+            }
+            estimate = next;
+        }
+        estimate
+    }
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> TransitionMatrix<N> {
+    /// Chooses the state following `state` at random.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `state` is out of bounds.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Simplex;
+    /// use poui::TransitionMatrix;
+    ///
+    /// let cycle = TransitionMatrix::new(vec![
+    ///     Simplex::<u8>::from_weights(&[0u8, 1]).unwrap(),
+    ///     Simplex::from_weights(&[1u8, 0]).unwrap(),
+    /// ])
+    /// .unwrap();
+    /// assert_eq!(cycle.step(0, &mut rand::thread_rng()), 1);
+    /// ```
+    #[cfg(feature = "rand")]
+    pub fn step<R: rand::Rng + ?Sized>(&self, state: usize, rng: &mut R) -> usize {
+        crate::sample_weighted(self.rows[state].as_slice(), rng).unwrap()
+    }
+}
+
+#[test]
+fn transition_matrix_evolution_is_stochastic() {
+    let chain = TransitionMatrix::new(vec![
+        Simplex::<u8>::from_weights(&[1u8, 1, 1]).unwrap(),
+        Simplex::from_weights(&[0u8, 1, 0]).unwrap(),
+        Simplex::from_weights(&[5u8, 0, 2]).unwrap(),
+    ])
+    .unwrap();
+    let mut dist = Simplex::from_weights(&[0u8, 0, 1]).unwrap();
+    for _ in 0..50 {
+        dist = chain.evolve(&dist, 1);
+        let sum: u32 = dist.as_slice().iter().map(|p| p.0 as u32).sum();
+        assert_eq!(sum, 256, "{dist:?}");
+    }
+    // State 1 is absorbing, up to the last few ULPs, which are too small to
+    // move once rounded.
+    assert!(dist[1] >= Poui(250), "{dist:?}");
+    assert_eq!(chain.evolve(&dist, 0), dist);
+    let absorbed = Simplex::from_weights(&[0u8, 1, 0]).unwrap();
+    assert_eq!(chain.evolve(&absorbed, 10), absorbed);
+    let rows = vec![Simplex::<u8>::uniform(2).unwrap(); 3];
+    assert_eq!(
+        TransitionMatrix::new(rows),
+        Err(TransitionMatrixError::NotSquare)
+    );
+    assert_eq!(
+        TransitionMatrix::<u8>::new(vec![]),
+        Err(TransitionMatrixError::NotSquare)
+    );
+}
+
+#[test]
+fn stationary_converges_for_periodic_chains() {
+    let flip = TransitionMatrix::new(vec![
+        Simplex::<u32>::from_weights(&[0u8, 1]).unwrap(),
+        Simplex::from_weights(&[1u8, 0]).unwrap(),
+    ])
+    .unwrap();
+    let stationary = flip.stationary(100);
+    assert_eq!(stationary, Simplex::uniform(2).unwrap());
+    let skewed = TransitionMatrix::new(vec![
+        Simplex::<u32>::from_weights(&[9u8, 1]).unwrap(),
+        Simplex::from_weights(&[1u8, 1]).unwrap(),
+    ])
+    .unwrap();
+    let stationary = skewed.stationary(1000);
+    // pi = (5/6, 1/6)
+    assert!(stationary[0].0.abs_diff((u32::MAX / 6) * 5) < 64);
+    assert_eq!(
+        skewed.evolve(&stationary, 1).as_slice()[0].0 >> 8,
+        stationary[0].0 >> 8
+    );
+}
@@ -0,0 +1,449 @@
+use crate::frac_bits;
+use crate::Poui;
+use num_traits::AsPrimitive;
+use num_traits::Float;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+use num_traits::WrappingSub;
+
+/// A fixed-point number made of an integer part and a `Poui` fraction.
+///
+/// The value of a `Mixed` is `int + frac`, where `frac` is a point on the unit
+/// interval [0, 1). This is the fixed-point rational number described in the
+/// documentation of [`Poui`]: e.g. `Mixed<i32, u32>` is a signed 32.32
+/// fixed-point number. Negative values have a negative integer part and a
+/// non-negative fraction, so `-0.25` has integer part `-1` and fraction
+/// `0.75`, and the integer part is always the floor of the value.
+///
+/// Addition and subtraction carry between the fraction and the integer part.
+/// The integer part follows the overflow behavior of `I`, so that e.g.
+/// overflowing an `i32` integer part panics in debug builds.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Mixed;
+/// use poui::Poui;
+///
+/// let a = Mixed::new(1i32, Poui(0xC000_0000u32)); // 1.75
+/// let b = Mixed::new(2i32, Poui(0x8000_0000u32)); // 2.5
+/// assert_eq!(a + b, Mixed::new(4, Poui(0x4000_0000))); // 4.25
+/// assert_eq!(a - b, Mixed::new(-1, Poui(0x4000_0000))); // -0.75
+/// assert_eq!(a * 3, Mixed::new(5, Poui(0x4000_0000))); // 5.25
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Mixed<I, N: Unsigned + WrappingAdd> {
+    /// The integer part, i.e. the floor of the value.
+    pub int: I,
+    /// The fractional part.
+    pub frac: Poui<N>,
+}
+
+impl<I, N: Unsigned + WrappingAdd> Mixed<I, N> {
+    /// Creates a mixed number from its integer and fractional parts.
+    pub const fn new(int: I, frac: Poui<N>) -> Self {
+        Mixed { int, frac }
+    }
+}
+
+impl<I: PrimInt, N: PrimInt + Unsigned + WrappingAdd + WrappingSub> Mixed<I, N> {
+    /// Creates a mixed number with no fractional part.
+    pub fn from_int(int: I) -> Self {
+        Mixed::new(int, Poui(N::zero()))
+    }
+
+    /// Returns the integer part, rounded towards negative infinity.
+    pub fn floor(self) -> I {
+        self.int
+    }
+
+    /// Returns the fractional part, i.e. `self - self.floor()`.
+    pub fn fract(self) -> Poui<N> {
+        self.frac
+    }
+
+    /// Converts a float to the nearest mixed number, rounding the fraction
+    /// down. A fraction too close to 1 to tell apart from it carries into
+    /// the integer part instead.
+    ///
+    /// Returns `None` if `x` is not finite or its integer part doesn't fit in
+    /// `I`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Mixed;
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Mixed::from_f64(-0.25), Some(Mixed::new(-1i32, Poui(192u8))));
+    /// assert_eq!(Mixed::<i8, u8>::from_f64(300.0), None);
+    /// ```
+    pub fn from_f64(x: f64) -> Option<Self> {
+        if !x.is_finite() {
+            return None;
+        }
+        let int = x.floor();
+        let one = 2f64.powi(frac_bits::<N>() as i32);
+        let frac = (x - int) * one;
+        // Just below an integer, the fraction can round up to a whole one.
+        let (int, frac) = if frac >= one {
+            (int + 1.0, 0.0)
+        } else {
+            (int, frac)
+        };
+        Some(Mixed::new(I::from(int)?, Poui(N::from(frac)?)))
+    }
+}
+
+impl<I, N> Mixed<I, N>
+where
+    I: PrimInt + AsPrimitive<N>,
+    N: PrimInt + Unsigned + WrappingAdd + WrappingSub + 'static,
+{
+    /// Splits a raw fixed-point integer, whose low bits are a fraction with
+    /// the precision of `N`, into a mixed number. This is how the output of
+    /// a wide phase accumulator is usually stored.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `I` isn't wider than `N`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Mixed;
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Mixed::<i32, u8>::from_raw(0x1C0), Mixed::new(1, Poui(0xC0)));
+    /// assert_eq!(Mixed::<i32, u8>::from_raw(-0x40), Mixed::new(-1, Poui(0xC0)));
+    /// ```
+    pub fn from_raw(raw: I) -> Self {
+        let f = frac_bits::<N>();
+        let bits = I::zero().count_zeros() as usize;
+        assert!(bits > f, "raw integer must be wider than the fraction");
+        let int = raw >> f;
+        let frac = raw & ((I::one() << f) - I::one());
+        Mixed::new(int, Poui(frac.as_()))
+    }
+
+    /// Wraps the value into [0, 1) by discarding the integer part, like
+    /// the *repeat* addressing mode of a texture sampler.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Mixed;
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Mixed::new(3i32, Poui(64u8)).wrap_repeat(), Poui(64));
+    /// assert_eq!(Mixed::new(-1i32, Poui(64u8)).wrap_repeat(), Poui(64));
+    /// ```
+    pub fn wrap_repeat(self) -> Poui<N> {
+        self.frac
+    }
+
+    /// Folds the value into [0, 1] by reflecting it back and forth at every
+    /// integer, like the *mirror* addressing mode of a texture sampler. The
+    /// value ping-pongs between 0 and 1, and 1 itself saturates to the
+    /// largest `Poui`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Mixed;
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Mixed::new(0i32, Poui(64u8)).wrap_mirror(), Poui(64));
+    /// assert_eq!(Mixed::new(1i32, Poui(64u8)).wrap_mirror(), Poui(192));
+    /// assert_eq!(Mixed::new(-1i32, Poui(64u8)).wrap_mirror(), Poui(192));
+    /// assert_eq!(Mixed::new(3i32, Poui(0u8)).wrap_mirror(), Poui(255));
+    /// ```
+    pub fn wrap_mirror(self) -> Poui<N> {
+        if self.int & I::one() == I::zero() {
+            self.frac
+        } else if self.frac.0 == N::zero() {
+            Poui(N::max_value())
+        } else {
+            Poui(N::zero().wrapping_sub(&self.frac.0))
+        }
+    }
+
+    /// Clamps the value to [0, 1), like the *clamp* addressing mode of a
+    /// texture sampler. Values of 1 or more saturate to the largest `Poui`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Mixed;
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Mixed::new(0i32, Poui(64u8)).wrap_clamp(), Poui(64));
+    /// assert_eq!(Mixed::new(1i32, Poui(64u8)).wrap_clamp(), Poui(255));
+    /// assert_eq!(Mixed::new(-1i32, Poui(64u8)).wrap_clamp(), Poui(0));
+    /// ```
+    pub fn wrap_clamp(self) -> Poui<N> {
+        if self.int < I::zero() {
+            Poui(N::zero())
+        } else if self.int > I::zero() {
+            Poui(N::max_value())
+        } else {
+            self.frac
+        }
+    }
+}
+
+/// Adds two fractions, returning the sum and whether it carried past 1.
+fn add_frac<N: PrimInt + WrappingAdd>(a: Poui<N>, b: Poui<N>) -> (Poui<N>, bool) {
+    let sum = a.0.wrapping_add(&b.0);
+    (Poui(sum), sum < a.0)
+}
+
+impl<I: PrimInt, N: PrimInt + Unsigned + WrappingAdd + WrappingSub> std::ops::Add for Mixed<I, N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        let (frac, carry) = add_frac(self.frac, rhs.frac);
+        let int = self.int + rhs.int;
+        Mixed::new(if carry { int + I::one() } else { int }, frac)
+    }
+}
+
+impl<I: PrimInt, N: PrimInt + Unsigned + WrappingAdd + WrappingSub> std::ops::Add<Poui<N>>
+    for Mixed<I, N>
+{
+    type Output = Self;
+
+    fn add(self, rhs: Poui<N>) -> Self::Output {
+        self + Mixed::new(I::zero(), rhs)
+    }
+}
+
+impl<I: PrimInt, N: PrimInt + Unsigned + WrappingAdd + WrappingSub> std::ops::Sub for Mixed<I, N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        let borrow = self.frac.0 < rhs.frac.0;
+        let frac = Poui(self.frac.0.wrapping_sub(&rhs.frac.0));
+        let int = if borrow {
+            self.int - I::one()
+        } else {
+            self.int
+        };
+        Mixed::new(int - rhs.int, frac)
+    }
+}
+
+impl<I: PrimInt, N: PrimInt + Unsigned + WrappingAdd + WrappingSub> std::ops::Sub<Poui<N>>
+    for Mixed<I, N>
+{
+    type Output = Self;
+
+    fn sub(self, rhs: Poui<N>) -> Self::Output {
+        self - Mixed::new(I::zero(), rhs)
+    }
+}
+
+impl<I: PrimInt, N: PrimInt + Unsigned + WrappingAdd + WrappingSub> std::ops::AddAssign
+    for Mixed<I, N>
+{
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<I: PrimInt, N: PrimInt + Unsigned + WrappingAdd + WrappingSub> std::ops::SubAssign
+    for Mixed<I, N>
+{
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+/// Multiplication of a mixed number by an integer.
+///
+/// The fraction is multiplied in 128-bit arithmetic and its carry added to the
+/// integer part, so the result is exact as long as it fits. Like integer
+/// multiplication, overflowing the integer part panics in debug builds. The
+/// fraction may be at most 64 bits wide.
+impl<I: PrimInt, N: PrimInt + Unsigned + WrappingAdd + WrappingSub> std::ops::Mul<I>
+    for Mixed<I, N>
+{
+    type Output = Self;
+
+    fn mul(self, rhs: I) -> Self::Output {
+        let bits = frac_bits::<N>();
+        let negative = rhs < I::zero();
+        let k = match rhs.to_i128() {
+            Some(k) => k.unsigned_abs(),
+            None => rhs.to_u128().unwrap(),
+        };
+        let product = self.frac.0.to_u128().unwrap() * k;
+        let mask = (1u128 << bits) - 1;
+        if !negative {
+            let carry = I::from(product >> bits).expect("Mixed multiplication overflowed");
+            let frac = Poui(N::from(product & mask).unwrap());
+            return Mixed::new(self.int * rhs + carry, frac);
+        }
+        // The fraction times `rhs` is minus the product, whose floor borrows
+        // one more from the integer part unless the product is whole. The
+        // integer part is summed in 128 bits, so that e.g. `-1 * I::MIN` can
+        // overflow `I` on the way to a result that fits.
+        let (borrow, frac) = match product & mask {
+            0 => (product >> bits, 0),
+            r => ((product >> bits) + 1, (1u128 << bits) - r),
+        };
+        let int = i128::try_from(borrow).ok().and_then(|borrow| {
+            self.int
+                .to_i128()?
+                .checked_mul(rhs.to_i128()?)?
+                .checked_sub(borrow)
+        });
+        let int = int
+            .and_then(I::from)
+            .expect("Mixed multiplication overflowed");
+        Mixed::new(int, Poui(N::from(frac).unwrap()))
+    }
+}
+
+impl<F, I, N> AsPrimitive<F> for Mixed<I, N>
+where
+    F: Float + 'static,
+    I: PrimInt + AsPrimitive<F>,
+    N: PrimInt + Unsigned + WrappingAdd + WrappingSub + AsPrimitive<F>,
+{
+    /// Converts the mixed number to a floating-point number.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use num_traits::AsPrimitive;
+    /// use poui::Mixed;
+    /// use poui::Poui;
+    ///
+    /// let m = Mixed::new(-3i32, Poui(0x4000u16));
+    /// let f: f64 = m.as_();
+    /// assert_eq!(f, -2.75);
+    /// ```
+    fn as_(self) -> F {
+        self.int.as_() + self.frac.as_()
+    }
+}
+
+#[test]
+fn mixed_add_carries() {
+    let a = Mixed::new(1i32, Poui(200u8));
+    let b = Mixed::new(2i32, Poui(100u8));
+    assert_eq!(a + b, Mixed::new(4, Poui(44u8)));
+    assert_eq!(a + Poui(55u8), Mixed::new(1, Poui(255u8)));
+    assert_eq!(a + Poui(56u8), Mixed::new(2, Poui(0u8)));
+}
+
+#[test]
+fn mixed_sub_borrows() {
+    let a = Mixed::new(1i32, Poui(100u8));
+    let b = Mixed::new(0i32, Poui(200u8));
+    assert_eq!(a - b, Mixed::new(0, Poui(156u8)));
+    assert_eq!(b - a, Mixed::new(-1, Poui(100u8)));
+    assert_eq!(
+        Mixed::new(-5i8, Poui(128u8)) - Poui(192u8),
+        Mixed::new(-6, Poui(192u8))
+    );
+}
+
+#[test]
+fn mixed_mul_by_integer() {
+    let a = Mixed::new(2i64, Poui(1u64 << 63)); // 2.5
+    assert_eq!(a * 3, Mixed::new(7, Poui(1u64 << 63)));
+    assert_eq!(a * 1, a);
+    assert_eq!(a * -1, Mixed::new(-3, Poui(1u64 << 63)));
+    assert_eq!(a * -2, Mixed::new(-5, Poui(0u64)));
+    let b = Mixed::new(0u32, Poui(0x5555u16)); // ~1/3
+    assert_eq!(b * 3, Mixed::new(0, Poui(0xFFFFu16)));
+    assert_eq!(b * 6, Mixed::new(1, Poui(0xFFFEu16)));
+    let one = Mixed::<i32, u32>::from_int(1);
+    assert_eq!(one * i32::MIN, Mixed::from_int(i32::MIN));
+    let half = Mixed::new(0i32, Poui(1u32 << 31));
+    assert_eq!(half * i32::MIN, Mixed::from_int(-(1 << 30)));
+    let minus_half = Mixed::new(-1i32, Poui(1u32 << 31));
+    assert_eq!(minus_half * i32::MIN, Mixed::from_int(1 << 30));
+    assert_eq!(
+        Mixed::new(0i8, Poui(1u8)) * i8::MIN,
+        Mixed::new(-1, Poui(0x80))
+    );
+}
+
+#[test]
+fn mixed_ordering() {
+    let values = [
+        Mixed::new(-2i32, Poui(0u32)),
+        Mixed::new(-1i32, Poui(1u32)),
+        Mixed::new(-1i32, Poui(u32::MAX)),
+        Mixed::new(0i32, Poui(0u32)),
+        Mixed::new(0i32, Poui(5u32)),
+        Mixed::new(3i32, Poui(0u32)),
+    ];
+    for pair in values.windows(2) {
+        assert!(pair[0] < pair[1]);
+    }
+}
+
+#[test]
+fn mixed_float_conversion() {
+    let m: Mixed<i32, u32> = Mixed::from_f64(12.375).unwrap();
+    assert_eq!(m, Mixed::new(12, Poui(0x6000_0000)));
+    let f: f64 = m.as_();
+    assert_eq!(f, 12.375);
+    let n: Mixed<i32, u32> = Mixed::from_f64(-12.375).unwrap();
+    assert_eq!(n, Mixed::new(-13, Poui(0xA000_0000)));
+    let g: f32 = n.as_();
+    assert_eq!(g, -12.375);
+    assert_eq!(Mixed::<i32, u32>::from_f64(f64::NAN), None);
+    let tiny = Mixed::<i32, u32>::from_f64(-1e-20);
+    assert_eq!(tiny, Some(Mixed::new(0, Poui(0))));
+    let tiny = Mixed::<i8, u64>::from_f64(-1e-30);
+    assert_eq!(tiny, Some(Mixed::new(0, Poui(0))));
+    assert_eq!(
+        Mixed::<i8, u8>::from_f64(127.0 - 1e-20),
+        Some(Mixed::from_int(127))
+    );
+}
+
+#[test]
+fn from_raw_splits_bits() {
+    assert_eq!(
+        Mixed::<i64, u32>::from_raw(-1),
+        Mixed::new(-1, Poui(u32::MAX))
+    );
+    assert_eq!(
+        Mixed::<u64, u32>::from_raw(5 << 32 | 7),
+        Mixed::new(5, Poui(7))
+    );
+    assert_eq!(
+        Mixed::<i16, u8>::from_raw(i16::MIN),
+        Mixed::new(-128, Poui(0))
+    );
+}
+
+#[test]
+#[should_panic]
+fn from_raw_needs_wider_integer() {
+    Mixed::<u8, u8>::from_raw(3);
+}
+
+#[test]
+fn wrap_modes_over_a_ramp() {
+    // Step through [-2, 2) in quarters.
+    let quarters: Vec<_> = (-8..8)
+        .map(|q| Mixed::<i32, u8>::from_raw(q * 64))
+        .collect();
+    let repeat: Vec<u8> = quarters.iter().map(|m| m.wrap_repeat().0).collect();
+    let mirror: Vec<u8> = quarters.iter().map(|m| m.wrap_mirror().0).collect();
+    let clamp: Vec<u8> = quarters.iter().map(|m| m.wrap_clamp().0).collect();
+    assert_eq!(repeat, [0, 64, 128, 192].repeat(4));
+    assert_eq!(mirror, [0, 64, 128, 192, 255, 192, 128, 64].repeat(2));
+    assert_eq!(
+        clamp,
+        [[0; 4], [0; 4], [0, 64, 128, 192], [255; 4]].concat()
+    );
+}
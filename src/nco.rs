@@ -0,0 +1,164 @@
+use crate::frac_bits;
+use crate::Poui;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+
+/// A numerically-controlled oscillator: a phase accumulator that advances by
+/// a fixed tuning word every tick.
+///
+/// The phase is a `Poui` fraction of a cycle, so it wraps around for free
+/// and the frequency is exactly `tuning_word * sample_rate` cycles per
+/// second, with no accumulated rounding error. Iterating an `Nco` yields
+/// successive phases, which can be fed to e.g. [`Poui::sin`] or
+/// [`crate::TrigTable`] to synthesize a waveform.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Nco;
+/// use poui::Poui;
+///
+/// let mut nco = Nco::<u16>::with_frequency(1_000, 48_000);
+/// assert_eq!(nco.tuning_word(), Poui(1365));
+/// assert_eq!(nco.tick(), Poui(0));
+/// assert_eq!(nco.tick(), Poui(1365));
+/// assert_eq!(nco.phase(), Poui(2730));
+///
+/// let phases: Vec<_> = Nco::new(Poui(96u8)).take(4).collect();
+/// assert_eq!(phases, [Poui(0), Poui(96), Poui(192), Poui(32)]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Nco<N: PrimInt + WrappingAdd> {
+    phase: Poui<N>,
+    tuning_word: Poui<N>,
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Nco<N> {
+    /// An oscillator starting at phase 0 that advances by `tuning_word` each
+    /// tick.
+    pub fn new(tuning_word: Poui<N>) -> Self {
+        Nco {
+            phase: Poui(N::zero()),
+            tuning_word,
+        }
+    }
+
+    /// An oscillator starting at phase 0 with the given frequency, as for
+    /// [`Nco::set_frequency`].
+    pub fn with_frequency(freq_hz: u64, sample_rate: u64) -> Self {
+        let mut nco = Nco::new(Poui(N::zero()));
+        nco.set_frequency(freq_hz, sample_rate);
+        nco
+    }
+
+    /// Sets the tuning word for a frequency of `freq_hz` when ticked
+    /// `sample_rate` times per second.
+    ///
+    /// The tuning word is `freq_hz / sample_rate` rounded to the nearest
+    /// `Poui`, computed exactly by integer long division. Frequencies at or
+    /// above the sample rate alias down to `freq_hz % sample_rate`, as they
+    /// would in any sampled system. The phase is left unchanged, so the
+    /// frequency can be changed without a discontinuity.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `sample_rate` is zero.
+    pub fn set_frequency(&mut self, freq_hz: u64, sample_rate: u64) {
+        assert!(sample_rate != 0, "Nco sample rate must be nonzero");
+        let word = fraction_bits(freq_hz % sample_rate, sample_rate, frac_bits::<N>());
+        self.tuning_word = Poui(N::from(word).unwrap_or(N::zero()));
+    }
+
+    /// The amount the phase advances each tick.
+    pub fn tuning_word(&self) -> Poui<N> {
+        self.tuning_word
+    }
+
+    /// Sets the amount the phase advances each tick.
+    pub fn set_tuning_word(&mut self, tuning_word: Poui<N>) {
+        self.tuning_word = tuning_word;
+    }
+
+    /// The current phase.
+    pub fn phase(&self) -> Poui<N> {
+        self.phase
+    }
+
+    /// Jumps to the given phase, e.g. to reset or synchronize the oscillator.
+    pub fn set_phase(&mut self, phase: Poui<N>) {
+        self.phase = phase;
+    }
+
+    /// Returns the current phase and advances to the next one.
+    pub fn tick(&mut self) -> Poui<N> {
+        let phase = self.phase;
+        self.phase = phase + self.tuning_word;
+        phase
+    }
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Iterator for Nco<N> {
+    type Item = Poui<N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.tick())
+    }
+}
+
+/// Computes `num / den` with `bits` fractional bits, rounded to nearest and
+/// wrapping to 0 if it rounds up to 1. Requires `num < den`.
+pub(crate) fn fraction_bits(num: u64, den: u64, bits: usize) -> u128 {
+    let (mut quotient, mut remainder) = (0u128, num as u128);
+    let den = den as u128;
+    for _ in 0..bits {
+        remainder <<= 1;
+        quotient <<= 1;
+        if remainder >= den {
+            remainder -= den;
+            quotient |= 1;
+        }
+    }
+    if remainder << 1 >= den {
+        quotient = quotient.wrapping_add(1);
+    }
+    if bits < 128 {
+        quotient & ((1 << bits) - 1)
+    } else {
+        quotient
+    }
+}
+
+#[test]
+fn nco_frequency_is_exact() {
+    let mut nco = Nco::<u32>::with_frequency(440, 44_100);
+    for _ in 0..44_100 {
+        nco.tick();
+    }
+    // After one second the phase has completed 440 cycles, up to the rounding
+    // of the tuning word.
+    let error = nco.phase().0.min(nco.phase().0.wrapping_neg());
+    assert!(error <= 44_100 / 2, "{error}");
+    assert_eq!(nco.tuning_word(), Poui(42852281));
+}
+
+#[test]
+fn nco_aliasing_and_wide_backings() {
+    assert_eq!(Nco::<u8>::with_frequency(3, 4).tuning_word(), Poui(192));
+    assert_eq!(Nco::<u8>::with_frequency(7, 4).tuning_word(), Poui(192));
+    assert_eq!(Nco::<u8>::with_frequency(1023, 1024).tuning_word(), Poui(0));
+    assert_eq!(
+        Nco::<u128>::with_frequency(1, 4).tuning_word(),
+        Poui(1 << 126)
+    );
+    assert_eq!(
+        Nco::<u64>::with_frequency(1, 3).tuning_word(),
+        Poui(u64::MAX / 3)
+    );
+}
+
+#[test]
+#[should_panic]
+fn nco_zero_sample_rate() {
+    Nco::<u16>::with_frequency(1, 0);
+}
@@ -0,0 +1,105 @@
+use crate::Poui;
+use num_traits::Num;
+use num_traits::WrappingAdd;
+
+/// Integer types that have a corresponding `NonZero` type.
+///
+/// This is the bridge between an integer backing and `std::num::NonZero`,
+/// used by [`NonZeroPoui`]. It is implemented for all integer backings.
+pub trait NonZeroBacking: Num + WrappingAdd + Copy {
+    type NonZero: Copy + Eq + Ord + std::hash::Hash + std::fmt::Debug;
+    fn to_non_zero(self) -> Option<Self::NonZero>;
+    fn from_non_zero(n: Self::NonZero) -> Self;
+}
+
+macro_rules! impl_non_zero_backing {
+    ($($n:ty),*) => {
+        $(
+            impl NonZeroBacking for $n {
+                type NonZero = std::num::NonZero<$n>;
+                fn to_non_zero(self) -> Option<Self::NonZero> {
+                    std::num::NonZero::new(self)
+                }
+                fn from_non_zero(n: Self::NonZero) -> Self {
+                    n.get()
+                }
+            }
+        )*
+    };
+}
+
+impl_non_zero_backing!(u8, u16, u32, u64, u128, i8, i16, i32, i64, i128);
+
+/// A `Poui` that is known not to be zero.
+///
+/// This is built on the `std::num::NonZero` integer types, so the compiler can
+/// use zero as a niche: `Option<NonZeroPoui<u32>>` is the same size as a
+/// `u32`. This makes it cheap to store optional probabilities or alpha values,
+/// e.g. in a sparse table where zero means "absent".
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::NonZeroPoui;
+/// use poui::Poui;
+///
+/// let p = NonZeroPoui::new(Poui(0x8000_0000u32)).unwrap();
+/// assert_eq!(p.get(), Poui(0x8000_0000u32));
+/// assert_eq!(NonZeroPoui::new(Poui(0u32)), None);
+/// assert_eq!(
+///     std::mem::size_of::<Option<NonZeroPoui<u32>>>(),
+///     std::mem::size_of::<u32>(),
+/// );
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[repr(transparent)]
+pub struct NonZeroPoui<N: NonZeroBacking>(N::NonZero);
+
+impl<N: NonZeroBacking> NonZeroPoui<N> {
+    /// Creates a `NonZeroPoui` if `value` is not zero.
+    pub fn new(value: Poui<N>) -> Option<Self> {
+        value.0.to_non_zero().map(NonZeroPoui)
+    }
+
+    /// Returns the contained value as a plain `Poui`.
+    pub fn get(self) -> Poui<N> {
+        Poui(N::from_non_zero(self.0))
+    }
+}
+
+impl<N: NonZeroBacking> From<NonZeroPoui<N>> for Poui<N> {
+    fn from(value: NonZeroPoui<N>) -> Self {
+        value.get()
+    }
+}
+
+#[test]
+fn non_zero_niche() {
+    use std::mem::size_of;
+    assert_eq!(size_of::<Option<NonZeroPoui<u8>>>(), 1);
+    assert_eq!(size_of::<Option<NonZeroPoui<u32>>>(), 4);
+    assert_eq!(size_of::<Option<NonZeroPoui<i64>>>(), 8);
+    assert_eq!(size_of::<Option<NonZeroPoui<u128>>>(), 16);
+}
+
+#[test]
+fn non_zero_round_trip() {
+    for x in [1u16, 2, 0x8000, u16::MAX] {
+        assert_eq!(NonZeroPoui::new(Poui(x)).unwrap().get(), Poui(x));
+    }
+    assert_eq!(
+        NonZeroPoui::new(Poui(-1i8)).map(Poui::from),
+        Some(Poui(-1i8))
+    );
+    assert_eq!(NonZeroPoui::new(Poui(0i8)), None);
+}
+
+#[test]
+fn non_zero_ordering() {
+    let a = NonZeroPoui::new(Poui(1u8)).unwrap();
+    let b = NonZeroPoui::new(Poui(200u8)).unwrap();
+    assert!(a < b);
+    let c = NonZeroPoui::new(Poui(-100i16)).unwrap();
+    let d = NonZeroPoui::new(Poui(100i16)).unwrap();
+    assert!(c < d);
+}
@@ -0,0 +1,157 @@
+use crate::easing::exp2;
+use crate::easing::from_q62;
+use crate::probability::LOG2_E;
+use crate::Ewma;
+use crate::Poui;
+use crate::StrictWiden;
+use num_traits::AsPrimitive;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+use std::time::Duration;
+
+/// Nanoseconds per second.
+const NANOS: u128 = 1_000_000_000;
+
+/// A one-pole low-pass filter, for smoothing `Poui` control signals such as
+/// a gain or a cutoff that is set in steps.
+///
+/// Each sample moves the output a fraction `coefficient` of the way towards
+/// the input, so a step in the input becomes an exponential glide instead of
+/// the click, or *zipper noise*, of a sudden jump. The update is an
+/// [`Ewma`]: it is computed exactly with the widening multiply, and the
+/// fraction of an ULP below the output is kept for the next sample, so the
+/// glide is smooth all the way and ends exactly on the input.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::OnePole;
+/// use poui::Poui;
+/// use std::time::Duration;
+///
+/// let mut gain = OnePole::with_time_constant(Duration::from_millis(5), 48_000, Poui(0u16));
+/// let first = gain.process(Poui(0xFFFF));
+/// assert!(first > Poui(0) && first < Poui(0x0200));
+/// for _ in 0..48_000 {
+///     gain.process(Poui(0xFFFF));
+/// }
+/// assert_eq!(gain.value(), Poui(0xFFFF));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OnePole<N: PrimInt + Unsigned + WrappingAdd>(Ewma<N>);
+
+impl<N, M> OnePole<N>
+where
+    N: PrimInt + Unsigned + WrappingAdd + StrictWiden<Widened = M> + 'static,
+    M: PrimInt + AsPrimitive<N>,
+{
+    /// A filter starting at `initial`, moving a fraction `coefficient` of the
+    /// way to the input every sample.
+    pub fn new(coefficient: Poui<N>, initial: Poui<N>) -> Self {
+        OnePole(Ewma::new(coefficient, initial))
+    }
+
+    /// A filter starting at `initial`, whose step response covers `1 − 1/e`
+    /// of a step after `time_constant`, at the given sample rate.
+    ///
+    /// The coefficient is `1 − e^(−1 / (time_constant · sample_rate))`,
+    /// computed in fixed point. A zero time constant, or sample rate, gives a
+    /// filter that follows its input immediately, and a very long one is
+    /// kept at one ULP rather than rounding to zero, so the filter always
+    /// moves eventually.
+    pub fn with_time_constant(time_constant: Duration, sample_rate: u64, initial: Poui<N>) -> Self {
+        Self::new(
+            time_constant_coefficient(time_constant, sample_rate),
+            initial,
+        )
+    }
+
+    /// The fraction of the way to the input the filter moves each sample.
+    pub fn coefficient(&self) -> Poui<N> {
+        self.0.alpha()
+    }
+
+    /// Changes the coefficient, keeping the current output.
+    pub fn set_coefficient(&mut self, coefficient: Poui<N>) {
+        self.0.set_alpha(coefficient);
+    }
+
+    /// Changes the coefficient to give the time constant at the sample rate,
+    /// as in [`OnePole::with_time_constant`], keeping the current output.
+    pub fn set_time_constant(&mut self, time_constant: Duration, sample_rate: u64) {
+        self.set_coefficient(time_constant_coefficient(time_constant, sample_rate));
+    }
+
+    /// The current output, rounded to the nearest `Poui`.
+    pub fn value(&self) -> Poui<N> {
+        self.0.value()
+    }
+
+    /// Jumps the output to `value`, forgetting the glide in progress.
+    pub fn reset(&mut self, value: Poui<N>) {
+        self.0.reset(value);
+    }
+
+    /// Filters the next sample, and returns the new output.
+    pub fn process(&mut self, sample: Poui<N>) -> Poui<N> {
+        self.0.update(sample)
+    }
+}
+
+/// The coefficient `1 − e^(−1 / (time_constant · sample_rate))`, at least one
+/// ULP.
+fn time_constant_coefficient<N: PrimInt + Unsigned + WrappingAdd>(
+    time_constant: Duration,
+    sample_rate: u64,
+) -> Poui<N> {
+    let samples = time_constant.as_nanos() * sample_rate as u128;
+    if samples == 0 {
+        return Poui(N::max_value());
+    }
+    // e^(−x) = 2^(−x · log2(e)), where x = 1 / samples, in Q62.
+    let exponent = (LOG2_E as u128 * NANOS / samples) as i128;
+    let coefficient: Poui<N> = from_q62((1 << 62) - exp2(-exponent));
+    Poui(coefficient.0.max(N::one()))
+}
+
+#[test]
+fn time_constant_matches_float() {
+    for (micros, rate) in [(10, 48_000), (1_000, 48_000), (20_000, 44_100), (1, 1_000)] {
+        let coefficient = time_constant_coefficient::<u32>(Duration::from_micros(micros), rate);
+        let samples = micros as f64 * 1e-6 * rate as f64;
+        let expected = (1.0 - (-1.0 / samples).exp()) * 2f64.powi(32);
+        assert!(
+            (coefficient.0 as f64 - expected.min(u32::MAX as f64)).abs() <= 1.0,
+            "{micros} {rate}"
+        );
+    }
+    assert_eq!(
+        time_constant_coefficient::<u16>(Duration::ZERO, 48_000),
+        Poui(u16::MAX)
+    );
+    assert_eq!(
+        time_constant_coefficient::<u16>(Duration::from_secs(3600), 48_000),
+        Poui(1)
+    );
+}
+
+#[test]
+fn one_pole_step_response_is_exponential() {
+    let mut filter = OnePole::with_time_constant(Duration::from_millis(1), 10_000, Poui(0u32));
+    let step = Poui(0xC000_0000u32);
+    let mut previous = Poui(0);
+    for i in 1..=100u32 {
+        let output = filter.process(step);
+        assert!(output >= previous);
+        previous = output;
+        if i == 10 {
+            let expected = step.0 as f64 * (1.0 - (-1f64).exp());
+            assert!((output.0 as f64 - expected).abs() < 2.0, "{output:?}");
+        }
+    }
+    filter.reset(Poui(9));
+    assert_eq!(filter.value(), Poui(9));
+    filter.set_coefficient(Poui(u32::MAX));
+    assert_eq!(filter.process(Poui(7)), Poui(7));
+}
@@ -0,0 +1,167 @@
+use crate::easing::from_q62;
+use crate::easing::to_q62;
+use crate::trig::cordic_cos_sin;
+use crate::trig::turn_bits;
+use crate::Poui;
+use num_traits::AsPrimitive;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+
+/// One in Q62.
+const ONE: i128 = 1 << 62;
+
+/// How the gains of a [`pan`] or [`crossfade`] trade off against each other.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum PanLaw {
+    /// The gains are the cosine and sine of a quarter turn scaled by `t`, so
+    /// their squares add up to 1. Uncorrelated signals, such as two
+    /// different songs, keep the same loudness throughout.
+    #[default]
+    EqualPower,
+    /// The gains are `1 − t` and `t`, so they add up to 1. Correlated
+    /// signals, such as the same color on two LEDs, keep the same level
+    /// throughout, but uncorrelated ones dip by 3 dB in the middle.
+    Linear,
+}
+
+impl PanLaw {
+    /// The gains of the first and second signals at position `t`, in Q62.
+    fn gains_q62<N: PrimInt + Unsigned + WrappingAdd>(self, t: Poui<N>) -> (i128, i128) {
+        match self {
+            PanLaw::EqualPower => {
+                let (cos, sin) = cordic_cos_sin(turn_bits(t.0) >> 2);
+                (cos as i128, sin as i128)
+            }
+            PanLaw::Linear => {
+                let t = to_q62(t);
+                (ONE - t, t)
+            }
+        }
+    }
+
+    /// The left and right gains of a signal panned to position `t`, from
+    /// hard left at 0 to hard right at 1.
+    ///
+    /// A gain of 1 saturates just below it.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::PanLaw;
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(PanLaw::Linear.gains(Poui(0x4000u16)), (Poui(0xC000), Poui(0x4000)));
+    /// assert_eq!(PanLaw::EqualPower.gains(Poui(0u16)), (Poui(0xFFFF), Poui(0)));
+    /// ```
+    pub fn gains<N: PrimInt + Unsigned + WrappingAdd>(self, t: Poui<N>) -> (Poui<N>, Poui<N>) {
+        let (left, right) = self.gains_q62(t);
+        (from_q62(left), from_q62(right))
+    }
+
+    /// Mixes `a` and `b` with the gains at position `t`, from all `a` at 0
+    /// towards all `b` at 1.
+    ///
+    /// The mix is computed with the exact gains, so it is exactly `a` at 0,
+    /// and rounded once to the nearest `Poui`. The equal-power mix of
+    /// correlated signals can exceed both of them, by up to 3 dB, and
+    /// saturates if it leaves the range of the samples.
+    ///
+    /// Samples can be of any backing that converts losslessly to `i64`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::PanLaw;
+    /// use poui::Poui;
+    ///
+    /// let (a, b) = (Poui(0x100i16), Poui(-0x100));
+    /// assert_eq!(PanLaw::Linear.crossfade(a, b, Poui(0x4000u16)), Poui(0x80));
+    /// assert_eq!(PanLaw::EqualPower.crossfade(a, b, Poui(0x8000u16)), Poui(0));
+    /// ```
+    pub fn crossfade<N, S>(self, a: Poui<S>, b: Poui<S>, t: Poui<N>) -> Poui<S>
+    where
+        N: PrimInt + Unsigned + WrappingAdd,
+        S: PrimInt + WrappingAdd + Into<i64> + 'static,
+        i128: AsPrimitive<S>,
+    {
+        let (ga, gb) = self.gains_q62(t);
+        let wide = |v: Poui<S>| -> i128 {
+            let v: i64 = v.0.into();
+            v as i128
+        };
+        let mix = (wide(a) * ga + wide(b) * gb + (1 << 61)) >> 62;
+        let (min, max) = (wide(Poui(S::min_value())), wide(Poui(S::max_value())));
+        Poui(mix.clamp(min, max).as_())
+    }
+}
+
+/// The left and right gains of a signal panned to position `t`, by the
+/// equal-power law.
+///
+/// See [`PanLaw::gains`] for details, and for the linear law.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::pan;
+/// use poui::Poui;
+///
+/// let (left, right) = pan(Poui(0x8000u16));
+/// assert_eq!(left, right);
+/// assert_eq!(left, Poui(0xB505)); // 1/√2
+/// assert_eq!(pan(Poui(0u8)), (Poui(0xFF), Poui(0)));
+/// ```
+pub fn pan<N: PrimInt + Unsigned + WrappingAdd>(t: Poui<N>) -> (Poui<N>, Poui<N>) {
+    PanLaw::EqualPower.gains(t)
+}
+
+/// Crossfades from `a` to `b` as `t` goes from 0 to 1, by the equal-power
+/// law.
+///
+/// See [`PanLaw::crossfade`] for details, and for the linear law.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::crossfade;
+/// use poui::Poui;
+///
+/// let (a, b) = (Poui(0x4000i16), Poui(0x2000));
+/// assert_eq!(crossfade(a, b, Poui(0u16)), a);
+/// assert_eq!(crossfade(a, b, Poui(0xFFFFu16)), b);
+/// ```
+pub fn crossfade<N, S>(a: Poui<S>, b: Poui<S>, t: Poui<N>) -> Poui<S>
+where
+    N: PrimInt + Unsigned + WrappingAdd,
+    S: PrimInt + WrappingAdd + Into<i64> + 'static,
+    i128: AsPrimitive<S>,
+{
+    PanLaw::EqualPower.crossfade(a, b, t)
+}
+
+#[test]
+fn equal_power_gains_keep_power() {
+    for t in (0..=u32::MAX).step_by(0x0123_4567) {
+        let (left, right) = pan(Poui(t));
+        let (l, r) = (left.0 as f64 / 4294967296.0, right.0 as f64 / 4294967296.0);
+        assert!((l * l + r * r - 1.0).abs() < 1e-9, "{t}");
+        let angle = t as f64 / 4294967296.0 * std::f64::consts::FRAC_PI_2;
+        assert!((l - angle.cos()).abs() < 1e-9 && (r - angle.sin()).abs() < 1e-9);
+    }
+    let (left, right) = PanLaw::Linear.gains(Poui(0x1234u16));
+    assert_eq!(left.0 as u32 + right.0 as u32, 0x10000);
+}
+
+#[test]
+fn crossfade_mixes_and_saturates() {
+    let (a, b) = (Poui(200u8), Poui(100u8));
+    for t in 0..=255u8 {
+        let mix = PanLaw::Linear.crossfade(a, b, Poui(t));
+        let exact = 200.0 - 100.0 * t as f64 / 256.0;
+        assert!((mix.0 as f64 - exact).abs() <= 0.5, "{t}");
+    }
+    let loud = Poui(0x7000i16);
+    assert_eq!(crossfade(loud, loud, Poui(0x8000u16)), Poui(i16::MAX));
+    assert_eq!(PanLaw::Linear.crossfade(loud, loud, Poui(0x8000u16)), loud);
+}
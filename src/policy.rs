@@ -0,0 +1,242 @@
+//! Overflow policies for `Poui` arithmetic.
+//!
+//! The same fixed-point arithmetic serves code with very different needs at
+//! the ends of the interval: angle and phase code wants to wrap around, while
+//! probability and gain code wants to saturate, or to be told about the
+//! overflow. The boundary behavior is factored out into zero-sized policy
+//! types implementing [`OverflowPolicy`]: [`Wrapping`] matches the operators
+//! of [`Poui`], and the operators of [`SatPoui`](crate::SatPoui) and
+//! [`CheckedPoui`](crate::CheckedPoui) are thin wrappers around
+//! [`Saturating`] and [`Checked`] respectively.
+//!
+//! The policies can also be selected directly, with [`Poui::add_with`] and
+//! friends, which lets generic code take the policy as a type parameter:
+//!
+//! ```rust
+//! use poui::policy::Checked;
+//! use poui::policy::OverflowPolicy;
+//! use poui::policy::Saturating;
+//! use poui::policy::Wrapping;
+//! use poui::Poui;
+//!
+//! fn double<P: OverflowPolicy>(p: Poui<u8>) -> P::Output<Poui<u8>> {
+//!     p.add_with::<P>(p)
+//! }
+//!
+//! assert_eq!(double::<Wrapping>(Poui(192)), Poui(128));
+//! assert_eq!(double::<Saturating>(Poui(192)), Poui(255));
+//! assert!(double::<Checked>(Poui(192)).is_err());
+//! ```
+//!
+//! `Poui` itself can't take the policy as a defaulted type parameter, i.e. be
+//! declared as `Poui<N, P = Wrapping>`, without adding a marker field to the
+//! tuple struct and breaking every `Poui(n)` constructor and pattern.
+
+use crate::Poui;
+use crate::PouiOverflow;
+use crate::Shorten;
+use crate::Widen;
+use num_traits::PrimInt;
+use num_traits::WrappingAdd;
+use num_traits::WrappingSub;
+
+/// Selects what happens when `Poui` arithmetic leaves the unit interval.
+pub trait OverflowPolicy {
+    /// The result of an operation on `T` under this policy, e.g. `T` itself
+    /// or a `Result` that can report overflow.
+    type Output<T>;
+
+    fn add<N>(a: Poui<N>, b: Poui<N>) -> Self::Output<Poui<N>>
+    where
+        N: PrimInt + WrappingAdd + WrappingSub;
+
+    fn sub<N>(a: Poui<N>, b: Poui<N>) -> Self::Output<Poui<N>>
+    where
+        N: PrimInt + WrappingAdd + WrappingSub;
+
+    fn mul<N, M>(a: Poui<N>, b: Poui<N>) -> Self::Output<Poui<N>>
+    where
+        N: PrimInt + WrappingAdd + Widen<Widened = M>,
+        M: PrimInt + WrappingAdd + Shorten<Shortened = N>;
+}
+
+/// Results wrap around the ends of the interval. This is the policy of
+/// [`Poui`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Wrapping;
+
+/// Results are clamped to the ends of the interval. This is the policy of
+/// [`SatPoui`](crate::SatPoui).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Saturating;
+
+/// Results that leave the interval are reported as a [`PouiOverflow`] error.
+/// This is the policy of [`CheckedPoui`](crate::CheckedPoui).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Checked;
+
+/// Whether `a * b` is `-1 * -1`, the only product of two points that lies
+/// outside the interval.
+fn product_overflows<N: PrimInt>(a: N, b: N) -> bool {
+    N::min_value() < N::zero() && a == N::min_value() && b == N::min_value()
+}
+
+impl OverflowPolicy for Wrapping {
+    type Output<T> = T;
+
+    fn add<N>(a: Poui<N>, b: Poui<N>) -> Poui<N>
+    where
+        N: PrimInt + WrappingAdd + WrappingSub,
+    {
+        Poui(a.0.wrapping_add(&b.0))
+    }
+
+    fn sub<N>(a: Poui<N>, b: Poui<N>) -> Poui<N>
+    where
+        N: PrimInt + WrappingAdd + WrappingSub,
+    {
+        Poui(a.0.wrapping_sub(&b.0))
+    }
+
+    fn mul<N, M>(a: Poui<N>, b: Poui<N>) -> Poui<N>
+    where
+        N: PrimInt + WrappingAdd + Widen<Widened = M>,
+        M: PrimInt + WrappingAdd + Shorten<Shortened = N>,
+    {
+        a * b
+    }
+}
+
+impl OverflowPolicy for Saturating {
+    type Output<T> = T;
+
+    fn add<N>(a: Poui<N>, b: Poui<N>) -> Poui<N>
+    where
+        N: PrimInt + WrappingAdd + WrappingSub,
+    {
+        Poui(a.0.saturating_add(b.0))
+    }
+
+    fn sub<N>(a: Poui<N>, b: Poui<N>) -> Poui<N>
+    where
+        N: PrimInt + WrappingAdd + WrappingSub,
+    {
+        Poui(a.0.saturating_sub(b.0))
+    }
+
+    fn mul<N, M>(a: Poui<N>, b: Poui<N>) -> Poui<N>
+    where
+        N: PrimInt + WrappingAdd + Widen<Widened = M>,
+        M: PrimInt + WrappingAdd + Shorten<Shortened = N>,
+    {
+        if product_overflows(a.0, b.0) {
+            Poui(N::max_value())
+        } else {
+            a * b
+        }
+    }
+}
+
+impl OverflowPolicy for Checked {
+    type Output<T> = Result<T, PouiOverflow>;
+
+    fn add<N>(a: Poui<N>, b: Poui<N>) -> Result<Poui<N>, PouiOverflow>
+    where
+        N: PrimInt + WrappingAdd + WrappingSub,
+    {
+        a.0.checked_add(&b.0).map(Poui).ok_or(PouiOverflow)
+    }
+
+    fn sub<N>(a: Poui<N>, b: Poui<N>) -> Result<Poui<N>, PouiOverflow>
+    where
+        N: PrimInt + WrappingAdd + WrappingSub,
+    {
+        a.0.checked_sub(&b.0).map(Poui).ok_or(PouiOverflow)
+    }
+
+    fn mul<N, M>(a: Poui<N>, b: Poui<N>) -> Result<Poui<N>, PouiOverflow>
+    where
+        N: PrimInt + WrappingAdd + Widen<Widened = M>,
+        M: PrimInt + WrappingAdd + Shorten<Shortened = N>,
+    {
+        if product_overflows(a.0, b.0) {
+            Err(PouiOverflow)
+        } else {
+            Ok(a * b)
+        }
+    }
+}
+
+impl<N: PrimInt + WrappingAdd + WrappingSub> Poui<N> {
+    /// Adds `rhs` to `self` under the overflow policy `P`.
+    pub fn add_with<P: OverflowPolicy>(self, rhs: Self) -> P::Output<Self> {
+        P::add(self, rhs)
+    }
+
+    /// Subtracts `rhs` from `self` under the overflow policy `P`.
+    pub fn sub_with<P: OverflowPolicy>(self, rhs: Self) -> P::Output<Self> {
+        P::sub(self, rhs)
+    }
+}
+
+impl<N, M> Poui<N>
+where
+    N: PrimInt + WrappingAdd + Widen<Widened = M>,
+    M: PrimInt + WrappingAdd + Shorten<Shortened = N>,
+{
+    /// Multiplies `self` by `rhs` under the overflow policy `P`.
+    pub fn mul_with<P: OverflowPolicy>(self, rhs: Self) -> P::Output<Self> {
+        P::mul(self, rhs)
+    }
+}
+
+#[test]
+fn wrapping_policy() {
+    assert_eq!(Poui(200u8).add_with::<Wrapping>(Poui(100u8)), Poui(44u8));
+    assert_eq!(Poui(100u8).sub_with::<Wrapping>(Poui(200u8)), Poui(156u8));
+    assert_eq!(
+        Poui(i8::MIN).mul_with::<Wrapping>(Poui(i8::MIN)),
+        Poui(i8::MIN)
+    );
+}
+
+#[test]
+fn saturating_policy() {
+    assert_eq!(Poui(200u8).add_with::<Saturating>(Poui(100u8)), Poui(255u8));
+    assert_eq!(Poui(100u8).sub_with::<Saturating>(Poui(200u8)), Poui(0u8));
+    assert_eq!(
+        Poui(i8::MIN).mul_with::<Saturating>(Poui(i8::MIN)),
+        Poui(i8::MAX)
+    );
+}
+
+#[test]
+fn checked_policy() {
+    assert_eq!(Poui(200u8).add_with::<Checked>(Poui(55u8)), Ok(Poui(255u8)));
+    assert_eq!(
+        Poui(200u8).add_with::<Checked>(Poui(56u8)),
+        Err(PouiOverflow)
+    );
+    assert_eq!(
+        Poui(100u8).sub_with::<Checked>(Poui(200u8)),
+        Err(PouiOverflow)
+    );
+    assert_eq!(
+        Poui(i8::MIN).mul_with::<Checked>(Poui(i8::MIN)),
+        Err(PouiOverflow)
+    );
+}
+
+#[test]
+fn policy_as_type_parameter() {
+    fn sum<P: OverflowPolicy>(values: &[Poui<u16>]) -> Vec<P::Output<Poui<u16>>> {
+        values
+            .windows(2)
+            .map(|w| w[0].add_with::<P>(w[1]))
+            .collect()
+    }
+    let values = [Poui(0x8000u16), Poui(0x8000u16)];
+    assert_eq!(sum::<Wrapping>(&values), vec![Poui(0u16)]);
+    assert_eq!(sum::<Saturating>(&values), vec![Poui(u16::MAX)]);
+    assert_eq!(sum::<Checked>(&values), vec![Err(PouiOverflow)]);
+}
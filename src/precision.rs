@@ -0,0 +1,300 @@
+use crate::frac_bits;
+use crate::Poui;
+use crate::Shorten;
+use crate::Widen;
+use num_traits::AsPrimitive;
+use num_traits::PrimInt;
+use num_traits::WrappingAdd;
+
+/// Fills the bits below a `from`-bit pattern shifted up to `to` bits with
+/// copies of the pattern, so that e.g. `0xAB` becomes `0xABAB`.
+fn replicate(magnitude: u128, from: usize, to: usize) -> u128 {
+    let mut result = magnitude << (to - from);
+    let mut shift = from;
+    while shift < to {
+        result |= result >> shift;
+        shift *= 2;
+    }
+    result
+}
+
+/// Shifts `magnitude` right by `shift` bits, rounding up.
+fn ceil_shr(magnitude: u128, shift: usize) -> u128 {
+    let mask = (1u128 << shift) - 1;
+    (magnitude >> shift) + u128::from(magnitude & mask != 0)
+}
+
+impl<N: PrimInt + WrappingAdd> Poui<N> {
+    /// Converts `self` to a `Poui` with a different backing type.
+    ///
+    /// When the target has fewer fractional bits, the value is rounded down,
+    /// just like [`Poui::demote`]. When it has more, the extra low bits are
+    /// filled by repeating the bit pattern of `self`, just like
+    /// [`Poui::promote`]. Negative values are converted by magnitude, so
+    /// negation commutes with conversion up to rounding, and they saturate to
+    /// zero when the target is unsigned.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui(0xABu8).convert::<u32>(), Poui(0xABABABABu32));
+    /// assert_eq!(Poui(0xABCDu16).convert::<u8>(), Poui(0xABu8));
+    /// assert_eq!(Poui(128u8).convert::<i8>(), Poui(64i8));
+    /// assert_eq!(Poui(-64i8).convert::<u8>(), Poui(0u8));
+    /// ```
+    pub fn convert<M: PrimInt + WrappingAdd>(self) -> Poui<M> {
+        let from = frac_bits::<N>();
+        let to = frac_bits::<M>();
+        let negative = self.0 < N::zero();
+        if negative && M::min_value() == M::zero() {
+            return Poui(M::zero());
+        }
+        let magnitude = match self.0.to_i128() {
+            Some(n) => n.unsigned_abs(),
+            None => self.0.to_u128().unwrap(),
+        };
+        let magnitude = if to <= from && negative {
+            ceil_shr(magnitude, from - to)
+        } else if to <= from {
+            magnitude >> (from - to)
+        } else if negative && magnitude == 1 << from {
+            1 << to
+        } else {
+            replicate(magnitude, from, to)
+        };
+        if negative {
+            // The magnitude may be exactly 1, which only fits in the target
+            // as the negation of `M::min_value()`.
+            match M::from(magnitude) {
+                Some(m) => Poui(M::zero() - m),
+                None => Poui(M::min_value()),
+            }
+        } else {
+            Poui(M::from(magnitude).unwrap())
+        }
+    }
+}
+
+impl<N, M> Poui<N>
+where
+    N: PrimInt + WrappingAdd + Widen<Widened = M> + AsPrimitive<M>,
+    M: PrimInt + WrappingAdd + 'static,
+{
+    /// Converts `self` to the next wider backing type, e.g. `Poui<u8>` to
+    /// `Poui<u16>`.
+    ///
+    /// The new low bits are filled by repeating the bit pattern of `self`.
+    /// This is the usual way of widening color channels: zero maps to zero and
+    /// the largest value maps to the largest value, so `Poui(255u8)` becomes
+    /// `Poui(65535u16)` rather than `Poui(65280u16)`. The price is that exact
+    /// values move slightly, e.g. `0.5` becomes `0.5 + 1/512`. Use
+    /// [`Poui::promote_exact`] when exact values matter more, e.g. for angles.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui(0xABu8).promote(), Poui(0xABABu16));
+    /// assert_eq!(Poui(255u8).promote(), Poui(65535u16));
+    /// assert_eq!(Poui(i8::MAX).promote(), Poui(i16::MAX));
+    /// ```
+    pub fn promote(self) -> Poui<M> {
+        self.convert()
+    }
+
+    /// Converts `self` to the next wider backing type without changing its
+    /// value, filling the new low bits with zeros.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui(128u8).promote_exact(), Poui(32768u16));
+    /// assert_eq!(Poui(255u8).promote_exact(), Poui(65280u16));
+    /// ```
+    pub fn promote_exact(self) -> Poui<M> {
+        let shift = frac_bits::<M>() - frac_bits::<N>();
+        Poui(self.0.as_() << shift)
+    }
+}
+
+impl<N, S> Poui<N>
+where
+    N: PrimInt + WrappingAdd + Shorten<Shortened = S> + AsPrimitive<S>,
+    S: PrimInt + WrappingAdd + 'static,
+{
+    /// The number of bits dropped when demoting to the narrower type.
+    fn dropped_bits() -> usize {
+        frac_bits::<N>() - frac_bits::<S>()
+    }
+
+    /// Converts `self` to the next narrower backing type, e.g. `Poui<u16>` to
+    /// `Poui<u8>`, by truncating the low bits.
+    ///
+    /// This always rounds down, which biases the results. See
+    /// [`Poui::demote_nearest`] and [`Poui::demote_dithered`] for unbiased
+    /// alternatives.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui(0xABCDu16).demote(), Poui(0xABu8));
+    /// assert_eq!(Poui(-1i16).demote(), Poui(-1i8));
+    /// ```
+    pub fn demote(self) -> Poui<S> {
+        Poui((self.0 >> Self::dropped_bits()).as_())
+    }
+
+    /// Converts `self` to the next narrower backing type, rounding to the
+    /// nearest representable value.
+    ///
+    /// Ties round up. Values that would round up to 1 saturate to the largest
+    /// representable value instead of wrapping around to zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui(0xAB7Fu16).demote_nearest(), Poui(0xABu8));
+    /// assert_eq!(Poui(0xAB80u16).demote_nearest(), Poui(0xACu8));
+    /// assert_eq!(Poui(0xFFFFu16).demote_nearest(), Poui(0xFFu8));
+    /// ```
+    pub fn demote_nearest(self) -> Poui<S> {
+        let shift = Self::dropped_bits();
+        if shift == 0 {
+            return Poui(self.0.as_());
+        }
+        self.demote_with_offset(N::one() << (shift - 1))
+    }
+
+    /// Converts `self` to the next narrower backing type, using `noise` to
+    /// decide whether to round up or down.
+    ///
+    /// The dropped low bits of `self` are compared against the bit pattern of
+    /// `noise`, and the result rounds up with probability proportional to how
+    /// far `self` is from the value below it. If `noise` is uniformly
+    /// distributed, the expected value of the result is exactly `self`, which
+    /// avoids the banding that [`Poui::demote`] and [`Poui::demote_nearest`]
+    /// produce in smooth gradients. Values that would round up to 1 saturate.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// // A quarter of the way from 0xAB to 0xAC.
+    /// let x = Poui(0xAB40u16);
+    /// assert_eq!(x.demote_dithered(Poui(0x00u8)), Poui(0xABu8));
+    /// assert_eq!(x.demote_dithered(Poui(0xBFu8)), Poui(0xABu8));
+    /// assert_eq!(x.demote_dithered(Poui(0xC0u8)), Poui(0xACu8));
+    /// ```
+    pub fn demote_dithered(self, noise: Poui<S>) -> Poui<S>
+    where
+        S: AsPrimitive<N>,
+    {
+        let shift = Self::dropped_bits();
+        if shift == 0 {
+            return Poui(self.0.as_());
+        }
+        let mask = (N::one() << shift) - N::one();
+        self.demote_with_offset(noise.0.as_() & mask)
+    }
+
+    fn demote_with_offset(self, offset: N) -> Poui<S> {
+        match self.0.checked_add(&offset) {
+            Some(n) => Poui((n >> Self::dropped_bits()).as_()),
+            None => Poui(S::max_value()),
+        }
+    }
+}
+
+#[test]
+fn convert_widening_replicates_bits() {
+    assert_eq!(Poui(0u8).convert::<u64>(), Poui(0u64));
+    assert_eq!(Poui(0x12u8).convert::<u64>(), Poui(0x1212121212121212u64));
+    assert_eq!(Poui(u16::MAX).convert::<u128>(), Poui(u128::MAX));
+    assert_eq!(Poui(i8::MAX).convert::<i64>(), Poui(i64::MAX));
+    assert_eq!(Poui(i8::MIN).convert::<i64>(), Poui(i64::MIN));
+}
+
+#[test]
+fn convert_narrowing_rounds_down() {
+    assert_eq!(
+        Poui(0x123456789ABCDEF0u64).convert::<u16>(),
+        Poui(0x1234u16)
+    );
+    assert_eq!(Poui(0x4001i16).convert::<i8>(), Poui(0x40i8));
+    assert_eq!(Poui(-0x4001i16).convert::<i8>(), Poui(-0x41i8));
+    assert_eq!(Poui(i64::MIN).convert::<i8>(), Poui(i8::MIN));
+}
+
+#[test]
+fn convert_between_signedness() {
+    assert_eq!(Poui(0x80u8).convert::<i16>(), Poui(0x4040i16));
+    assert_eq!(Poui(0x4000i16).convert::<u8>(), Poui(0x80u8));
+    assert_eq!(Poui(-1i32).convert::<u32>(), Poui(0u32));
+    assert_eq!(Poui(i8::MIN).convert::<u8>(), Poui(0u8));
+}
+
+#[test]
+fn promote_adjacent_widths() {
+    assert_eq!(Poui(0xCDu8).promote(), Poui(0xCDCDu16));
+    assert_eq!(Poui(0xCDEFu16).promote(), Poui(0xCDEFCDEFu32));
+    assert_eq!(Poui(u32::MAX).promote(), Poui(u64::MAX));
+    assert_eq!(Poui(1u64).promote(), Poui(0x10000000000000001u128));
+    assert_eq!(Poui(-1i8).promote(), Poui(-258i16));
+}
+
+#[test]
+fn promote_exact_adjacent_widths() {
+    assert_eq!(Poui(0xCDu8).promote_exact(), Poui(0xCD00u16));
+    assert_eq!(Poui(-64i8).promote_exact(), Poui(-16384i16));
+    assert_eq!(
+        Poui(u64::MAX).promote_exact(),
+        Poui((u64::MAX as u128) << 64)
+    );
+}
+
+#[test]
+fn demote_all_modes() {
+    let x = Poui(0x12345678u32);
+    assert_eq!(x.demote(), Poui(0x1234u16));
+    assert_eq!(x.demote_nearest(), Poui(0x1234u16));
+    assert_eq!(x.demote_dithered(Poui(0xA987u16)), Poui(0x1234u16));
+    assert_eq!(x.demote_dithered(Poui(0xA988u16)), Poui(0x1235u16));
+}
+
+#[test]
+fn demote_nearest_signed() {
+    assert_eq!(Poui(-1i16).demote_nearest(), Poui(0i8));
+    assert_eq!(Poui(-0x80i16).demote_nearest(), Poui(0i8));
+    assert_eq!(Poui(-0x81i16).demote_nearest(), Poui(-1i8));
+    assert_eq!(Poui(i16::MAX).demote_nearest(), Poui(i8::MAX));
+}
+
+#[test]
+fn demote_dithered_is_unbiased() {
+    for x in [0u16, 1, 0x1234, 0xAB40, 0xFEFF] {
+        let sum: u32 = (0..=255u8)
+            .map(|noise| Poui(x).demote_dithered(Poui(noise)).0 as u32)
+            .sum();
+        assert_eq!(sum, x as u32);
+    }
+}
+
+#[test]
+fn promote_then_demote_round_trips() {
+    for x in 0..=255u8 {
+        assert_eq!(Poui(x).promote().demote(), Poui(x));
+    }
+    for x in -128..=127i8 {
+        assert_eq!(Poui(x).promote_exact().demote(), Poui(x));
+    }
+}
@@ -0,0 +1,41 @@
+use crate::frac_bits;
+use crate::Poui;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+
+/// A small xorshift64* generator, for the random modulation sources.
+///
+/// It's not suitable for anything that needs unpredictability, but it's
+/// fast, deterministic given its seed, and needs no dependencies.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) struct XorShift(u64);
+
+impl XorShift {
+    /// A generator with the given seed. Every seed, including 0, is valid.
+    pub(crate) fn new(seed: u64) -> Self {
+        // The all-zero state is a fixed point, so scramble it away.
+        let state = seed ^ 0x9E37_79B9_7F4A_7C15;
+        XorShift(if state == 0 { 1 } else { state })
+    }
+
+    pub(crate) fn next_u64(&mut self) -> u64 {
+        let mut x = self.0;
+        x ^= x >> 12;
+        x ^= x << 25;
+        x ^= x >> 27;
+        self.0 = x;
+        x.wrapping_mul(0x2545_F491_4F6C_DD1D)
+    }
+
+    /// A uniformly distributed `Poui`.
+    pub(crate) fn next_poui<N: PrimInt + Unsigned + WrappingAdd>(&mut self) -> Poui<N> {
+        let bits = frac_bits::<N>();
+        let raw = if bits <= 64 {
+            (self.next_u64() >> (64 - bits)) as u128
+        } else {
+            (self.next_u64() as u128) << 64 | self.next_u64() as u128
+        };
+        Poui(N::from(raw).unwrap())
+    }
+}
@@ -0,0 +1,486 @@
+use crate::easing::exp2;
+use crate::easing::from_q62;
+use crate::easing::LN_2;
+use crate::frac_bits;
+use crate::FromRatioError;
+use crate::Mixed;
+use crate::Poui;
+use crate::StrictWiden;
+use num_traits::AsPrimitive;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+
+impl<N, M> Poui<N>
+where
+    N: PrimInt + Unsigned + WrappingAdd + StrictWiden<Widened = M> + 'static,
+    M: PrimInt + AsPrimitive<N>,
+{
+    /// The probability that two independent events with probabilities
+    /// `self` and `other` both happen, i.e. their product.
+    ///
+    /// The product is computed in the widened type and rounded down, so it
+    /// never exceeds either operand.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui(128u8).and(Poui(64)), Poui(32));
+    /// assert_eq!(Poui(255u8).and(Poui(255)), Poui(254));
+    /// ```
+    pub fn and(self, other: Self) -> Self {
+        Poui(((self.0.widen() * other.0.widen()) >> frac_bits::<N>()).as_())
+    }
+
+    /// The probability that at least one of two independent events with
+    /// probabilities `self` and `other` happens, i.e. `p + q − p·q`.
+    ///
+    /// The sum and product are computed in the widened type, and the product
+    /// is rounded up, so the result is rounded down. Since `1 − p + q − p·q`
+    /// is `(1 − p)·(1 − q)`, which is positive, the result always fits, and
+    /// it is never less than either operand.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui(128u8).or(Poui(128)), Poui(192));
+    /// assert_eq!(Poui(0u8).or(Poui(77)), Poui(77));
+    /// assert_eq!(Poui(255u8).or(Poui(255)), Poui(255));
+    /// ```
+    pub fn or(self, other: Self) -> Self {
+        let f = frac_bits::<N>();
+        let (p, q) = (self.0.widen(), other.0.widen());
+        let round = (M::one() << f) - M::one();
+        Poui((p + q - ((p * q + round) >> f)).as_())
+    }
+}
+
+/// `1 / ln 2` in Q62.
+pub(crate) const LOG2_E: i128 = 6653256548922161246;
+
+/// `log2(10)` in Q62.
+pub(crate) const LOG2_10: i128 = 15319689349413178110;
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Poui<N> {
+    /// The odds in favour of an event with probability `self`, as the ratio
+    /// `p : (1 − p)` in lowest terms.
+    ///
+    /// Both parts always fit in the backing: the denominator of a `Poui` is
+    /// a power of two, so reducing the ratio leaves the second part odd and
+    /// below `2^bits`. Zero has odds of `0 : 1`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui(64u8).to_odds(), (1, 3));
+    /// assert_eq!(Poui(128u8).to_odds(), (1, 1));
+    /// assert_eq!(Poui(255u8).to_odds(), (255, 1));
+    /// assert_eq!(Poui(0u8).to_odds(), (0, 1));
+    /// ```
+    pub fn to_odds(self) -> (N, N) {
+        if self.0 == N::zero() {
+            return (N::zero(), N::one());
+        }
+        let shift = self.0.trailing_zeros() as usize;
+        (self.0 >> shift, (!self.0 + N::one()) >> shift)
+    }
+
+    /// The probability of an event with odds of `in_favour : against`,
+    /// rounded to the nearest `Poui` and saturating just below 1.
+    ///
+    /// The division is exact long division, so this inverts
+    /// [`Poui::to_odds`] exactly, except that if the sum of the parts
+    /// overflows 128 bits both are halved first, which can change the result
+    /// by one ULP.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FromRatioError::ZeroDenominator`] if both parts are zero,
+    /// and [`FromRatioError::OutOfRange`] if only `against` is, since the
+    /// probability would be 1.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::FromRatioError;
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui::from_odds(1u8, 3), Ok(Poui(64)));
+    /// assert_eq!(Poui::from_odds(2u8, 1), Ok(Poui(171)));
+    /// assert_eq!(Poui::from_odds(1u8, 0), Err(FromRatioError::OutOfRange));
+    /// ```
+    pub fn from_odds(in_favour: N, against: N) -> Result<Self, FromRatioError> {
+        let (a, b) = (in_favour.to_u128().unwrap(), against.to_u128().unwrap());
+        match (a, b) {
+            (0, 0) => Err(FromRatioError::ZeroDenominator),
+            (_, 0) => Err(FromRatioError::OutOfRange),
+            _ => Ok(match a.checked_add(b) {
+                Some(total) => ratio(a, total),
+                None => ratio(a >> 1, (a >> 1) + (b >> 1)),
+            }),
+        }
+    }
+
+    /// The natural log-odds of an event with probability `self`, i.e.
+    /// `ln(p / (1 − p))`, as a signed 32.32 fixed-point number.
+    ///
+    /// Log-odds turn Bayesian updates into additions, so evidence can be
+    /// accumulated with plain fixed-point sums and converted back with
+    /// [`Poui::expit`]. The logarithms are computed by repeated squaring, to
+    /// within a couple of ULPs of the 32-bit fraction. The log-odds of 0 are
+    /// minus infinity, which is represented by the smallest `Mixed`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Mixed;
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui(0x8000u16).logit(), Mixed::new(0, Poui(0)));
+    /// // ln 3 = 1.0986...
+    /// let three_to_one = Poui(0xC000u16).logit();
+    /// assert_eq!(three_to_one.int, 1);
+    /// assert_eq!(three_to_one.frac.0 >> 24, 0x19);
+    /// ```
+    pub fn logit(self) -> Mixed<i32, u32> {
+        if self.0 == N::zero() {
+            return Mixed::new(i32::MIN, Poui(0));
+        }
+        let p = self.0.to_u128().unwrap();
+        let q = if frac_bits::<N>() == 128 {
+            p.wrapping_neg()
+        } else {
+            (1 << frac_bits::<N>()) - p
+        };
+        let log2_odds = (log2_q62(p) - log2_q62(q)) >> 30;
+        let v = (log2_odds * LN_2 + (1 << 61)) >> 62;
+        Mixed::new((v >> 32) as i32, Poui(v as u32))
+    }
+
+    /// The probability with natural log-odds `x`, i.e. the logistic function
+    /// `1 / (1 + e^−x)`, rounded to the nearest `Poui`.
+    ///
+    /// This inverts [`Poui::logit`], and is evaluated with 62 fractional bits
+    /// internally, like the easing functions. Large negative log-odds round
+    /// to 0, and large positive ones saturate just below 1.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Mixed;
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui::<u16>::expit(Mixed::new(0, Poui(0))), Poui(0x8000));
+    ///
+    /// // Two independent pieces of 3:1 evidence give 9:1 odds.
+    /// let evidence = Poui(0xC000u16).logit();
+    /// assert_eq!(Poui::<u16>::expit(evidence + evidence), Poui(0xE666));
+    /// ```
+    pub fn expit(x: Mixed<i32, u32>) -> Self {
+        let v = ((x.int as i128) << 32) | x.frac.0 as i128;
+        let e = exp2(-((v.abs() * LOG2_E) >> 32));
+        let one = 1i128 << 62;
+        if v >= 0 {
+            from_q62((1 << 124) / (one + e))
+        } else {
+            from_q62((e << 62) / (one + e))
+        }
+    }
+}
+
+/// The base-2 logarithm of a positive integer in Q62, with about 40 correct
+/// fractional bits.
+pub(crate) fn log2_q62(x: u128) -> i128 {
+    let whole = 127 - x.leading_zeros() as i128;
+    // The mantissa, in [1, 2) in Q62. Squaring it doubles its logarithm, so
+    // each square that reaches 2 contributes the next bit of the fraction.
+    let mut m = ((x << x.leading_zeros()) >> 65) as i128;
+    let mut frac = 0;
+    for bit in (22..62).rev() {
+        m = (m * m) >> 62;
+        if m >= 2 << 62 {
+            m >>= 1;
+            frac |= 1 << bit;
+        }
+    }
+    (whole << 62) + frac
+}
+
+/// Updates the probability of a hypothesis after observing some evidence,
+/// by Bayes' rule.
+///
+/// `likelihood_true` and `likelihood_false` are the probabilities of the
+/// evidence if the hypothesis is true and if it is false. The posterior is
+///
+/// ```text
+/// prior · likelihood_true / (prior · likelihood_true + (1 − prior) · likelihood_false)
+/// ```
+///
+/// Both products are computed exactly in the widened type, and the division
+/// rounds to the nearest `Poui`, saturating just below 1. For 64-bit
+/// backings the sum of the products can overflow 128 bits, in which case
+/// both are halved first, which can change the result by at most one ULP.
+///
+/// If the evidence is impossible either way, i.e. both products are zero,
+/// the prior is returned unchanged.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::posterior;
+/// use poui::Poui;
+///
+/// // A sensor that fires for 3/4 of occupied cells and 1/4 of empty ones.
+/// let occupied = posterior(Poui(128u8), Poui(192), Poui(64));
+/// assert_eq!(occupied, Poui(192));
+/// assert_eq!(posterior(occupied, Poui(192), Poui(64)), Poui(230));
+/// ```
+pub fn posterior<N, M>(
+    prior: Poui<N>,
+    likelihood_true: Poui<N>,
+    likelihood_false: Poui<N>,
+) -> Poui<N>
+where
+    N: PrimInt + Unsigned + WrappingAdd + StrictWiden<Widened = M> + 'static,
+    M: PrimInt + AsPrimitive<N>,
+{
+    let one = M::one() << frac_bits::<N>();
+    let p = prior.0.widen();
+    let a = (p * likelihood_true.0.widen()).to_u128().unwrap();
+    let b = ((one - p) * likelihood_false.0.widen()).to_u128().unwrap();
+    match a.checked_add(b) {
+        Some(0) => prior,
+        Some(total) => ratio(a, total),
+        None => ratio(a >> 1, (a >> 1) + (b >> 1)),
+    }
+}
+
+/// Computes `num / den`, for `num <= den`, rounded to the nearest `Poui` and
+/// saturating just below 1.
+///
+/// This is exact for any pair of 128-bit integers, using long division one
+/// bit at a time.
+pub(crate) fn ratio<N: PrimInt + Unsigned + WrappingAdd>(num: u128, den: u128) -> Poui<N> {
+    if num >= den {
+        return Poui(N::max_value());
+    }
+    let (q, r) = div_scaled(num, den, frac_bits::<N>());
+    let q = if r >= den - r { q + 1 } else { q };
+    Poui(N::from(q).unwrap_or(N::max_value()))
+}
+
+/// Computes `num · 2^bits / den`, for `num < den`, as a quotient and a
+/// remainder.
+pub(crate) fn div_scaled(num: u128, den: u128, bits: usize) -> (u128, u128) {
+    let (mut q, mut r) = (0u128, num);
+    for _ in 0..bits {
+        // `r < den`, so `2r - den` fits whenever `2r` overflows.
+        let carry = r >> 127 == 1;
+        r <<= 1;
+        q <<= 1;
+        if carry || r >= den {
+            r = r.wrapping_sub(den);
+            q |= 1;
+        }
+    }
+    (q, r)
+}
+
+/// Chooses an index with probability proportional to its weight, by
+/// roulette-wheel selection with `r` as the spin, or returns `None` if every
+/// weight is zero.
+///
+/// The weights' prefix sums are computed exactly in 128 bits, and the spin
+/// lands at `floor(r · total)` on the wheel, so index `i` is chosen for a
+/// contiguous range of `r` whose length is exactly proportional to its
+/// weight, up to rounding at its ends. A zero weight is never chosen.
+///
+/// # Panics
+///
+/// Panics if the weights sum to `2^128` or more, which needs 128-bit
+/// weights.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::choose_weighted;
+/// use poui::Poui;
+///
+/// let weights = [Poui(64u8), Poui(0), Poui(192)];
+/// assert_eq!(choose_weighted(&weights, Poui(0)), Some(0));
+/// assert_eq!(choose_weighted(&weights, Poui(63)), Some(0));
+/// assert_eq!(choose_weighted(&weights, Poui(64)), Some(2));
+/// assert_eq!(choose_weighted(&[Poui(0u8)], Poui(64)), None);
+/// ```
+pub fn choose_weighted<N: PrimInt + Unsigned + WrappingAdd>(
+    weights: &[Poui<N>],
+    r: Poui<N>,
+) -> Option<usize> {
+    let total = total_weight(weights);
+    if total == 0 {
+        return None;
+    }
+    let spin = r.0.to_u128().unwrap() << (128 - frac_bits::<N>());
+    Some(weighted_index(weights, mul_hi(spin, total)))
+}
+
+/// The sum of the weights, which must fit in 128 bits.
+pub(crate) fn total_weight<N: PrimInt + Unsigned + WrappingAdd>(weights: &[Poui<N>]) -> u128 {
+    weights.iter().fold(0u128, |sum, w| {
+        sum.checked_add(w.0.to_u128().unwrap())
+            .expect("weights sum to 2^128 or more")
+    })
+}
+
+/// The index of the weight whose slot on the wheel contains `target`, which
+/// must be less than the total weight.
+pub(crate) fn weighted_index<N: PrimInt + Unsigned + WrappingAdd>(
+    weights: &[Poui<N>],
+    target: u128,
+) -> usize {
+    let mut end = 0u128;
+    weights
+        .iter()
+        .position(|w| {
+            end += w.0.to_u128().unwrap();
+            end > target
+        })
+        .expect("target is below the total weight")
+}
+
+/// Whether the probabilities sum to 1 to within `tolerance` ULPs either
+/// way.
+///
+/// The sum of 128-bit probabilities can reach `2^128` or more, so this keeps
+/// count of how many times it carries past 128 bits.
+pub(crate) fn sums_to_one<N: PrimInt + Unsigned + WrappingAdd>(
+    probabilities: &[Poui<N>],
+    tolerance: u128,
+) -> bool {
+    let (mut carries, mut low) = (0u128, 0u128);
+    for p in probabilities {
+        let (sum, carry) = low.overflowing_add(p.0.to_u128().unwrap());
+        (carries, low) = (carries + carry as u128, sum);
+    }
+    match (frac_bits::<N>(), carries) {
+        (128, 0) => u128::MAX - low < tolerance,
+        (128, 1) => low <= tolerance,
+        (bits, 0) => low.abs_diff(1 << bits) <= tolerance,
+        _ => false,
+    }
+}
+
+/// The top half of the 256-bit product of `a` and `b`.
+pub(crate) fn mul_hi(a: u128, b: u128) -> u128 {
+    let (a1, a0) = (a >> 64, a as u64 as u128);
+    let (b1, b0) = (b >> 64, b as u64 as u128);
+    let (cross1, cross0) = (a1 * b0, a0 * b1);
+    let mid = ((a0 * b0) >> 64) + (cross1 as u64 as u128) + (cross0 as u64 as u128);
+    a1 * b1 + (cross1 >> 64) + (cross0 >> 64) + (mid >> 64)
+}
+
+#[test]
+fn and_or_match_exact_products() {
+    for p in 0..=255u8 {
+        for q in 0..=255u8 {
+            let product = p as u32 * q as u32;
+            let and = Poui(p).and(Poui(q));
+            let or = Poui(p).or(Poui(q));
+            assert_eq!(and.0 as u32, product / 256);
+            assert_eq!(or.0 as u32, (256 * (p as u32 + q as u32) - product) / 256);
+            assert!(and <= Poui(p).min(Poui(q)));
+            assert!(or >= Poui(p).max(Poui(q)));
+        }
+    }
+}
+
+#[test]
+fn and_or_wide_backings() {
+    let max = Poui(u64::MAX);
+    assert_eq!(max.or(max), max);
+    assert_eq!(max.and(max), Poui(u64::MAX - 1));
+    assert_eq!(Poui(1u32 << 31).or(Poui(1 << 30)), Poui(5 << 29));
+}
+
+#[test]
+fn posterior_matches_exact_bayes() {
+    for prior in (0..=255u8).step_by(5) {
+        for lt in (0..=255u8).step_by(7) {
+            for lf in (0..=255u8).step_by(11) {
+                let a = prior as u64 * lt as u64;
+                let b = (256 - prior as u64) * lf as u64;
+                let actual = posterior(Poui(prior), Poui(lt), Poui(lf));
+                let expected = match a + b {
+                    0 => prior as u64,
+                    total => ((a * 512 + total) / (2 * total)).min(255),
+                };
+                assert_eq!(actual.0 as u64, expected, "{prior} {lt} {lf}");
+            }
+        }
+    }
+}
+
+#[test]
+fn ratio_is_exact_for_wide_operands() {
+    assert_eq!(ratio::<u64>(1, 3), Poui(0x5555_5555_5555_5555));
+    assert_eq!(ratio::<u64>(2, 3), Poui(0xAAAA_AAAA_AAAA_AAAB));
+    assert_eq!(ratio::<u128>(u128::MAX / 3, u128::MAX), Poui(u128::MAX / 3));
+    assert_eq!(ratio::<u128>(u128::MAX - 1, u128::MAX), Poui(u128::MAX));
+    assert_eq!(ratio::<u8>(7, 7), Poui(255));
+    let max = Poui(u64::MAX);
+    assert_eq!(posterior(max, max, max).0 >> 1, u64::MAX >> 1);
+}
+
+#[test]
+fn odds_round_trip() {
+    for p in 0..=u16::MAX {
+        let (a, b) = Poui(p).to_odds();
+        assert_eq!(Poui::from_odds(a, b), Ok(Poui(p)));
+    }
+    let p = Poui(u128::MAX - 2);
+    assert_eq!(p.to_odds(), (u128::MAX - 2, 3));
+    assert_eq!(Poui::from_odds(1u128, 3), Ok(Poui(1 << 126)));
+    assert!(Poui::from_odds(u128::MAX - 2, 3).unwrap().0 >= u128::MAX - 3);
+    assert_eq!(
+        Poui::from_odds(0u8, 0),
+        Err(FromRatioError::ZeroDenominator)
+    );
+}
+
+#[test]
+fn logit_and_expit_match_floats() {
+    for p in (1..=u16::MAX).step_by(97) {
+        let x = p as f64 / 65536.0;
+        let logit = Poui(p).logit();
+        let actual = logit.int as f64 + logit.frac.0 as f64 / 2f64.powi(32);
+        assert!((actual - (x / (1.0 - x)).ln()).abs() < 1e-9, "{p}");
+        assert!(Poui::<u16>::expit(logit).0.abs_diff(p) <= 1, "{p}");
+    }
+    assert_eq!(Poui::<u32>::expit(Poui(0u32).logit()), Poui(0));
+    assert_eq!(Poui::<u8>::expit(Mixed::new(100, Poui(0))), Poui(255));
+    let tiny = Poui(1u128).logit();
+    assert_eq!(tiny.int, -89);
+    assert_eq!(Poui(u64::MAX).logit().int, 44);
+}
+
+#[test]
+fn choose_weighted_slots_are_proportional() {
+    let weights = [Poui(5u8), Poui(0), Poui(250), Poui(1)];
+    let mut counts = [0usize; 4];
+    for r in 0..=255u8 {
+        counts[choose_weighted(&weights, Poui(r)).unwrap()] += 1;
+    }
+    // 256 spins spread over a total weight of 256, one slot each.
+    assert_eq!(counts, [5, 0, 250, 1]);
+    let thirds = [Poui(u64::MAX); 3];
+    assert_eq!(choose_weighted(&thirds, Poui(u64::MAX / 3)), Some(0));
+    assert_eq!(choose_weighted(&thirds, Poui(u64::MAX / 3 + 1)), Some(1));
+    assert_eq!(choose_weighted(&thirds, Poui(u64::MAX)), Some(2));
+    assert_eq!(choose_weighted::<u16>(&[], Poui(0)), None);
+}
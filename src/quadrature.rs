@@ -0,0 +1,156 @@
+use crate::Nco;
+use crate::Poui;
+use crate::ToSigned;
+use crate::TrigTable;
+use num_traits::AsPrimitive;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+
+/// A way of computing the sine and cosine of an unsigned `Poui` angle.
+///
+/// This lets [`QuadratureOsc`] choose at compile time between the
+/// [`QuadratureCordic`] routine and a precomputed [`TrigTable`].
+pub trait QuadratureSinCos<U: PrimInt + WrappingAdd> {
+    /// The backing type of the signed outputs.
+    type Output: PrimInt + WrappingAdd;
+
+    /// The sine and cosine of `angle`, as a fraction of a turn.
+    fn sin_cos(&self, angle: Poui<U>) -> (Poui<Self::Output>, Poui<Self::Output>);
+}
+
+/// Computes sines and cosines with [`Poui::sin_cos`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct QuadratureCordic;
+
+impl<U, S> QuadratureSinCos<U> for QuadratureCordic
+where
+    U: PrimInt + Unsigned + WrappingAdd + ToSigned<Signed = S>,
+    S: PrimInt + WrappingAdd + 'static,
+    i128: AsPrimitive<S>,
+{
+    type Output = S;
+
+    fn sin_cos(&self, angle: Poui<U>) -> (Poui<S>, Poui<S>) {
+        angle.sin_cos()
+    }
+}
+
+/// Implements `QuadratureSinCos` for trig tables with each signed backing and
+/// the unsigned backing of the same width used for angles.
+macro_rules! impl_table_sin_cos {
+    ($($s:ty => $u:ty),*) => {
+        $(
+            impl<const SIZE: usize> QuadratureSinCos<$u> for TrigTable<SIZE, $s> {
+                type Output = $s;
+
+                fn sin_cos(&self, angle: Poui<$u>) -> (Poui<$s>, Poui<$s>) {
+                    TrigTable::<SIZE, $s>::sin_cos(self, angle)
+                }
+            }
+        )*
+    };
+}
+
+impl_table_sin_cos!(i8 => u8, i16 => u16, i32 => u32, i64 => u64);
+
+impl<U: PrimInt + WrappingAdd, T: QuadratureSinCos<U>> QuadratureSinCos<U> for &T {
+    type Output = T::Output;
+
+    fn sin_cos(&self, angle: Poui<U>) -> (Poui<Self::Output>, Poui<Self::Output>) {
+        (**self).sin_cos(angle)
+    }
+}
+
+/// An oscillator producing a sine and cosine each tick, for IQ modulation or
+/// smooth periodic effects.
+///
+/// The phase comes from an [`Nco`], and the backend `B` computes the two
+/// outputs from it: [`QuadratureCordic`] by default, or a reference to a
+/// [`TrigTable`] for speed.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Nco;
+/// use poui::Poui;
+/// use poui::QuadratureOsc;
+/// use poui::TrigTable;
+///
+/// let mut osc = QuadratureOsc::new(Nco::new(Poui(0x4000u16)));
+/// assert_eq!(osc.tick(), (Poui(0), Poui(i16::MAX)));
+/// assert_eq!(osc.tick(), (Poui(i16::MAX), Poui(0)));
+/// assert_eq!(osc.tick(), (Poui(0), Poui(i16::MIN)));
+///
+/// static TABLE: TrigTable<256, i16> = TrigTable::<256, i16>::new();
+/// let osc = QuadratureOsc::with_backend(Nco::new(Poui(0x4000u16)), &TABLE);
+/// assert_eq!(osc.skip(3).next(), Some((Poui(i16::MIN), Poui(0))));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct QuadratureOsc<U: PrimInt + WrappingAdd, B = QuadratureCordic> {
+    nco: Nco<U>,
+    backend: B,
+}
+
+impl<U: PrimInt + Unsigned + WrappingAdd> QuadratureOsc<U>
+where
+    QuadratureCordic: QuadratureSinCos<U>,
+{
+    /// An oscillator driven by `nco`, using CORDIC.
+    pub fn new(nco: Nco<U>) -> Self {
+        QuadratureOsc::with_backend(nco, QuadratureCordic)
+    }
+}
+
+impl<U: PrimInt + Unsigned + WrappingAdd, B: QuadratureSinCos<U>> QuadratureOsc<U, B> {
+    /// An oscillator driven by `nco`, using `backend` to compute the outputs.
+    pub fn with_backend(nco: Nco<U>, backend: B) -> Self {
+        QuadratureOsc { nco, backend }
+    }
+
+    /// The phase accumulator driving the oscillator.
+    pub fn nco(&self) -> &Nco<U> {
+        &self.nco
+    }
+
+    /// The phase accumulator driving the oscillator, e.g. to retune it.
+    pub fn nco_mut(&mut self) -> &mut Nco<U> {
+        &mut self.nco
+    }
+
+    /// Returns the sine and cosine of the current phase, and advances to the
+    /// next phase.
+    pub fn tick(&mut self) -> (Poui<B::Output>, Poui<B::Output>) {
+        self.backend.sin_cos(self.nco.tick())
+    }
+}
+
+impl<U: PrimInt + Unsigned + WrappingAdd, B: QuadratureSinCos<U>> Iterator for QuadratureOsc<U, B> {
+    type Item = (Poui<B::Output>, Poui<B::Output>);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.tick())
+    }
+}
+
+#[test]
+fn quadrature_backends_agree() {
+    let table = TrigTable::<1024, i32>::new();
+    let nco = Nco::<u32>::with_frequency(1_234, 48_000);
+    let cordic = QuadratureOsc::new(nco);
+    let lookup = QuadratureOsc::with_backend(nco, &table);
+    for ((s1, c1), (s2, c2)) in cordic.zip(lookup).take(1000) {
+        assert!((s1.0 as i64 - s2.0 as i64).abs() < 1 << 14);
+        assert!((c1.0 as i64 - c2.0 as i64).abs() < 1 << 14);
+    }
+}
+
+#[test]
+fn quadrature_retune() {
+    let mut osc = QuadratureOsc::new(Nco::new(Poui(0u8)));
+    assert_eq!(osc.tick(), (Poui(0), Poui(i8::MAX)));
+    osc.nco_mut().set_tuning_word(Poui(128));
+    osc.tick();
+    assert_eq!(osc.tick(), (Poui(0), Poui(i8::MIN)));
+    assert_eq!(osc.nco().phase(), Poui(0));
+}
@@ -0,0 +1,197 @@
+use crate::frac_bits;
+use crate::Poui;
+use crate::StrictWiden;
+use num_traits::AsPrimitive;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+
+/// How [`quantile`] picks a value when the quantile falls between samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum QuantileMethod {
+    /// Interpolates linearly between the closest samples, so the `q`
+    /// quantile of `n` samples sits `q · (n − 1)` of the way through them in
+    /// sorted order. This is the default of most statistics packages.
+    #[default]
+    Interpolated,
+    /// The smallest sample with at least a fraction `q` of the samples at or
+    /// below it, which is always one of the samples.
+    NearestRank,
+}
+
+/// The `q` quantile of the samples, or `None` if there are none.
+///
+/// The samples are reordered in place by quickselect rather than sorted,
+/// so this takes linear time on average. The position of the quantile is
+/// computed exactly, and interpolation uses [`Poui::lerp`].
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::quantile;
+/// use poui::Poui;
+/// use poui::QuantileMethod;
+///
+/// let mut samples = [Poui(40u8), Poui(10), Poui(30), Poui(20)];
+/// let q = Poui(64); // 1/4
+/// assert_eq!(quantile(&mut samples, q, QuantileMethod::Interpolated), Some(Poui(17)));
+/// assert_eq!(quantile(&mut samples, q, QuantileMethod::NearestRank), Some(Poui(10)));
+/// ```
+pub fn quantile<N, M>(
+    samples: &mut [Poui<N>],
+    q: Poui<N>,
+    method: QuantileMethod,
+) -> Option<Poui<N>>
+where
+    N: PrimInt + Unsigned + WrappingAdd + StrictWiden<Widened = M> + 'static,
+    M: PrimInt + AsPrimitive<N>,
+{
+    percentiles(samples, &[q], method).map(|values| values[0])
+}
+
+/// The median of the samples, interpolated halfway between the middle two
+/// when there are an even number, or `None` if there are none.
+///
+/// The samples are reordered in place, as by [`quantile`].
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::median;
+/// use poui::Poui;
+///
+/// assert_eq!(median(&mut [Poui(9u8), Poui(1), Poui(5)]), Some(Poui(5)));
+/// assert_eq!(median(&mut [Poui(9u8), Poui(1), Poui(5), Poui(3)]), Some(Poui(4)));
+/// assert_eq!(median::<u8, u16>(&mut []), None);
+/// ```
+pub fn median<N, M>(samples: &mut [Poui<N>]) -> Option<Poui<N>>
+where
+    N: PrimInt + Unsigned + WrappingAdd + StrictWiden<Widened = M> + 'static,
+    M: PrimInt + AsPrimitive<N>,
+{
+    let half = Poui(N::one() << (frac_bits::<N>() - 1));
+    quantile(samples, half, QuantileMethod::Interpolated)
+}
+
+/// Several quantiles of the samples at once, in the order of `qs`, or
+/// `None` if there are no samples.
+///
+/// Each quantile is as for [`quantile`], but the samples are only
+/// partitioned once for all of them: each selection only searches the
+/// samples above the rank selected before it.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::percentiles;
+/// use poui::Poui;
+/// use poui::QuantileMethod;
+///
+/// let mut samples: Vec<_> = (0..=100u8).rev().map(Poui).collect();
+/// let qs = [Poui(128), Poui(0), Poui(230)];
+/// let values = percentiles(&mut samples, &qs, QuantileMethod::NearestRank);
+/// assert_eq!(values, Some(vec![Poui(50), Poui(0), Poui(90)]));
+/// ```
+pub fn percentiles<N, M>(
+    samples: &mut [Poui<N>],
+    qs: &[Poui<N>],
+    method: QuantileMethod,
+) -> Option<Vec<Poui<N>>>
+where
+    N: PrimInt + Unsigned + WrappingAdd + StrictWiden<Widened = M> + 'static,
+    M: PrimInt + AsPrimitive<N>,
+{
+    if samples.is_empty() {
+        return None;
+    }
+    let positions: Vec<_> = qs
+        .iter()
+        .map(|&q| position(q, samples.len(), method))
+        .collect();
+    let mut ranks: Vec<usize> = positions
+        .iter()
+        .flat_map(|&(rank, frac)| [Some(rank), (frac.0 != N::zero()).then_some(rank + 1)])
+        .flatten()
+        .collect();
+    ranks.sort_unstable();
+    ranks.dedup();
+    let mut start = 0;
+    for rank in ranks {
+        samples[start..].select_nth_unstable(rank - start);
+        start = rank + 1;
+    }
+    let values = positions.into_iter().map(|(rank, frac)| {
+        if frac.0 == N::zero() {
+            samples[rank]
+        } else {
+            samples[rank].lerp(samples[rank + 1], frac)
+        }
+    });
+    Some(values.collect())
+}
+
+/// The rank in sorted order of the `q` quantile of `len` samples, and how
+/// far it is towards the next rank.
+fn position<N: PrimInt + Unsigned + WrappingAdd>(
+    q: Poui<N>,
+    len: usize,
+    method: QuantileMethod,
+) -> (usize, Poui<N>) {
+    let bits = frac_bits::<N>();
+    let q = q.0.to_u128().unwrap();
+    match method {
+        QuantileMethod::Interpolated => {
+            let h = q * (len as u128 - 1);
+            let frac = h & ((1 << bits) - 1);
+            ((h >> bits) as usize, Poui(N::from(frac).unwrap()))
+        }
+        QuantileMethod::NearestRank => {
+            let rank = (q * len as u128).div_ceil(1 << bits) as usize;
+            (rank.saturating_sub(1), Poui(N::zero()))
+        }
+    }
+}
+
+#[test]
+fn quantiles_match_sorting() {
+    let mut samples: Vec<Poui<u16>> = (0..1000u32)
+        .map(|i| Poui((i * 7919 % 65521) as u16))
+        .collect();
+    let mut sorted = samples.clone();
+    sorted.sort();
+    let qs: Vec<_> = (0..=u16::MAX).step_by(997).map(Poui).collect();
+    let nearest = percentiles(&mut samples, &qs, QuantileMethod::NearestRank).unwrap();
+    let interpolated = percentiles(&mut samples, &qs, QuantileMethod::Interpolated).unwrap();
+    for ((q, nearest), interpolated) in qs.iter().zip(nearest).zip(interpolated) {
+        let rank = ((q.0 as usize * 1000).div_ceil(65536)).max(1);
+        assert_eq!(nearest, sorted[rank - 1], "{q:?} {rank}");
+        let h = q.0 as f64 / 65536.0 * 999.0;
+        let (lo, hi) = (
+            sorted[h as usize].0 as f64,
+            sorted[(h as usize + 1).min(999)].0 as f64,
+        );
+        let expected = lo + (hi - lo) * h.fract();
+        assert!((interpolated.0 as f64 - expected).abs() < 1.0, "{q:?}");
+    }
+}
+
+#[test]
+fn quantile_edge_cases() {
+    let mut one = [Poui(42u8)];
+    assert_eq!(
+        quantile(&mut one, Poui(255), QuantileMethod::Interpolated),
+        Some(Poui(42))
+    );
+    assert_eq!(
+        quantile(&mut one, Poui(0), QuantileMethod::NearestRank),
+        Some(Poui(42))
+    );
+    let mut same = [Poui(7u32); 5];
+    assert_eq!(median(&mut same), Some(Poui(7)));
+    assert_eq!(
+        percentiles::<u8, u16>(&mut [], &[Poui(0)], QuantileMethod::NearestRank),
+        None
+    );
+    let mut wide = [Poui(u64::MAX), Poui(0)];
+    assert_eq!(median(&mut wide), Some(Poui(u64::MAX / 2)));
+}
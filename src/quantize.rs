@@ -0,0 +1,151 @@
+use crate::frac_bits;
+use crate::Poui;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Poui<N> {
+    /// Snaps `self` to the nearest of `steps + 1` equally spaced detents,
+    /// at `k / steps` for `k` from 0 to `steps`, and returns the detent and
+    /// its index `k`. Ties snap upwards.
+    ///
+    /// Detents are placed at the smallest `Poui` not below `k / steps`, so
+    /// every detent is the first value that snaps to it with
+    /// [`Poui::quantize_floor`]. The last detent, 1, and any others that
+    /// round up to it, saturate to the largest `Poui`. Everything is
+    /// computed exactly with integer arithmetic.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `steps` is zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui(100u8).quantize(4), (Poui(128), 2));
+    /// assert_eq!(Poui(96u8).quantize(4), (Poui(128), 2));
+    /// assert_eq!(Poui(250u8).quantize(4), (Poui(255), 4));
+    /// assert_eq!(Poui(100u8).quantize(3), (Poui(86), 1));
+    /// ```
+    pub fn quantize(self, steps: u32) -> (Self, u32) {
+        let (k, rem) = self.scaled(steps);
+        let k = k + (rem >> 127) as u32;
+        (Self::detent(k, steps), k)
+    }
+
+    /// Snaps `self` down to the detent at or below it, like
+    /// [`Poui::quantize`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui(127u8).quantize_floor(4), (Poui(64), 1));
+    /// assert_eq!(Poui(128u8).quantize_floor(4), (Poui(128), 2));
+    /// ```
+    pub fn quantize_floor(self, steps: u32) -> (Self, u32) {
+        let (k, _) = self.scaled(steps);
+        (Self::detent(k, steps), k)
+    }
+
+    /// Snaps `self` up to the detent at or above it, like
+    /// [`Poui::quantize`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui(129u8).quantize_ceil(4), (Poui(192), 3));
+    /// assert_eq!(Poui(128u8).quantize_ceil(4), (Poui(128), 2));
+    /// ```
+    pub fn quantize_ceil(self, steps: u32) -> (Self, u32) {
+        let (k, rem) = self.scaled(steps);
+        let k = k + (rem != 0) as u32;
+        (Self::detent(k, steps), k)
+    }
+
+    /// Splits `self * steps` into its integer part and its fractional part,
+    /// scaled by `2^128`.
+    fn scaled(self, steps: u32) -> (u32, u128) {
+        assert!(steps != 0, "cannot quantize to zero steps");
+        let x = self.0.to_u128().unwrap() << (128 - frac_bits::<N>());
+        let steps = steps as u128;
+        let lo = (x as u64 as u128) * steps;
+        let hi = (x >> 64) * steps + (lo >> 64);
+        ((hi >> 64) as u32, hi << 64 | (lo as u64 as u128))
+    }
+
+    /// The smallest `Poui` not below `k / steps`, saturating at 1.
+    pub(crate) fn detent(k: u32, steps: u32) -> Self {
+        if k >= steps {
+            return Poui(N::max_value());
+        }
+        let bits = frac_bits::<N>();
+        let (k, steps) = (k as u128, steps as u128);
+        // Split 2^bits into q * steps + r, so that k * 2^bits / steps is
+        // k * q + k * r / steps without overflowing.
+        let (q, r) = if bits < 128 {
+            ((1 << bits) / steps, (1 << bits) % steps)
+        } else {
+            let (q, r) = (u128::MAX / steps, u128::MAX % steps + 1);
+            if r == steps {
+                (q + 1, 0)
+            } else {
+                (q, r)
+            }
+        };
+        Poui(N::from(k * q + (k * r).div_ceil(steps)).unwrap_or(N::max_value()))
+    }
+}
+
+#[test]
+fn quantize_matches_exact_rationals() {
+    for steps in [1u32, 2, 3, 7, 10, 255, 256, 1000] {
+        for x in 0..=255u8 {
+            let exact = x as u64 * steps as u64;
+            let detent = |k: u64| ((k * 256).div_ceil(steps as u64)).min(255) as u8;
+            let floor = exact / 256;
+            let ceil = exact.div_ceil(256);
+            let nearest = (exact + 128) / 256;
+            assert_eq!(
+                Poui(x).quantize_floor(steps),
+                (Poui(detent(floor)), floor as u32)
+            );
+            assert_eq!(
+                Poui(x).quantize_ceil(steps),
+                (Poui(detent(ceil)), ceil as u32)
+            );
+            assert_eq!(
+                Poui(x).quantize(steps),
+                (Poui(detent(nearest)), nearest as u32)
+            );
+            assert!(Poui(detent(floor)) <= Poui(x));
+        }
+    }
+}
+
+#[test]
+fn quantize_wide_backings() {
+    let (detent, k) = Poui(u128::MAX).quantize_floor(7);
+    assert_eq!((detent, k), (Poui(u128::MAX / 7 * 6 + 4), 6));
+    assert_eq!(
+        Poui(u128::MAX / 3 + 1).quantize_floor(3),
+        (Poui(u128::MAX / 3 + 1), 1)
+    );
+    assert_eq!(Poui(u128::MAX / 3).quantize_floor(3), (Poui(0), 0));
+    assert_eq!(Poui(u64::MAX).quantize(2), (Poui(u64::MAX), 2));
+    assert_eq!(
+        Poui(1u64 << 63).quantize_ceil(1 << 31),
+        (Poui(1 << 63), 1 << 30)
+    );
+}
+
+#[test]
+#[should_panic]
+fn quantize_zero_steps() {
+    Poui(3u16).quantize(0);
+}
@@ -0,0 +1,149 @@
+use crate::Poui;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+
+/// A linear ramp of a gain, for changing it without clicks.
+///
+/// Jumping the gain applied to audio makes an audible click, so gain
+/// changes are usually spread over a few milliseconds of samples instead.
+/// Setting a target starts a straight-line ramp from the current value,
+/// lasting the given number of ticks. Each value is computed from the start
+/// and the number of ticks so far by exact integer division, rather than
+/// by adding up a rounded step, so the ramp lands exactly on the target on
+/// its last tick, and stays there.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Poui;
+/// use poui::Ramp;
+///
+/// let mut gain = Ramp::new(Poui(0u16));
+/// gain.set_target(Poui(1000), 3);
+/// let values: Vec<_> = (0..4).map(|_| gain.tick()).collect();
+/// assert_eq!(values, [Poui(333), Poui(666), Poui(1000), Poui(1000)]);
+/// assert!(!gain.is_ramping());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Ramp<N: PrimInt + Unsigned + WrappingAdd> {
+    start: Poui<N>,
+    target: Poui<N>,
+    value: Poui<N>,
+    length: u32,
+    elapsed: u32,
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Ramp<N> {
+    /// A ramp at rest at `value`.
+    pub fn new(value: Poui<N>) -> Self {
+        Ramp {
+            start: value,
+            target: value,
+            value,
+            length: 0,
+            elapsed: 0,
+        }
+    }
+
+    /// Starts ramping from the current value to `target`, arriving after
+    /// `ticks` ticks. With no ticks, the value jumps straight to the target.
+    pub fn set_target(&mut self, target: Poui<N>, ticks: u32) {
+        self.start = self.value;
+        self.target = target;
+        self.length = ticks;
+        self.elapsed = 0;
+        if ticks == 0 {
+            self.value = target;
+        }
+    }
+
+    /// The current value.
+    pub fn value(&self) -> Poui<N> {
+        self.value
+    }
+
+    /// The value the ramp is heading for.
+    pub fn target(&self) -> Poui<N> {
+        self.target
+    }
+
+    /// Whether the ramp has yet to reach its target.
+    pub fn is_ramping(&self) -> bool {
+        self.elapsed < self.length
+    }
+
+    /// The number of ticks left until the ramp reaches its target.
+    pub fn remaining(&self) -> u32 {
+        self.length - self.elapsed
+    }
+
+    /// Jumps to `value`, abandoning any ramp in progress.
+    pub fn reset(&mut self, value: Poui<N>) {
+        *self = Ramp::new(value);
+    }
+
+    /// Advances the ramp by a tick, and returns the new value.
+    pub fn tick(&mut self) -> Poui<N> {
+        if !self.is_ramping() {
+            return self.value;
+        }
+        self.elapsed += 1;
+        let (start, target) = (
+            self.start.0.to_u128().unwrap(),
+            self.target.0.to_u128().unwrap(),
+        );
+        let distance = start.abs_diff(target);
+        // `distance · elapsed / length`, rounded towards the start, without
+        // overflowing.
+        let (n, k) = (self.length as u128, self.elapsed as u128);
+        let step = distance / n * k + distance % n * k / n;
+        let value = if target >= start {
+            start + step
+        } else {
+            start - step
+        };
+        self.value = Poui(N::from(value).unwrap());
+        self.value
+    }
+}
+
+#[test]
+fn ramp_arrives_exactly() {
+    for (from, to, ticks) in [
+        (0u64, u64::MAX, 7),
+        (u64::MAX, 1, 1000),
+        (5, 5, 3),
+        (9, 2, 10),
+    ] {
+        let mut ramp = Ramp::new(Poui(from));
+        ramp.set_target(Poui(to), ticks);
+        let mut previous = from;
+        for i in 1..=ticks {
+            let value = ramp.tick().0;
+            assert!(value.abs_diff(previous) <= from.abs_diff(to) / ticks as u64 + 1);
+            assert!(value.abs_diff(from) <= from.abs_diff(to));
+            previous = value;
+            assert_eq!(ramp.remaining(), ticks - i);
+        }
+        assert_eq!(ramp.value(), Poui(to));
+        assert_eq!(ramp.tick(), Poui(to));
+    }
+}
+
+#[test]
+fn ramp_retargets_from_current_value() {
+    let mut ramp = Ramp::new(Poui(0u8));
+    ramp.set_target(Poui(200), 4);
+    ramp.tick();
+    ramp.tick();
+    assert_eq!(ramp.value(), Poui(100));
+    ramp.set_target(Poui(0), 2);
+    assert_eq!(ramp.tick(), Poui(50));
+    assert_eq!(ramp.tick(), Poui(0));
+    ramp.set_target(Poui(77), 0);
+    assert_eq!(ramp.value(), Poui(77));
+    assert!(!ramp.is_ramping());
+    ramp.reset(Poui(1));
+    assert_eq!((ramp.value(), ramp.target()), (Poui(1), Poui(1)));
+}
@@ -0,0 +1,458 @@
+use crate::frac_bits;
+use crate::probability::mul_hi;
+use crate::probability::total_weight;
+use crate::probability::weighted_index;
+use crate::Poui;
+use num_traits::Num;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+use num_traits::WrappingSub;
+use rand::distributions::uniform::SampleBorrow;
+use rand::distributions::uniform::SampleUniform;
+use rand::distributions::uniform::UniformSampler;
+use rand::distributions::Distribution;
+use rand::distributions::Standard;
+use rand::seq::SliceRandom;
+use rand::Fill;
+use rand::Rng;
+
+/// Samples a uniformly distributed `Poui`.
+///
+/// Every bit pattern of the backing is a valid `Poui`, so this is exactly
+/// uniform over the representable points, with no rejection or rounding.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Poui;
+/// use rand::Rng;
+///
+/// let p: Poui<u32> = rand::thread_rng().gen();
+/// let angle: Poui<i16> = rand::thread_rng().gen();
+/// ```
+impl<N: Num + WrappingAdd> Distribution<Poui<N>> for Standard
+where
+    Standard: Distribution<N>,
+{
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Poui<N> {
+        Poui(rng.gen())
+    }
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Poui<N>
+where
+    Standard: Distribution<N>,
+{
+    /// Treats `self` as a probability and runs a Bernoulli trial, returning
+    /// `true` with probability exactly `self`.
+    ///
+    /// This compares a fresh uniform `Poui` of the same width against
+    /// `self`, so there is no float conversion and no bias. Since `Poui`
+    /// can't represent 1, the trial is never certain to succeed, but it
+    /// always fails for 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// let mut rng = rand::thread_rng();
+    /// assert!(!Poui(0u8).sample_bool(&mut rng));
+    /// let heads = Poui(0x8000u16).sample_bool(&mut rng);
+    /// ```
+    pub fn sample_bool<R: Rng + ?Sized>(self, rng: &mut R) -> bool {
+        rng.gen::<Poui<N>>() < self
+    }
+}
+
+/// Samples `Poui`s uniformly from an arc, for use with
+/// [`rand::distributions::Uniform`].
+///
+/// The range runs forward from the low end to the high end, like
+/// [`Poui::is_between`], so it may wrap around: if `low > high` it covers
+/// `[low, 1)` and `[0, high)`, which is handy for ranges of angles. An
+/// inclusive range whose high end is just before its low end covers the
+/// whole interval.
+///
+/// Sampling never rejects: a wide random number is multiplied by the width
+/// of the range, and the top half of the product is the offset from the low
+/// end. With 128 random bits for backings up to 64 bits wide, and 256 bits
+/// beyond, the bias is below one part in `2^64`.
+///
+/// `Rng::gen_range` checks that `low < high` before sampling, so wrapped
+/// ranges need a `Uniform` built directly.
+///
+/// # Panics
+///
+/// Building an exclusive range panics if `low == high`, since the arc is
+/// empty.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Poui;
+/// use rand::distributions::Distribution;
+/// use rand::distributions::Uniform;
+///
+/// let mut rng = rand::thread_rng();
+/// let middle = Uniform::new(Poui(64u8), Poui(192u8)).sample(&mut rng);
+/// assert!(Poui(64) <= middle && middle < Poui(192));
+///
+/// // Within an eighth of a turn either side of 0.
+/// let heading = Uniform::new_inclusive(Poui(224u8), Poui(32u8)).sample(&mut rng);
+/// assert!(heading.is_between(Poui(224), Poui(33)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UniformPoui<N> {
+    low: N,
+    // One less than the number of points in the range, as an unsigned bit
+    // pattern, so that the whole interval fits even for 128-bit backings.
+    span: u128,
+}
+
+impl<N: PrimInt + WrappingAdd + WrappingSub> SampleUniform for Poui<N> {
+    type Sampler = UniformPoui<N>;
+}
+
+impl<N: PrimInt + WrappingAdd + WrappingSub> UniformSampler for UniformPoui<N> {
+    type X = Poui<N>;
+
+    fn new<B1, B2>(low: B1, high: B2) -> Self
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        let (low, high) = (low.borrow().0, high.borrow().0);
+        assert!(low != high, "Uniform::new called with an empty arc");
+        Self::new_inclusive(Poui(low), Poui(high.wrapping_sub(&N::one())))
+    }
+
+    fn new_inclusive<B1, B2>(low: B1, high: B2) -> Self
+    where
+        B1: SampleBorrow<Self::X> + Sized,
+        B2: SampleBorrow<Self::X> + Sized,
+    {
+        let (low, high) = (low.borrow().0, high.borrow().0);
+        UniformPoui {
+            low,
+            span: to_bits(high.wrapping_sub(&low)),
+        }
+    }
+
+    fn sample<R: Rng + ?Sized>(&self, rng: &mut R) -> Poui<N> {
+        let offset = match self.span.checked_add(1) {
+            None => rng.gen(),
+            Some(width) if N::zero().count_zeros() <= 64 => mul_hi(rng.gen(), width),
+            Some(width) => {
+                // The top 128 bits of a 256-bit random number times `width`.
+                let (hi, lo): (u128, u128) = rng.gen();
+                let (_, carry) = hi.wrapping_mul(width).overflowing_add(mul_hi(lo, width));
+                mul_hi(hi, width) + carry as u128
+            }
+        };
+        Poui(self.low.wrapping_add(&from_bits(offset)))
+    }
+}
+
+/// The bit pattern of `n`, zero-extended to 128 bits.
+fn to_bits<N: PrimInt>(n: N) -> u128 {
+    let mask = u128::MAX >> (128 - N::zero().count_zeros());
+    n.to_u128()
+        .unwrap_or_else(|| n.to_i128().unwrap() as u128 & mask)
+}
+
+/// The inverse of [`to_bits`], for bit patterns that fit in `N`.
+fn from_bits<N: PrimInt>(x: u128) -> N {
+    N::from(x).unwrap_or_else(|| {
+        let shift = 128 - N::zero().count_zeros();
+        N::from(((x << shift) as i128) >> shift).unwrap()
+    })
+}
+
+impl<N: Num + WrappingAdd> Poui<N>
+where
+    [N]: Fill,
+{
+    /// Fills a slice of `Poui`s with uniformly distributed values, in bulk.
+    ///
+    /// Every bit pattern of the backing is a valid `Poui`, so this fills the
+    /// underlying integers directly, with the same results as
+    /// [`Rng::fill`] on `[N]`. The orphan rules don't allow implementing
+    /// [`Fill`] for slices of a foreign type, hence the associated function.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the random number generator fails, like [`Rng::fill`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// let mut phases = [Poui(0u32); 1024];
+    /// Poui::fill(&mut phases, &mut rand::thread_rng());
+    /// ```
+    pub fn fill<R: Rng + ?Sized>(pouis: &mut [Self], rng: &mut R) {
+        // SAFETY: `Poui` is `#[repr(transparent)]` over `N`, so the slices
+        // have the same layout, and every `N` is a valid `Poui<N>`.
+        let raw =
+            unsafe { std::slice::from_raw_parts_mut(pouis.as_mut_ptr().cast::<N>(), pouis.len()) };
+        rng.fill(raw);
+    }
+}
+
+/// Chooses an index at random, with probability proportional to its weight,
+/// or returns `None` if every weight is zero.
+///
+/// Unlike [`choose_weighted`](crate::choose_weighted) with a random `Poui`,
+/// this draws uniformly from the exact total weight, so the probabilities
+/// are exact even when the weights are fine-grained.
+///
+/// # Panics
+///
+/// Panics if the weights sum to `2^128` or more, which needs 128-bit
+/// weights.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::sample_weighted;
+/// use poui::Poui;
+///
+/// let loot = [Poui(200u8), Poui(50), Poui(5), Poui(0)];
+/// let drop = sample_weighted(&loot, &mut rand::thread_rng()).unwrap();
+/// assert!(drop < 3);
+/// ```
+pub fn sample_weighted<N, R>(weights: &[Poui<N>], rng: &mut R) -> Option<usize>
+where
+    N: PrimInt + Unsigned + WrappingAdd,
+    R: Rng + ?Sized,
+{
+    match total_weight(weights) {
+        0 => None,
+        total => Some(weighted_index(weights, rng.gen_range(0..total))),
+    }
+}
+
+/// One random `Poui` in each of `n` equal strata of the unit interval, in
+/// random order, or nothing if `n` is zero.
+///
+/// Stratum `k` is the `Poui`s from `k / n` up to `(k + 1) / n`, with the
+/// boundaries rounded exactly as by [`Poui::quantize_ceil`], so the strata
+/// partition the interval and [`Poui::quantize_floor`] maps each sample
+/// back to its stratum. Each sample is uniform within its stratum. This
+/// gets most of the evenness of a grid without its aliasing.
+///
+/// # Panics
+///
+/// Panics if `n` is greater than the number of `Poui`s.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::stratified;
+/// use poui::Poui;
+///
+/// let rng = &mut rand::thread_rng();
+/// let mut strata: Vec<u32> = stratified::<u16, _>(10, rng)
+///     .map(|x| x.quantize_floor(10).1)
+///     .collect();
+/// strata.sort();
+/// assert_eq!(strata, (0..10).collect::<Vec<_>>());
+/// ```
+pub fn stratified<N, R>(n: u32, rng: &mut R) -> impl Iterator<Item = Poui<N>> + '_
+where
+    N: PrimInt + Unsigned + WrappingAdd + WrappingSub,
+    R: Rng + ?Sized,
+{
+    check_strata::<N>(n);
+    let mut order: Vec<u32> = (0..n).collect();
+    order.shuffle(rng);
+    order.into_iter().map(move |k| jitter(k, n, rng))
+}
+
+/// One random point in each cell of an `n` by `m` grid over the unit
+/// square, in random order.
+///
+/// The cells are the products of the strata of [`stratified`], `n` along
+/// the first coordinate and `m` along the second, and each point is
+/// uniform within its cell.
+///
+/// # Panics
+///
+/// Panics if `n` or `m` is greater than the number of `Poui`s.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::stratified_2d;
+/// use poui::Poui;
+///
+/// let points: Vec<[Poui<u8>; 2]> = stratified_2d(4, 3, &mut rand::thread_rng()).collect();
+/// assert_eq!(points.len(), 12);
+/// ```
+pub fn stratified_2d<N, R>(n: u32, m: u32, rng: &mut R) -> impl Iterator<Item = [Poui<N>; 2]> + '_
+where
+    N: PrimInt + Unsigned + WrappingAdd + WrappingSub,
+    R: Rng + ?Sized,
+{
+    check_strata::<N>(n);
+    check_strata::<N>(m);
+    let mut order: Vec<(u32, u32)> = (0..n).flat_map(|i| (0..m).map(move |j| (i, j))).collect();
+    order.shuffle(rng);
+    order
+        .into_iter()
+        .map(move |(i, j)| [jitter(i, n, rng), jitter(j, m, rng)])
+}
+
+/// Checks that every one of `n` strata has a `Poui` in it.
+fn check_strata<N: PrimInt + Unsigned>(n: u32) {
+    let bits = frac_bits::<N>();
+    assert!(bits >= 32 || n <= 1 << bits, "more strata than Pouis");
+}
+
+/// A uniformly random `Poui` in stratum `k` of `n`.
+fn jitter<N, R>(k: u32, n: u32, rng: &mut R) -> Poui<N>
+where
+    N: PrimInt + Unsigned + WrappingAdd + WrappingSub,
+    R: Rng + ?Sized,
+{
+    let low = Poui::detent(k, n);
+    // The last stratum ends at 1, which wraps around to 0.
+    let high = match k + 1 {
+        end if end == n => N::zero(),
+        end => Poui::<N>::detent(end, n).0,
+    };
+    UniformPoui::new_inclusive(low, Poui(high.wrapping_sub(&N::one()))).sample(rng)
+}
+
+#[cfg(test)]
+pub(crate) fn test_rng() -> rand::rngs::StdRng {
+    rand::SeedableRng::seed_from_u64(0x5EED)
+}
+
+#[test]
+fn standard_covers_every_backing() {
+    let mut rng = test_rng();
+    let _: (Poui<u8>, Poui<u16>, Poui<u32>) = rng.gen();
+    let _: (Poui<u64>, Poui<u128>, Poui<usize>) = rng.gen();
+    let _: (Poui<i8>, Poui<i16>, Poui<i32>) = rng.gen();
+    let _: (Poui<i64>, Poui<i128>, Poui<isize>) = rng.gen();
+    let mut counts = [0u32; 256];
+    for _ in 0..256 * 400 {
+        let p: Poui<u8> = rng.gen();
+        counts[p.0 as usize] += 1;
+    }
+    assert!(counts.iter().all(|&c| (300..500).contains(&c)));
+}
+
+#[test]
+fn sample_bool_is_exact_bernoulli() {
+    let mut rng = test_rng();
+    assert!((0..1000).all(|_| !Poui(0u32).sample_bool(&mut rng)));
+    for p in [1u8, 64, 128, 255] {
+        let trials = 100_000;
+        let hits = (0..trials)
+            .filter(|_| Poui(p).sample_bool(&mut rng))
+            .count();
+        let expected = trials * p as usize / 256;
+        assert!(hits.abs_diff(expected) < 800, "{p}: {hits}");
+    }
+}
+
+#[test]
+fn uniform_covers_wrapped_and_unwrapped_arcs() {
+    use rand::distributions::Uniform;
+    let mut rng = test_rng();
+    let mut counts = [0u32; 256];
+    for p in Uniform::new(Poui(250u8), Poui(4u8))
+        .sample_iter(&mut rng)
+        .take(10_000)
+    {
+        counts[p.0 as usize] += 1;
+    }
+    for (i, &c) in counts.iter().enumerate() {
+        if (250..=255).contains(&i) || i < 4 {
+            assert!((900..1600).contains(&c), "{i}: {c}");
+        } else {
+            assert_eq!(c, 0, "{i}");
+        }
+    }
+    let signed = Uniform::new_inclusive(Poui(-3i8), Poui(2i8));
+    assert!((0..1000).all(|_| (-3..=2).contains(&signed.sample(&mut rng).0)));
+    let whole = Uniform::new_inclusive(Poui(5u16), Poui(4u16));
+    let seen: std::collections::HashSet<_> = (0..200_000)
+        .map(|_| whole.sample(&mut rng).0 >> 8)
+        .collect();
+    assert_eq!(seen.len(), 256);
+    let top = Uniform::new(Poui(u128::MAX - 2), Poui(1u128));
+    assert!((0..1000).all(|_| !top
+        .sample(&mut rng)
+        .is_between(Poui(1), Poui(u128::MAX - 2))));
+    let all = Uniform::new_inclusive(Poui(0i128), Poui(-1i128));
+    let _ = all.sample(&mut rng);
+}
+
+#[test]
+fn wide_multiply_reduction_is_exact() {
+    assert_eq!(mul_hi(u128::MAX, u128::MAX), u128::MAX - 1);
+    assert_eq!(mul_hi(1 << 127, 6), 3);
+    assert_eq!(mul_hi(u128::MAX, 7), 6);
+    for n in [0i8, 1, -1, i8::MIN, i8::MAX] {
+        assert_eq!(from_bits::<i8>(to_bits(n)), n);
+    }
+    assert_eq!(to_bits(-1i8), 0xFF);
+    assert_eq!(to_bits(-2i128), u128::MAX - 1);
+    assert_eq!(from_bits::<i128>(u128::MAX), -1);
+}
+
+#[test]
+fn fill_matches_filling_the_backing() {
+    let mut pouis = [Poui(0u16); 100];
+    let mut raw = [0u16; 100];
+    Poui::fill(&mut pouis, &mut test_rng());
+    test_rng().fill(&mut raw[..]);
+    assert!(pouis.iter().zip(raw).all(|(p, r)| p.0 == r));
+    assert!(raw.iter().any(|&r| r != 0));
+    let mut angles = vec![Poui(0i64); 33];
+    Poui::fill(&mut angles, &mut test_rng());
+    assert!(angles.iter().any(|&a| a.0 < 0));
+}
+
+#[test]
+fn sample_weighted_is_proportional() {
+    let mut rng = test_rng();
+    let weights = [Poui(3u8), Poui(0), Poui(1)];
+    let mut counts = [0u32; 3];
+    for _ in 0..40_000 {
+        counts[sample_weighted(&weights, &mut rng).unwrap()] += 1;
+    }
+    assert_eq!(counts[1], 0);
+    assert!(counts[0].abs_diff(30_000) < 600, "{counts:?}");
+    assert_eq!(sample_weighted(&[Poui(0u32); 4], &mut rng), None);
+}
+
+#[test]
+fn stratified_samples_hit_every_stratum_once() {
+    let mut rng = test_rng();
+    for n in [1, 2, 3, 7, 256] {
+        let mut strata: Vec<_> = stratified::<u8, _>(n, &mut rng)
+            .map(|x| x.quantize_floor(n).1)
+            .collect();
+        strata.sort();
+        assert_eq!(strata, (0..n).collect::<Vec<_>>(), "{n}");
+    }
+    let mut cells: Vec<_> = stratified_2d::<u64, _>(5, 3, &mut rng)
+        .map(|[x, y]| (x.quantize_floor(5).1, y.quantize_floor(3).1))
+        .collect();
+    cells.sort();
+    let expected: Vec<_> = (0..5).flat_map(|i| (0..3).map(move |j| (i, j))).collect();
+    assert_eq!(cells, expected);
+}
+
+#[test]
+#[should_panic]
+fn stratified_rejects_more_strata_than_pouis() {
+    let _ = stratified::<u8, _>(257, &mut test_rng());
+}
@@ -0,0 +1,165 @@
+use crate::frac_bits;
+use crate::probability::div_scaled;
+use crate::Poui;
+use num_traits::PrimInt;
+use num_traits::WrappingAdd;
+
+/// The error returned when an integer ratio cannot be converted to a `Poui`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FromRatioError {
+    /// The denominator was zero.
+    ZeroDenominator,
+    /// The ratio was outside the interval represented by the backing type,
+    /// i.e. [0, 1) for unsigned backings or [-1, 1) for signed backings.
+    OutOfRange,
+}
+
+impl std::fmt::Display for FromRatioError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FromRatioError::ZeroDenominator => write!(f, "denominator is zero"),
+            FromRatioError::OutOfRange => {
+                write!(f, "ratio is outside the interval represented by the Poui")
+            }
+        }
+    }
+}
+
+impl std::error::Error for FromRatioError {}
+
+impl<N: PrimInt + WrappingAdd> Poui<N> {
+    /// Creates the point on the unit interval nearest to
+    /// `numerator / denominator`.
+    ///
+    /// The division is exact long division on the magnitudes, so no
+    /// floating-point rounding is involved, even for 128-bit backings. Ties
+    /// are rounded away from zero. Since both parts of the ratio fit in the
+    /// backing type, a ratio less than 1 never rounds up to 1.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`FromRatioError::ZeroDenominator`] if `denominator` is zero,
+    /// and [`FromRatioError::OutOfRange`] if the ratio is not in [0, 1) (or
+    /// [-1, 1) for signed backings).
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::FromRatioError;
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui::from_ratio(1u8, 4), Ok(Poui(64u8)));
+    /// assert_eq!(Poui::from_ratio(1u16, 3), Ok(Poui(21845u16)));
+    /// assert_eq!(Poui::from_ratio(-1i8, 2), Ok(Poui(-64i8)));
+    /// assert_eq!(Poui::from_ratio(3u8, 3), Err(FromRatioError::OutOfRange));
+    /// assert_eq!(Poui::from_ratio(1u8, 0), Err(FromRatioError::ZeroDenominator));
+    /// ```
+    pub fn from_ratio(numerator: N, denominator: N) -> Result<Self, FromRatioError> {
+        if denominator == N::zero() {
+            return Err(FromRatioError::ZeroDenominator);
+        }
+        let negative = (numerator < N::zero()) != (denominator < N::zero());
+        let (num, den) = (magnitude(numerator), magnitude(denominator));
+        if num > den || (num == den && !negative) {
+            return Err(FromRatioError::OutOfRange);
+        }
+        if num == den {
+            return Ok(Poui(N::min_value()));
+        }
+        let (quotient, remainder) = div_scaled(num, den, frac_bits::<N>());
+        // Round half up, i.e. away from zero, without overflowing `2r`.
+        let quotient = if remainder >= den - remainder {
+            quotient + 1
+        } else {
+            quotient
+        };
+        let quotient = N::from(quotient).unwrap();
+        if negative {
+            Ok(Poui(N::zero() - quotient))
+        } else {
+            Ok(Poui(quotient))
+        }
+    }
+}
+
+fn magnitude<N: PrimInt>(n: N) -> u128 {
+    if n < N::zero() {
+        n.to_i128().unwrap().unsigned_abs()
+    } else {
+        n.to_u128().unwrap()
+    }
+}
+
+#[test]
+fn from_ratio_exact() {
+    assert_eq!(Poui::from_ratio(0u8, 7), Ok(Poui(0u8)));
+    assert_eq!(Poui::from_ratio(1u8, 2), Ok(Poui(128u8)));
+    assert_eq!(Poui::from_ratio(3u16, 4), Ok(Poui(49152u16)));
+    assert_eq!(Poui::from_ratio(1u32, 1 << 31), Ok(Poui(2u32)));
+    assert_eq!(Poui::from_ratio(5u64, 8), Ok(Poui(5u64 << 61)));
+}
+
+#[test]
+fn from_ratio_rounds_to_nearest() {
+    // 256 / 3 = 85.33
+    assert_eq!(Poui::from_ratio(1u8, 3), Ok(Poui(85u8)));
+    // 512 / 3 = 170.67
+    assert_eq!(Poui::from_ratio(2u8, 3), Ok(Poui(171u8)));
+    // 2^32 * 113 / 355 = 1367130435.06
+    assert_eq!(Poui::from_ratio(113u32, 355), Ok(Poui(1367130435u32)));
+}
+
+#[test]
+fn from_ratio_near_one() {
+    // 256 * 254 / 255 = 254.996
+    assert_eq!(Poui::from_ratio(254u8, 255), Ok(Poui(255u8)));
+    assert_eq!(Poui::from_ratio(999u16, 1000), Ok(Poui(65470u16)));
+    assert_eq!(Poui::from_ratio(126i8, 127), Ok(Poui(127i8)));
+}
+
+#[test]
+fn from_ratio_signed() {
+    assert_eq!(Poui::from_ratio(1i8, -2), Ok(Poui(-64i8)));
+    assert_eq!(Poui::from_ratio(-1i8, -2), Ok(Poui(64i8)));
+    assert_eq!(Poui::from_ratio(-5i16, 5), Ok(Poui(i16::MIN)));
+    assert_eq!(
+        Poui::from_ratio(i8::MIN, i8::MIN),
+        Err(FromRatioError::OutOfRange)
+    );
+    assert_eq!(
+        Poui::from_ratio(i8::MIN, i8::MAX),
+        Err(FromRatioError::OutOfRange)
+    );
+}
+
+#[test]
+fn from_ratio_errors() {
+    assert_eq!(
+        Poui::from_ratio(0u8, 0),
+        Err(FromRatioError::ZeroDenominator)
+    );
+    assert_eq!(Poui::from_ratio(5u8, 4), Err(FromRatioError::OutOfRange));
+    assert_eq!(Poui::from_ratio(4u8, 4), Err(FromRatioError::OutOfRange));
+    assert_eq!(Poui::from_ratio(3i8, -1), Err(FromRatioError::OutOfRange));
+}
+
+#[test]
+fn from_ratio_128_bits() {
+    assert_eq!(Poui::from_ratio(1u128, 3), Ok(Poui(u128::MAX / 3)));
+    assert_eq!(Poui::from_ratio(1u128, 2), Ok(Poui(1u128 << 127)));
+    assert_eq!(
+        Poui::from_ratio(u128::MAX - 1, u128::MAX),
+        Ok(Poui(u128::MAX))
+    );
+    assert_eq!(Poui::from_ratio(1i128, 3), Ok(Poui(i128::MAX / 3 + 1)));
+    assert_eq!(Poui::from_ratio(-1i128, 3), Ok(Poui(-(i128::MAX / 3 + 1))));
+    assert_eq!(
+        Poui::from_ratio(i128::MIN, i128::MIN + 1),
+        Err(FromRatioError::OutOfRange)
+    );
+    assert_eq!(
+        Poui::from_ratio(i128::MIN + 1, i128::MAX),
+        Ok(Poui(i128::MIN))
+    );
+    assert_eq!(Poui::from_ratio(i128::MAX, i128::MIN), Ok(Poui(-i128::MAX)));
+}
@@ -0,0 +1,188 @@
+use crate::frac_bits;
+use crate::Poui;
+use crate::StrictWiden;
+use num_traits::AsPrimitive;
+use num_traits::PrimInt;
+use num_traits::WrappingAdd;
+
+/// A precomputed linear mapping from one subrange of the unit interval to
+/// another.
+///
+/// Remapping a value from `[from_lo, from_hi)` to `[to_lo, to_hi)` requires a
+/// division by the length of the source range. `Remap` performs that division
+/// once, when it is constructed, and stores the ratio of the two lengths as a
+/// widened fixed-point scale factor. Each call to [`Remap::apply`] is then just
+/// a widened multiplication and a shift.
+///
+/// Either range may run in either direction, e.g. `from_lo` may be greater
+/// than `from_hi`, in which case increasing inputs map to decreasing outputs.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Poui;
+/// use poui::Remap;
+///
+/// let remap = Remap::new(Poui(64u8), Poui(192u8), Poui(0u8), Poui(128u8)).unwrap();
+/// assert_eq!(remap.apply(Poui(64u8)), Poui(0u8));
+/// assert_eq!(remap.apply(Poui(128u8)), Poui(64u8));
+/// assert_eq!(remap.apply(Poui(160u8)), Poui(96u8));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Remap<N: StrictWiden> {
+    from: N::Widened,
+    from_len: N::Widened,
+    from_descending: bool,
+    to: N::Widened,
+    to_descending: bool,
+    scale: N::Widened,
+}
+
+impl<N, M> Remap<N>
+where
+    N: PrimInt + WrappingAdd + StrictWiden<Widened = M> + 'static,
+    M: PrimInt + AsPrimitive<N>,
+{
+    /// Creates a mapping from `[from_lo, from_hi)` to `[to_lo, to_hi)`.
+    ///
+    /// Returns `None` if the source range is empty, i.e. if
+    /// `from_lo == from_hi`. An empty target range is allowed and maps every
+    /// input to `to_lo`.
+    pub fn new(from_lo: Poui<N>, from_hi: Poui<N>, to_lo: Poui<N>, to_hi: Poui<N>) -> Option<Self> {
+        let (from, from_len, from_descending) = span(from_lo.0.widen(), from_hi.0.widen());
+        let (to, to_len, to_descending) = span(to_lo.0.widen(), to_hi.0.widen());
+        if from_len == M::zero() {
+            return None;
+        }
+        // Since `to_len` fits in the backing type, shifting it left by the
+        // number of fractional bits always fits in the widened type.
+        let scale = (to_len << frac_bits::<N>()) / from_len;
+        Some(Remap {
+            from,
+            from_len,
+            from_descending,
+            to,
+            to_descending,
+            scale,
+        })
+    }
+
+    /// Maps `x` from the source range to the target range.
+    ///
+    /// Values of `x` outside the source range are clamped to it, so the result
+    /// always lies within the target range. The result is rounded towards
+    /// `to_lo`.
+    pub fn apply(&self, x: Poui<N>) -> Poui<N> {
+        let x = x.0.widen();
+        let offset = if self.from_descending {
+            if x >= self.from {
+                M::zero()
+            } else {
+                self.from - x
+            }
+        } else if x <= self.from {
+            M::zero()
+        } else {
+            x - self.from
+        };
+        let offset = offset.min(self.from_len - M::one());
+        // `offset < from_len` and `scale <= (to_len << f) / from_len`, so the
+        // product is less than `to_len << f` and cannot overflow.
+        let delta = (offset * self.scale) >> frac_bits::<N>();
+        let y = if self.to_descending {
+            self.to - delta
+        } else {
+            self.to + delta
+        };
+        Poui(y.as_())
+    }
+}
+
+/// Returns the start, length and direction of the range from `lo` to `hi`.
+fn span<M: PrimInt>(lo: M, hi: M) -> (M, M, bool) {
+    if hi >= lo {
+        (lo, hi - lo, false)
+    } else {
+        (lo, lo - hi, true)
+    }
+}
+
+impl<N, M> Poui<N>
+where
+    N: PrimInt + WrappingAdd + StrictWiden<Widened = M> + 'static,
+    M: PrimInt + AsPrimitive<N>,
+{
+    /// Remaps `self` from `[from_lo, from_hi)` to `[to_lo, to_hi)`.
+    ///
+    /// This is a shorthand for constructing a [`Remap`] and applying it once.
+    /// When remapping many values between the same ranges, construct the
+    /// `Remap` once instead, to avoid repeating the division.
+    ///
+    /// Returns `None` if the source range is empty.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// let x = Poui(150u8);
+    /// let y = x.remap(Poui(100u8), Poui(200u8), Poui(0u8), Poui(200u8));
+    /// assert_eq!(y, Some(Poui(100u8)));
+    /// ```
+    pub fn remap(self, from_lo: Self, from_hi: Self, to_lo: Self, to_hi: Self) -> Option<Self> {
+        Remap::new(from_lo, from_hi, to_lo, to_hi).map(|remap| remap.apply(self))
+    }
+}
+
+#[test]
+fn remap_shrink_u8() {
+    let remap = Remap::new(Poui(0u8), Poui(200u8), Poui(50u8), Poui(150u8)).unwrap();
+    assert_eq!(remap.apply(Poui(0u8)), Poui(50u8));
+    assert_eq!(remap.apply(Poui(100u8)), Poui(100u8));
+    assert_eq!(remap.apply(Poui(199u8)), Poui(149u8));
+}
+
+#[test]
+fn remap_grow_u16() {
+    let remap = Remap::new(Poui(1000u16), Poui(1010u16), Poui(0u16), Poui(60000u16)).unwrap();
+    assert_eq!(remap.apply(Poui(1000u16)), Poui(0u16));
+    assert_eq!(remap.apply(Poui(1005u16)), Poui(30000u16));
+    assert_eq!(remap.apply(Poui(1009u16)), Poui(54000u16));
+}
+
+#[test]
+fn remap_reversed() {
+    let remap = Remap::new(Poui(0u8), Poui(128u8), Poui(255u8), Poui(127u8)).unwrap();
+    assert_eq!(remap.apply(Poui(0u8)), Poui(255u8));
+    assert_eq!(remap.apply(Poui(64u8)), Poui(191u8));
+    let remap = Remap::new(Poui(200u8), Poui(100u8), Poui(0u8), Poui(100u8)).unwrap();
+    assert_eq!(remap.apply(Poui(150u8)), Poui(50u8));
+}
+
+#[test]
+fn remap_clamps_out_of_range() {
+    let remap = Remap::new(Poui(64u8), Poui(192u8), Poui(0u8), Poui(128u8)).unwrap();
+    assert_eq!(remap.apply(Poui(0u8)), Poui(0u8));
+    assert_eq!(remap.apply(Poui(255u8)), Poui(127u8));
+}
+
+#[test]
+fn remap_signed() {
+    let remap = Remap::new(Poui(-128i8), Poui(0i8), Poui(0i8), Poui(127i8)).unwrap();
+    assert_eq!(remap.apply(Poui(-128i8)), Poui(0i8));
+    assert_eq!(remap.apply(Poui(-64i8)), Poui(63i8));
+}
+
+#[test]
+fn remap_empty_ranges() {
+    assert_eq!(Remap::new(Poui(5u8), Poui(5u8), Poui(0u8), Poui(9u8)), None);
+    let remap = Remap::new(Poui(0u8), Poui(9u8), Poui(7u8), Poui(7u8)).unwrap();
+    assert_eq!(remap.apply(Poui(4u8)), Poui(7u8));
+}
+
+#[test]
+fn remap_u32() {
+    let x = Poui(3u32 << 30);
+    let y = x.remap(Poui(1 << 31), Poui(u32::MAX), Poui(0), Poui(1 << 31));
+    assert_eq!(y, Some(Poui(1 << 30)));
+}
@@ -0,0 +1,276 @@
+use crate::trig::turn_bits;
+use crate::Mixed;
+use crate::Poui;
+use crate::SampleInterpolation;
+use num_traits::AsPrimitive;
+use num_traits::PrimInt;
+use num_traits::Signed;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+use num_traits::WrappingSub;
+
+/// A delay line that can be read between its samples, for delay and chorus
+/// effects and for resampling.
+///
+/// The line keeps the last few samples pushed into it. Reading it at a
+/// delay, an integer number of samples plus a `Poui` fraction, interpolates
+/// between the samples either side, to 32 bits of the fraction. A delay of
+/// 0 is the latest sample, and the line holds delays up to one less than
+/// its length. Before the line has filled up, the missing samples are 0.
+///
+/// Samples can be at most 64 bits.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::FractionalDelay;
+/// use poui::Mixed;
+/// use poui::Poui;
+///
+/// let mut line = FractionalDelay::new(4);
+/// for s in [0i16, 100, 200, 300] {
+///     line.push(Poui(s));
+/// }
+/// assert_eq!(line.read(Mixed::<u32, u32>::from_int(0)), Poui(300));
+/// assert_eq!(line.read(Mixed::new(1u32, Poui(0x8000_0000u32))), Poui(150));
+/// assert_eq!(line.read(Mixed::new(2u32, Poui(0xC0u8))), Poui(25));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FractionalDelay<S: PrimInt + Signed + WrappingAdd> {
+    buffer: Vec<Poui<S>>,
+    head: usize,
+    interpolation: SampleInterpolation,
+}
+
+impl<S> FractionalDelay<S>
+where
+    S: PrimInt + Signed + WrappingAdd + Into<i64> + 'static,
+    i128: AsPrimitive<S>,
+{
+    /// A silent line of `len` samples, with linear interpolation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `len` is 0.
+    pub fn new(len: usize) -> Self {
+        assert!(len > 0, "a delay line needs samples");
+        FractionalDelay {
+            buffer: vec![Poui(S::zero()); len],
+            head: 0,
+            interpolation: SampleInterpolation::Linear,
+        }
+    }
+
+    /// Sets how to interpolate between the samples.
+    ///
+    /// Cubic interpolation needs a sample either side of the two it
+    /// interpolates between, so at the ends of the line it repeats the end
+    /// sample instead.
+    pub fn with_interpolation(self, interpolation: SampleInterpolation) -> Self {
+        FractionalDelay {
+            interpolation,
+            ..self
+        }
+    }
+
+    /// How the line interpolates between its samples.
+    pub fn interpolation(&self) -> SampleInterpolation {
+        self.interpolation
+    }
+
+    /// The number of samples the line keeps.
+    pub fn len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    /// Always false, since a line keeps at least one sample.
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Pushes the next sample into the line, dropping the oldest.
+    pub fn push(&mut self, sample: Poui<S>) {
+        self.head = (self.head + 1) % self.buffer.len();
+        self.buffer[self.head] = sample;
+    }
+
+    /// Silences the line.
+    pub fn reset(&mut self) {
+        self.buffer.fill(Poui(S::zero()));
+    }
+
+    /// The signal `delay` samples before the latest sample.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `delay` is more than one less than the length of the line.
+    pub fn read<U>(&self, delay: Mixed<u32, U>) -> Poui<S>
+    where
+        U: PrimInt + Unsigned + WrappingAdd + WrappingSub,
+    {
+        let len = self.buffer.len();
+        let index = delay.int as usize;
+        let t = (turn_bits(delay.frac.0) >> 32) as i128;
+        assert!(
+            index < len && (index + 1 < len || t == 0),
+            "delay {index} is too long for a line of {len} samples"
+        );
+        let at = |back: usize| -> i128 {
+            let back = back.min(len - 1);
+            let v: i64 = self.buffer[(self.head + len - back) % len].0.into();
+            v as i128
+        };
+        let newer = index.saturating_sub(1);
+        let value = self
+            .interpolation
+            .interpolate([at(newer), at(index), at(index + 1), at(index + 2)], t);
+        let (min, max) = (S::min_value().into(), S::max_value().into());
+        Poui(value.clamp(min as i128, max as i128).as_())
+    }
+}
+
+/// The ratio of an input sample rate to an output sample rate, for driving
+/// a [`FractionalDelay`] to convert between them.
+///
+/// The ratio is the step, in input samples, between output samples, as an
+/// unsigned 32.32 fixed-point number. For each output sample, [`tick`]
+/// says how many input samples to push into the line, and [`delay`] then
+/// says where to read the output from. The position is kept exactly, so
+/// the output never drifts from the input, and the step can be changed at
+/// any time, e.g. to bend the pitch of a sample player.
+///
+/// [`tick`]: ResampleRatio::tick
+/// [`delay`]: ResampleRatio::delay
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::FractionalDelay;
+/// use poui::Poui;
+/// use poui::ResampleRatio;
+///
+/// // From 48 kHz down to 32 kHz.
+/// let mut ratio = ResampleRatio::from_rates(48_000, 32_000);
+/// let mut line = FractionalDelay::new(4);
+/// let mut input = (0..).map(|i| Poui(i * 100i16));
+/// let output: Vec<_> = (0..5)
+///     .map(|_| {
+///         for _ in 0..ratio.tick() {
+///             line.push(input.next().unwrap());
+///         }
+///         line.read(ratio.delay())
+///     })
+///     .collect();
+/// // The output steps by 1.5 input samples.
+/// assert_eq!(output, [Poui(0), Poui(100), Poui(250), Poui(400), Poui(550)]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ResampleRatio {
+    step: Mixed<u32, u32>,
+    phase: Poui<u32>,
+}
+
+impl ResampleRatio {
+    /// A ratio stepping `step` input samples per output sample.
+    pub fn new(step: Mixed<u32, u32>) -> Self {
+        ResampleRatio {
+            step,
+            phase: Poui(0),
+        }
+    }
+
+    /// The ratio for converting from `input_rate` to `output_rate`, with the
+    /// step rounded to the nearest 32.32 fixed-point number.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `output_rate` is 0.
+    pub fn from_rates(input_rate: u32, output_rate: u32) -> Self {
+        assert!(output_rate > 0, "the output rate must be positive");
+        let (input, output) = (input_rate as u64, output_rate as u64);
+        let step = ((input << 32) + output / 2) / output;
+        ResampleRatio::new(Mixed::new((step >> 32) as u32, Poui(step as u32)))
+    }
+
+    /// The step, in input samples, between output samples.
+    pub fn step(&self) -> Mixed<u32, u32> {
+        self.step
+    }
+
+    /// Changes the step, keeping the position.
+    pub fn set_step(&mut self, step: Mixed<u32, u32>) {
+        self.step = step;
+    }
+
+    /// How far the current output sample is past the second-latest input
+    /// sample, as a fraction of an input sample.
+    pub fn phase(&self) -> Poui<u32> {
+        self.phase
+    }
+
+    /// Moves back to the start of an input sample.
+    pub fn reset(&mut self) {
+        self.phase = Poui(0);
+    }
+
+    /// Advances to the next output sample, and returns the number of input
+    /// samples to push into the line before reading it.
+    pub fn tick(&mut self) -> u32 {
+        let position = self.phase.0 as u64 + self.step.frac.0 as u64;
+        self.phase = Poui(position as u32);
+        self.step.int + (position >> 32) as u32
+    }
+
+    /// The delay at which to read the current output sample from the line,
+    /// between 0 and 1.
+    ///
+    /// Cubic interpolation is most accurate with a sample either side, so
+    /// for it, read a sample later than this.
+    pub fn delay(&self) -> Mixed<u32, u32> {
+        Mixed::from_int(1) - self.phase
+    }
+}
+
+#[test]
+fn fractional_delay_reads_between_samples() {
+    let samples: Vec<_> = (0..8i32)
+        .map(|i| Poui(i.wrapping_mul(0x1234_5678).wrapping_mul(7)))
+        .collect();
+    for interpolation in [SampleInterpolation::Linear, SampleInterpolation::Cubic] {
+        let mut line = FractionalDelay::new(8).with_interpolation(interpolation);
+        for &s in &samples {
+            line.push(s);
+        }
+        for (back, &s) in samples.iter().rev().enumerate() {
+            assert_eq!(line.read(Mixed::new(back as u32, Poui(0u16))), s);
+        }
+    }
+    let mut line = FractionalDelay::new(3);
+    line.push(Poui(-40i8));
+    assert_eq!(line.read(Mixed::new(0u32, Poui(0x40u8))), Poui(-30));
+    line.reset();
+    assert_eq!(line.read(Mixed::new(2u32, Poui(0u8))), Poui(0));
+}
+
+#[test]
+#[should_panic]
+fn fractional_delay_panics_past_its_end() {
+    FractionalDelay::<i16>::new(3).read(Mixed::new(2u32, Poui(1u16)));
+}
+
+#[test]
+fn resample_ratio_keeps_exact_position() {
+    let mut ratio = ResampleRatio::from_rates(44_100, 48_000);
+    let mut pushed = 0u64;
+    for n in 1..=48_000u64 {
+        pushed += ratio.tick() as u64;
+        // The latest input is at `pushed - 1`, and the output `delay` before
+        // it, so n steps past the input sample before the first is at
+        // `pushed + 1 - delay`.
+        let delay = ratio.delay();
+        let position = ((pushed + 1) << 32) - ((delay.int as u64) << 32) - delay.frac.0 as u64;
+        let step = ratio.step();
+        let exact = n * (((step.int as u64) << 32) + step.frac.0 as u64);
+        assert_eq!(position, exact, "{n}");
+    }
+}
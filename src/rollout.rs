@@ -0,0 +1,221 @@
+//! Gradual feature rollouts.
+//!
+//! A rollout enables a feature for a fraction of users. Each user is hashed,
+//! together with a salt naming the rollout, to a stable bucket: a
+//! `Poui<u64>`, uniformly distributed over users. The feature is enabled
+//! for the users whose bucket is below the rollout's fraction. Since the
+//! buckets never move, ramping the fraction up only ever adds users, and
+//! ramping it back down removes the most recently added first. Different
+//! salts give independent buckets, so the users in one rollout are
+//! unrelated to those in another. An [`Experiment`] splits the buckets
+//! between several variants instead. Buckets are only guaranteed to stay
+//! put for a given compiler version, as explained at [`bucket`].
+//!
+//! ```rust
+//! use poui::rollout::Rollout;
+//! use poui::Poui;
+//!
+//! let mut rollout = Rollout::new("new-checkout", Poui(0));
+//! assert!(!rollout.is_enabled("alice"));
+//!
+//! rollout.set_fraction(Poui(u64::MAX / 10));
+//! let early: Vec<_> = (0..1000).filter(|id| rollout.is_enabled(id)).collect();
+//! rollout.set_fraction(Poui(u64::MAX / 2));
+//! assert!(early.iter().all(|id| rollout.is_enabled(id)));
+//! ```
+
+use crate::hash::hash_poui;
+use crate::hash::StableHasher;
+use crate::Cdf;
+use crate::Poui;
+use crate::Simplex;
+use std::hash::Hash;
+use std::hash::Hasher;
+
+/// The bucket of `id` in the rollout with the given salt.
+///
+/// The bucket only depends on the bytes hashed for the salt and the ID, so
+/// it is the same in every process, on every run, for a given compiler
+/// version. See [`should_sample`](crate::should_sample) for why upgrading
+/// the compiler can move it.
+pub fn bucket(salt: impl Hash, id: impl Hash) -> Poui<u64> {
+    Salt::new(salt).bucket(id)
+}
+
+/// The hash of a rollout's salt.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+struct Salt(u64);
+
+impl Salt {
+    fn new(salt: impl Hash) -> Self {
+        let mut hasher = StableHasher::new();
+        salt.hash(&mut hasher);
+        Salt(hasher.finish())
+    }
+
+    fn bucket(self, id: impl Hash) -> Poui<u64> {
+        hash_poui((self, id))
+    }
+}
+
+/// A feature enabled for a fraction of the users.
+///
+/// See the [module documentation](self).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rollout {
+    salt: Salt,
+    fraction: Poui<u64>,
+}
+
+impl Rollout {
+    /// A rollout named by `salt`, enabled for the given fraction of users.
+    pub fn new(salt: impl Hash, fraction: Poui<u64>) -> Self {
+        Rollout {
+            salt: Salt::new(salt),
+            fraction,
+        }
+    }
+
+    /// The fraction of users the feature is enabled for.
+    pub fn fraction(&self) -> Poui<u64> {
+        self.fraction
+    }
+
+    /// Changes the fraction of users, keeping every user's bucket.
+    pub fn set_fraction(&mut self, fraction: Poui<u64>) {
+        self.fraction = fraction;
+    }
+
+    /// Raises the fraction of users to at least `fraction`, for ramping up
+    /// without ever accidentally ramping down.
+    pub fn ramp_to(&mut self, fraction: Poui<u64>) {
+        self.fraction = self.fraction.max(fraction);
+    }
+
+    /// The bucket of `id` in this rollout.
+    pub fn bucket(&self, id: impl Hash) -> Poui<u64> {
+        self.salt.bucket(id)
+    }
+
+    /// Whether the feature is enabled for `id`.
+    pub fn is_enabled(&self, id: impl Hash) -> bool {
+        self.bucket(id) < self.fraction
+    }
+}
+
+/// An experiment assigning each user to one of several variants, in
+/// proportion to their weights.
+///
+/// The unit interval of buckets is split into contiguous segments, one per
+/// variant in order, with lengths given by the weights, and each user gets
+/// the variant whose segment their bucket falls in. The segment boundaries
+/// are exact, as by [`Cdf::quantile`], so the variants get exactly their
+/// share of the buckets. When the weights change, only the users between
+/// the old and new boundaries move, so e.g. growing the last variant at the
+/// expense of the one before it leaves everyone else where they were.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::rollout::Experiment;
+/// use poui::Simplex;
+///
+/// // A control group and two treatments, 50/25/25.
+/// let weights = Simplex::from_weights(&[2u8, 1, 1]).unwrap();
+/// let experiment = Experiment::new("button-color", &weights);
+/// let variant = experiment.assign("alice");
+/// assert!(variant < 3);
+/// assert_eq!(experiment.assign("alice"), variant);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Experiment {
+    salt: Salt,
+    segments: Cdf<u64>,
+}
+
+impl Experiment {
+    /// An experiment named by `salt`, with the variants weighted by
+    /// `weights`.
+    pub fn new(salt: impl Hash, weights: &Simplex<u64>) -> Self {
+        Experiment {
+            salt: Salt::new(salt),
+            segments: segments(weights),
+        }
+    }
+
+    /// The number of variants.
+    pub fn variants(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Changes the weights of the variants, keeping every user's bucket.
+    pub fn set_weights(&mut self, weights: &Simplex<u64>) {
+        self.segments = segments(weights);
+    }
+
+    /// The bucket of `id` in this experiment.
+    pub fn bucket(&self, id: impl Hash) -> Poui<u64> {
+        self.salt.bucket(id)
+    }
+
+    /// The variant `id` is assigned to.
+    pub fn assign(&self, id: impl Hash) -> usize {
+        self.segments.quantile(self.bucket(id))
+    }
+}
+
+/// The segments of the variants, which a `Simplex` always has.
+fn segments(weights: &Simplex<u64>) -> Cdf<u64> {
+    Cdf::new(weights.as_slice()).expect("a simplex is a valid distribution")
+}
+
+#[test]
+fn rollout_ramps_without_reshuffling() {
+    let mut rollout = Rollout::new(7u32, Poui(0));
+    let mut enabled = 0;
+    for percent in [1u64, 5, 25, 50, 100] {
+        let previous = enabled;
+        rollout.ramp_to(Poui((u64::MAX / 100).saturating_mul(percent)));
+        enabled = (0..10_000u32).filter(|id| rollout.is_enabled(id)).count();
+        assert!(enabled >= previous);
+        assert!(
+            enabled.abs_diff(percent as usize * 100) < 250,
+            "{percent}: {enabled}"
+        );
+    }
+    rollout.ramp_to(Poui(0));
+    assert_eq!(rollout.fraction(), Poui(u64::MAX / 100 * 100));
+    assert_eq!(rollout.bucket("bob"), bucket(7u32, "bob"));
+}
+
+#[test]
+fn salts_give_independent_buckets() {
+    let a = Rollout::new("a", Poui(u64::MAX / 2));
+    let b = Rollout::new("b", Poui(u64::MAX / 2));
+    let both = (0..10_000u32)
+        .filter(|id| a.is_enabled(id) && b.is_enabled(id))
+        .count();
+    assert!(both.abs_diff(2500) < 200, "{both}");
+}
+
+#[test]
+fn experiment_moves_only_users_past_changed_boundaries() {
+    let before = Simplex::from_weights(&[5u8, 3, 2]).unwrap();
+    let after = Simplex::from_weights(&[5u8, 1, 4]).unwrap();
+    let mut experiment = Experiment::new(99u64, &before);
+    let first: Vec<_> = (0..10_000u32).map(|id| experiment.assign(id)).collect();
+    let counts = (0..3).map(|v| first.iter().filter(|&&a| a == v).count());
+    for (count, expected) in counts.zip([5000, 3000, 2000]) {
+        assert!(count.abs_diff(expected) < 250, "{count}");
+    }
+    experiment.set_weights(&after);
+    for (id, &old) in first.iter().enumerate() {
+        let new = experiment.assign(id as u32);
+        // Variant 0 keeps its segment, and 1 only shrinks.
+        assert!(new == old || (old == 1 && new == 2), "{id}");
+    }
+    let certain = Simplex::from_weights(&[0u8, 1]).unwrap();
+    experiment.set_weights(&certain);
+    assert!((0..1000u32).all(|id| experiment.assign(id) == 1));
+    assert_eq!(experiment.variants(), 2);
+}
@@ -0,0 +1,115 @@
+use crate::trig::cordic_cos_sin;
+use crate::trig::turn_bits;
+use crate::Poui;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+
+/// A 2D rotation by a fixed angle, with its sine and cosine precomputed.
+///
+/// Rotating many points by the same angle, e.g. the corners of a sprite,
+/// only needs the trigonometry once. The sine and cosine are kept in Q30, so
+/// that quarter turns rotate exactly, and each rotation costs four integer
+/// multiplications.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Poui;
+/// use poui::Rotation;
+///
+/// let quarter = Rotation::new(Poui(0x4000u16));
+/// assert_eq!(quarter.apply(100, 20), (-20, 100));
+///
+/// let eighth = Rotation::new(Poui(0x2000u16));
+/// assert_eq!(eighth.apply(1000, 0), (707, 707));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Rotation {
+    cos: i32,
+    sin: i32,
+}
+
+impl Rotation {
+    /// The rotation anticlockwise by `angle`, as a fraction of a turn.
+    pub fn new<N: PrimInt + Unsigned + WrappingAdd>(angle: Poui<N>) -> Self {
+        let (cos, sin) = cordic_cos_sin(turn_bits(angle.0));
+        let q30 = |v: i64| ((v + (1 << 31)) >> 32) as i32;
+        Rotation {
+            cos: q30(cos),
+            sin: q30(sin),
+        }
+    }
+
+    /// The rotation that undoes this one.
+    pub fn inverse(self) -> Self {
+        Rotation {
+            cos: self.cos,
+            sin: -self.sin,
+        }
+    }
+
+    /// Rotates the point `(x, y)` about the origin.
+    ///
+    /// The result is rounded to the nearest integer, and saturates if it
+    /// doesn't fit in an `i32`, which can only happen for points more than
+    /// `i32::MAX` from the origin.
+    pub fn apply(self, x: i32, y: i32) -> (i32, i32) {
+        let (x, y) = (x as i64, y as i64);
+        let (cos, sin) = (self.cos as i64, self.sin as i64);
+        let round = |v: i64| ((v + (1 << 29)) >> 30).clamp(i32::MIN as i64, i32::MAX as i64) as i32;
+        (round(x * cos - y * sin), round(x * sin + y * cos))
+    }
+}
+
+/// Rotates the point `(x, y)` anticlockwise about the origin by `angle`.
+///
+/// This is shorthand for [`Rotation::new`] followed by [`Rotation::apply`];
+/// build a `Rotation` instead to rotate several points by the same angle.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Poui;
+///
+/// assert_eq!(poui::rotate(5, -3, Poui(0x8000)), (-5, 3));
+/// assert_eq!(poui::rotate(0, 2000, Poui(0x1555)), (-1000, 1732));
+/// ```
+pub fn rotate(x: i32, y: i32, angle: Poui<u16>) -> (i32, i32) {
+    Rotation::new(angle).apply(x, y)
+}
+
+#[test]
+fn rotate_quarter_turns_exactly() {
+    for (x, y) in [(1, 0), (123456, -7890), (i32::MAX, 0), (-5, i32::MIN + 1)] {
+        assert_eq!(rotate(x, y, Poui(0)), (x, y));
+        assert_eq!(rotate(x, y, Poui(0x4000)), (-y, x));
+        assert_eq!(rotate(x, y, Poui(0x8000)), (-x, -y));
+        assert_eq!(rotate(x, y, Poui(0xC000)), (y, -x));
+    }
+}
+
+#[test]
+fn rotate_matches_float() {
+    for n in (0..=u16::MAX).step_by(251) {
+        let radians = n as f64 / 65536.0 * std::f64::consts::TAU;
+        let (x, y) = (30000.0, -12345.0);
+        let expected = (
+            x * radians.cos() - y * radians.sin(),
+            x * radians.sin() + y * radians.cos(),
+        );
+        let actual = rotate(x as i32, y as i32, Poui(n));
+        assert!((actual.0 as f64 - expected.0).abs() <= 1.0, "{n}");
+        assert!((actual.1 as f64 - expected.1).abs() <= 1.0, "{n}");
+    }
+}
+
+#[test]
+fn rotation_inverse_and_saturation() {
+    let rotation = Rotation::new(Poui(0x1234_5678u32));
+    let (x, y) = rotation.apply(1_000_000, 2_000_000);
+    let (x, y) = rotation.inverse().apply(x, y);
+    assert!((x - 1_000_000).abs() <= 1 && (y - 2_000_000).abs() <= 1);
+    let eighth = Rotation::new(Poui(0x20u8));
+    assert_eq!(eighth.apply(i32::MAX, i32::MAX).1, i32::MAX);
+}
@@ -0,0 +1,151 @@
+use crate::prng::XorShift;
+use crate::Nco;
+use crate::Poui;
+use crate::StrictWiden;
+use num_traits::AsPrimitive;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+
+/// A sample-and-hold random modulation source.
+///
+/// The output holds a random level, and picks a new one each time the phase
+/// of its [`Nco`] wraps around. With a nonzero slew, the output glides
+/// linearly from the old level to the new one over that fraction of each
+/// cycle, instead of jumping. The levels come from a small built-in
+/// generator, so they're repeatable for a given seed.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Nco;
+/// use poui::Poui;
+/// use poui::SampleHold;
+///
+/// let mut random = SampleHold::new(Nco::new(Poui(0x4000u16)));
+/// let levels: Vec<_> = random.by_ref().take(8).collect();
+/// assert!(levels[..4].iter().all(|&l| l == levels[0]));
+/// assert!(levels[4..].iter().all(|&l| l == levels[4]));
+///
+/// // Glide over the first half of each cycle.
+/// random.set_slew(Poui(0x8000));
+/// let old = random.held();
+/// random.by_ref().take(4).for_each(drop);
+/// let new = random.held();
+/// assert_eq!(random.tick(), old);
+/// assert_eq!(random.tick(), old.lerp(new, Poui(0x8000)));
+/// assert_eq!(random.tick(), new);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SampleHold<N: PrimInt + WrappingAdd> {
+    nco: Nco<N>,
+    slew: Poui<N>,
+    previous: Poui<N>,
+    held: Poui<N>,
+    rng: XorShift,
+}
+
+impl<N, M> SampleHold<N>
+where
+    N: PrimInt + Unsigned + WrappingAdd + StrictWiden<Widened = M> + 'static,
+    M: PrimInt + AsPrimitive<N>,
+{
+    /// A sample-and-hold without slew, driven by `nco`.
+    pub fn new(nco: Nco<N>) -> Self {
+        let mut rng = XorShift::new(0);
+        let held = rng.next_poui();
+        SampleHold {
+            nco,
+            slew: Poui(N::zero()),
+            previous: held,
+            held,
+            rng,
+        }
+    }
+
+    /// Reseeds the generator and picks a new level, without slewing to it.
+    pub fn set_seed(&mut self, seed: u64) {
+        self.rng = XorShift::new(seed);
+        self.held = self.rng.next_poui();
+        self.previous = self.held;
+    }
+
+    /// The fraction of each cycle spent gliding to the new level.
+    pub fn slew(&self) -> Poui<N> {
+        self.slew
+    }
+
+    /// Sets the fraction of each cycle spent gliding to the new level. Zero
+    /// jumps straight to it.
+    pub fn set_slew(&mut self, slew: Poui<N>) {
+        self.slew = slew;
+    }
+
+    /// The level picked at the start of the current cycle.
+    pub fn held(&self) -> Poui<N> {
+        self.held
+    }
+
+    /// The phase accumulator driving the sample-and-hold.
+    pub fn nco(&self) -> &Nco<N> {
+        &self.nco
+    }
+
+    /// The phase accumulator driving the sample-and-hold, e.g. to retune it.
+    pub fn nco_mut(&mut self) -> &mut Nco<N> {
+        &mut self.nco
+    }
+
+    /// Returns the output at the current phase, and advances to the next
+    /// phase.
+    pub fn tick(&mut self) -> Poui<N> {
+        let phase = self.nco.tick();
+        let value = if phase < self.slew {
+            let t = Poui::from_ratio(phase.0, self.slew.0).unwrap();
+            self.previous.lerp(self.held, t)
+        } else {
+            self.held
+        };
+        if self.nco.phase() < phase {
+            self.previous = self.held;
+            self.held = self.rng.next_poui();
+        }
+        value
+    }
+}
+
+impl<N, M> Iterator for SampleHold<N>
+where
+    N: PrimInt + Unsigned + WrappingAdd + StrictWiden<Widened = M> + 'static,
+    M: PrimInt + AsPrimitive<N>,
+{
+    type Item = Poui<N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.tick())
+    }
+}
+
+#[test]
+fn sample_hold_levels_are_spread_out() {
+    let mut random = SampleHold::new(Nco::new(Poui(u8::MAX)));
+    random.set_seed(42);
+    let mut counts = [0u32; 4];
+    for level in random.take(4000) {
+        counts[(level.0 >> 6) as usize] += 1;
+    }
+    assert!(
+        counts.iter().all(|&c| (900..1100).contains(&c)),
+        "{counts:?}"
+    );
+}
+
+#[test]
+fn sample_hold_slew_is_continuous() {
+    let mut random = SampleHold::new(Nco::new(Poui(0x0100u16)));
+    random.set_slew(Poui(u16::MAX));
+    let levels: Vec<_> = random.take(1024).collect();
+    for w in levels.windows(2) {
+        assert!(w[0].0.abs_diff(w[1].0) <= 0x0100, "{w:?}");
+    }
+}
@@ -0,0 +1,140 @@
+use crate::policy::OverflowPolicy;
+use crate::policy::Saturating;
+use crate::Poui;
+use crate::Shorten;
+use crate::Widen;
+use num_traits::Num;
+use num_traits::PrimInt;
+use num_traits::WrappingAdd;
+use num_traits::WrappingSub;
+
+/// A point on the unit interval whose arithmetic saturates instead of
+/// wrapping.
+///
+/// `Poui` arithmetic wraps around the ends of the interval, which is exactly
+/// right for angles and phases, but wrong for probabilities and gains, where
+/// `0.75 + 0.5` should be as close to 1 as possible rather than `0.25`.
+/// `SatPoui` encodes the saturating policy in the type, so that it doesn't
+/// have to be remembered at every call site.
+///
+/// `SatPoui` converts to and from `Poui` for free, so it is easy to switch
+/// policies for part of a computation. Its operators use the
+/// [`Saturating`] policy.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Poui;
+/// use poui::SatPoui;
+///
+/// let a = SatPoui(192u8);
+/// let b = SatPoui(128u8);
+/// assert_eq!(a + b, SatPoui(255u8));
+/// assert_eq!(b - a, SatPoui(0u8));
+/// assert_eq!(Poui::from(a * b), Poui(96u8));
+/// ```
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+#[repr(transparent)]
+pub struct SatPoui<N: Num + WrappingAdd>(pub N);
+
+impl<N: Num + WrappingAdd> From<Poui<N>> for SatPoui<N> {
+    fn from(p: Poui<N>) -> Self {
+        SatPoui(p.0)
+    }
+}
+
+impl<N: Num + WrappingAdd> From<SatPoui<N>> for Poui<N> {
+    fn from(p: SatPoui<N>) -> Self {
+        Poui(p.0)
+    }
+}
+
+impl<N: PrimInt + WrappingAdd + WrappingSub> std::ops::Add for SatPoui<N> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Saturating::add(Poui(self.0), Poui(rhs.0)).into()
+    }
+}
+
+impl<N: PrimInt + WrappingAdd + WrappingSub> std::ops::Sub for SatPoui<N> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Saturating::sub(Poui(self.0), Poui(rhs.0)).into()
+    }
+}
+
+/// Multiplication of `SatPoui` values.
+///
+/// This is the same as `Poui` multiplication, except that the one product that
+/// doesn't fit, `-1 * -1` for signed backings, saturates to the largest value
+/// instead of wrapping around to `-1`.
+impl<N, M> std::ops::Mul for SatPoui<N>
+where
+    N: PrimInt + WrappingAdd + Widen<Widened = M>,
+    M: PrimInt + WrappingAdd + Shorten<Shortened = N>,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        Saturating::mul(Poui(self.0), Poui(rhs.0)).into()
+    }
+}
+
+impl<N: PrimInt + WrappingAdd + WrappingSub> std::ops::AddAssign for SatPoui<N> {
+    fn add_assign(&mut self, rhs: Self) {
+        *self = *self + rhs;
+    }
+}
+
+impl<N: PrimInt + WrappingAdd + WrappingSub> std::ops::SubAssign for SatPoui<N> {
+    fn sub_assign(&mut self, rhs: Self) {
+        *self = *self - rhs;
+    }
+}
+
+#[test]
+fn saturating_add_unsigned() {
+    assert_eq!(SatPoui(100u8) + SatPoui(100u8), SatPoui(200u8));
+    assert_eq!(SatPoui(200u8) + SatPoui(100u8), SatPoui(u8::MAX));
+    assert_eq!(SatPoui(u64::MAX) + SatPoui(1u64), SatPoui(u64::MAX));
+}
+
+#[test]
+fn saturating_sub_unsigned() {
+    assert_eq!(SatPoui(100u16) - SatPoui(40u16), SatPoui(60u16));
+    assert_eq!(SatPoui(40u16) - SatPoui(100u16), SatPoui(0u16));
+}
+
+#[test]
+fn saturating_signed() {
+    assert_eq!(SatPoui(100i8) + SatPoui(100i8), SatPoui(i8::MAX));
+    assert_eq!(SatPoui(-100i8) + SatPoui(-100i8), SatPoui(i8::MIN));
+    assert_eq!(SatPoui(-100i8) - SatPoui(100i8), SatPoui(i8::MIN));
+    assert_eq!(SatPoui(-100i8) + SatPoui(50i8), SatPoui(-50i8));
+}
+
+#[test]
+fn saturating_mul() {
+    assert_eq!(SatPoui(128u8) * SatPoui(128u8), SatPoui(64u8));
+    assert_eq!(SatPoui(64i8) * SatPoui(-64i8), SatPoui(-32i8));
+    assert_eq!(SatPoui(i8::MIN) * SatPoui(i8::MIN), SatPoui(i8::MAX));
+    assert_eq!(SatPoui(i32::MIN) * SatPoui(i32::MAX), SatPoui(-i32::MAX));
+}
+
+#[test]
+fn saturating_assign_ops() {
+    let mut gain = SatPoui(200u8);
+    gain += SatPoui(100u8);
+    assert_eq!(gain, SatPoui(255u8));
+    gain -= SatPoui(5u8);
+    assert_eq!(gain, SatPoui(250u8));
+}
+
+#[test]
+fn saturating_converts_to_and_from_poui() {
+    let p = Poui(42u32);
+    assert_eq!(SatPoui::from(p), SatPoui(42u32));
+    assert_eq!(Poui::from(SatPoui(42u32)), p);
+}
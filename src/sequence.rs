@@ -0,0 +1,511 @@
+use crate::frac_bits;
+use crate::probability::ratio;
+use crate::Poui;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+use std::marker::PhantomData;
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Poui<N> {
+    /// The `i`th point of the van der Corput sequence in base 2.
+    ///
+    /// The point is the binary fraction whose digits are those of `i`
+    /// reversed, so it is just the bits of `i` reversed, with no rounding at
+    /// all for the first `2^bits` points. Any `2^k` consecutive points, from
+    /// a multiple of `2^k`, fall one in each interval of length `2^-k`.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui::<u8>::van_der_corput(0), Poui(0));
+    /// assert_eq!(Poui::<u8>::van_der_corput(1), Poui(128));
+    /// assert_eq!(Poui::<u8>::van_der_corput(2), Poui(64));
+    /// assert_eq!(Poui::<u8>::van_der_corput(3), Poui(192));
+    /// assert_eq!(Poui::<u8>::van_der_corput(0b110), Poui(0b0110_0000));
+    /// ```
+    pub fn van_der_corput(i: u64) -> Self {
+        let bits = frac_bits::<N>();
+        let reversed = i.reverse_bits() as u128;
+        let raw = if bits <= 64 {
+            reversed >> (64 - bits)
+        } else {
+            reversed << (bits - 64)
+        };
+        Poui(N::from(raw).unwrap())
+    }
+}
+
+/// The van der Corput sequence in base 2, as an iterator.
+///
+/// See [`Poui::van_der_corput`]. Each point splits the largest remaining gap
+/// between the points before it, which makes the sequence a good order in
+/// which to take stratified samples or dither thresholds, since every
+/// prefix is about as evenly spread as it can be.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Poui;
+/// use poui::VanDerCorput;
+///
+/// let points: Vec<Poui<u8>> = VanDerCorput::new().take(4).collect();
+/// assert_eq!(points, [Poui(0), Poui(128), Poui(64), Poui(192)]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct VanDerCorput<N: PrimInt + Unsigned + WrappingAdd> {
+    index: u64,
+    points: PhantomData<N>,
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> VanDerCorput<N> {
+    /// The sequence from its first point, 0.
+    pub fn new() -> Self {
+        Self::starting_at(0)
+    }
+
+    /// The sequence from its `index`th point.
+    pub fn starting_at(index: u64) -> Self {
+        VanDerCorput {
+            index,
+            points: PhantomData,
+        }
+    }
+
+    /// The index of the next point.
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Default for VanDerCorput<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Iterator for VanDerCorput<N> {
+    type Item = Poui<N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let point = Poui::van_der_corput(self.index);
+        self.index = self.index.wrapping_add(1);
+        Some(point)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.index = self.index.wrapping_add(n as u64);
+        self.next()
+    }
+}
+
+/// The radical inverse of `i` in `base`: the fraction whose digits in that
+/// base are those of `i` reversed, rounded to the nearest `Poui`.
+fn radical_inverse<N: PrimInt + Unsigned + WrappingAdd>(mut i: u64, base: u32) -> Poui<N> {
+    if base == 2 {
+        return Poui::van_der_corput(i);
+    }
+    let base = base as u128;
+    let (mut reversed, mut den) = (0u128, 1u128);
+    while i != 0 {
+        reversed = reversed * base + (i as u128 % base);
+        den *= base;
+        i /= base as u64;
+    }
+    ratio(reversed, den)
+}
+
+/// The Halton sequence in one dimension: the van der Corput sequence in an
+/// arbitrary base.
+///
+/// Each point is the radical inverse of its index, computed exactly as an
+/// integer numerator over a power of the base, then rounded once. Any
+/// `base^k` consecutive points, from a multiple of `base^k`, fall one in
+/// each interval of length `base^-k`. In base 2 the points are exactly
+/// those of [`VanDerCorput`].
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Halton;
+/// use poui::Poui;
+///
+/// let points: Vec<Poui<u16>> = Halton::new(3).take(4).collect();
+/// // 0, 1/3, 2/3, and 1/9.
+/// assert_eq!(points, [Poui(0), Poui(21845), Poui(43691), Poui(7282)]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Halton<N: PrimInt + Unsigned + WrappingAdd> {
+    base: u32,
+    index: u64,
+    points: PhantomData<N>,
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Halton<N> {
+    /// The sequence in `base` from its first point, 0.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `base` is less than 2.
+    pub fn new(base: u32) -> Self {
+        Self::starting_at(base, 0)
+    }
+
+    /// The sequence in `base` from its `index`th point.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `base` is less than 2.
+    pub fn starting_at(base: u32, index: u64) -> Self {
+        assert!(base >= 2, "a Halton sequence needs a base of at least 2");
+        Halton {
+            base,
+            index,
+            points: PhantomData,
+        }
+    }
+
+    /// The base of the sequence.
+    pub fn base(&self) -> u32 {
+        self.base
+    }
+
+    /// The index of the next point.
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Iterator for Halton<N> {
+    type Item = Poui<N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let point = radical_inverse(self.index, self.base);
+        self.index = self.index.wrapping_add(1);
+        Some(point)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.index = self.index.wrapping_add(n as u64);
+        self.next()
+    }
+}
+
+/// The Halton sequence in `D` dimensions, as an iterator of points in the
+/// unit hypercube.
+///
+/// Coordinate `k` of each point is the radical inverse of the index in the
+/// `k`th base, as by [`Halton`]. The bases should be pairwise coprime, and
+/// are usually the first `D` primes, which [`HaltonPoints::new`] uses.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::HaltonPoints;
+/// use poui::Poui;
+///
+/// let mut points = HaltonPoints::<u8, 2>::new();
+/// assert_eq!(points.nth(1), Some([Poui(128), Poui(85)]));
+/// assert_eq!(points.next(), Some([Poui(64), Poui(171)]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HaltonPoints<N: PrimInt + Unsigned + WrappingAdd, const D: usize> {
+    bases: [u32; D],
+    index: u64,
+    points: PhantomData<N>,
+}
+
+/// The bases of [`HaltonPoints::new`].
+const PRIMES: [u32; 16] = [2, 3, 5, 7, 11, 13, 17, 19, 23, 29, 31, 37, 41, 43, 47, 53];
+
+impl<N: PrimInt + Unsigned + WrappingAdd, const D: usize> HaltonPoints<N, D> {
+    /// The sequence with the first `D` primes as its bases.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `D` is more than 16. Beyond a handful of dimensions the
+    /// coordinates in large bases are strongly correlated, and a scrambled
+    /// or Sobol sequence is a better choice.
+    pub fn new() -> Self {
+        assert!(D <= PRIMES.len(), "no more than 16 default bases");
+        Self::with_bases(std::array::from_fn(|k| PRIMES[k]))
+    }
+
+    /// The sequence with the given bases.
+    ///
+    /// # Panics
+    ///
+    /// Panics if any base is less than 2.
+    pub fn with_bases(bases: [u32; D]) -> Self {
+        assert!(
+            bases.iter().all(|&b| b >= 2),
+            "a Halton sequence needs bases of at least 2"
+        );
+        HaltonPoints {
+            bases,
+            index: 0,
+            points: PhantomData,
+        }
+    }
+
+    /// The bases of the coordinates.
+    pub fn bases(&self) -> [u32; D] {
+        self.bases
+    }
+
+    /// The index of the next point.
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd, const D: usize> Default for HaltonPoints<N, D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd, const D: usize> Iterator for HaltonPoints<N, D> {
+    type Item = [Poui<N>; D];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let point = self.bases.map(|base| radical_inverse(self.index, base));
+        self.index = self.index.wrapping_add(1);
+        Some(point)
+    }
+
+    fn nth(&mut self, n: usize) -> Option<Self::Item> {
+        self.index = self.index.wrapping_add(n as u64);
+        self.next()
+    }
+}
+
+/// `1 / φ`, the fractional part of the golden ratio, in Q128.
+const GOLDEN: u128 = 0x9E37_79B9_7F4A_7C15_F39C_C060_5CED_C834;
+
+/// `1 / ρ` and `1 / ρ²`, for the plastic number `ρ`, in Q128.
+const PLASTIC: [u128; 2] = [
+    0xC13F_A9A9_02A6_328F_434F_F71B_2D97_724B,
+    0x91E1_0DA5_C79E_7B1C_D438_A0A8_E6C9_C0FC,
+];
+
+/// An irrational step truncated to a `Poui`, rounded to the nearest odd
+/// value, so that adding it repeatedly visits every `Poui` before repeating.
+fn odd_step<N: PrimInt + Unsigned + WrappingAdd>(step: u128) -> Poui<N> {
+    Poui(N::from(step >> (128 - frac_bits::<N>()) | 1).unwrap())
+}
+
+/// The additive recurrence with the golden ratio, whose points are spread
+/// as evenly as those of any sequence of this kind.
+///
+/// Each point is the previous one plus `1 / φ`, wrapping around the unit
+/// interval, which `Poui` addition does for free. Every new point falls in
+/// one of the largest gaps between those before it, splitting it close to
+/// the golden ratio, so any prefix of the sequence is well spread. This is
+/// a classic for assigning distinct hues, and for jitter.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::GoldenRatioSeq;
+/// use poui::Poui;
+///
+/// let points: Vec<Poui<u8>> = GoldenRatioSeq::new().take(4).collect();
+/// assert_eq!(points, [Poui(0), Poui(159), Poui(62), Poui(221)]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct GoldenRatioSeq<N: PrimInt + Unsigned + WrappingAdd> {
+    point: Poui<N>,
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> GoldenRatioSeq<N> {
+    /// The sequence starting at 0.
+    pub fn new() -> Self {
+        Self::starting_at(Poui(N::zero()))
+    }
+
+    /// The sequence starting at `start`, i.e. offset by `start`.
+    pub fn starting_at(start: Poui<N>) -> Self {
+        GoldenRatioSeq { point: start }
+    }
+
+    /// The step between points, `1 / φ` rounded to an odd `Poui`, so that
+    /// the sequence visits every `Poui` once before repeating.
+    pub fn step() -> Poui<N> {
+        odd_step(GOLDEN)
+    }
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Default for GoldenRatioSeq<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Iterator for GoldenRatioSeq<N> {
+    type Item = Poui<N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let point = self.point;
+        self.point = self.point + Self::step();
+        Some(point)
+    }
+}
+
+/// Martin Roberts' R2 sequence, the two-dimensional analogue of
+/// [`GoldenRatioSeq`].
+///
+/// Each point is the previous one plus `(1 / ρ, 1 / ρ²)` for the plastic
+/// number `ρ`, the root of `x³ = x + 1`, wrapping around the unit square.
+/// The points are spread more evenly than a Halton sequence's, and only
+/// cost two additions each, which makes them handy for spawn positions and
+/// two-dimensional jitter.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Poui;
+/// use poui::R2Seq;
+///
+/// let mut points = R2Seq::<u16>::new();
+/// assert_eq!(points.next(), Some([Poui(0), Poui(0)]));
+/// assert_eq!(points.next(), Some([Poui(0xC13F), Poui(0x91E1)]));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct R2Seq<N: PrimInt + Unsigned + WrappingAdd> {
+    point: [Poui<N>; 2],
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> R2Seq<N> {
+    /// The sequence starting at the origin.
+    pub fn new() -> Self {
+        Self::starting_at([Poui(N::zero()); 2])
+    }
+
+    /// The sequence starting at `start`, i.e. offset by `start`.
+    pub fn starting_at(start: [Poui<N>; 2]) -> Self {
+        R2Seq { point: start }
+    }
+
+    /// The step between points, `(1 / ρ, 1 / ρ²)` with each coordinate
+    /// rounded to an odd `Poui`.
+    pub fn step() -> [Poui<N>; 2] {
+        PLASTIC.map(odd_step)
+    }
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Default for R2Seq<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Iterator for R2Seq<N> {
+    type Item = [Poui<N>; 2];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let point = self.point;
+        let [dx, dy] = Self::step();
+        self.point = [point[0] + dx, point[1] + dy];
+        Some(point)
+    }
+}
+
+#[test]
+fn van_der_corput_stratifies_every_block() {
+    let points: Vec<Poui<u16>> = VanDerCorput::new().take(1 << 16).collect();
+    let mut sorted = points.clone();
+    sorted.sort();
+    assert!(sorted.iter().enumerate().all(|(i, p)| p.0 as usize == i));
+    // Every aligned block of 8 points has one point in each eighth.
+    for block in points.chunks(8) {
+        let mut eighths: Vec<_> = block.iter().map(|p| p.0 >> 13).collect();
+        eighths.sort();
+        assert_eq!(eighths, [0, 1, 2, 3, 4, 5, 6, 7]);
+    }
+}
+
+#[test]
+fn van_der_corput_wide_and_narrow_backings() {
+    assert_eq!(Poui::<u128>::van_der_corput(1), Poui(1 << 127));
+    assert_eq!(Poui::<u64>::van_der_corput(u64::MAX), Poui(u64::MAX));
+    // Bits beyond the precision of the backing are dropped.
+    assert_eq!(Poui::<u8>::van_der_corput(256), Poui(0));
+    let mut sequence = VanDerCorput::<u32>::starting_at(5);
+    assert_eq!(sequence.nth(1), Some(Poui::van_der_corput(6)));
+    assert_eq!(sequence.index(), 7);
+}
+
+#[test]
+fn halton_points_match_radical_inverses() {
+    for (i, p) in Halton::<u32>::new(5).take(500).enumerate() {
+        let (mut i, mut expected, mut scale) = (i, 0.0f64, 1.0 / 5.0);
+        while i != 0 {
+            expected += (i % 5) as f64 * scale;
+            i /= 5;
+            scale /= 5.0;
+        }
+        assert!((p.0 as f64 - expected * 4294967296.0).abs() <= 0.5, "{p:?}");
+    }
+    // Each block of 9 points has one in each ninth. The first block lies
+    // on the boundaries, where rounding could go either way.
+    let points: Vec<Poui<u16>> = Halton::new(3).take(81).collect();
+    for block in points.chunks(9).skip(1) {
+        let mut ninths: Vec<_> = block.iter().map(|p| (p.0 as u32 * 9) >> 16).collect();
+        ninths.sort();
+        assert_eq!(ninths, (0..9).collect::<Vec<_>>());
+    }
+    assert!(Halton::<u8>::new(2)
+        .take(300)
+        .eq(VanDerCorput::new().take(300)));
+}
+
+#[test]
+fn halton_points_are_coordinatewise_sequences() {
+    let points: Vec<[Poui<u64>; 3]> = HaltonPoints::new().take(100).collect();
+    for (k, base) in [2, 3, 5].into_iter().enumerate() {
+        let coordinates: Vec<_> = points.iter().map(|p| p[k]).collect();
+        assert_eq!(coordinates, Halton::new(base).take(100).collect::<Vec<_>>());
+    }
+    // The largest index still rounds below 1.
+    assert!(Halton::<u8>::starting_at(7, u64::MAX).next().is_some());
+}
+
+#[test]
+#[should_panic]
+fn halton_rejects_base_one() {
+    HaltonPoints::<u8, 2>::with_bases([2, 1]);
+}
+
+#[test]
+fn golden_ratio_sequence_has_three_gaps() {
+    // By the three-distance theorem, the gaps between the first `n` points
+    // take at most three distinct lengths.
+    let mut points: Vec<Poui<u32>> = GoldenRatioSeq::new().take(1000).collect();
+    points.sort();
+    let mut gaps: Vec<_> = points.windows(2).map(|w| w[1].0 - w[0].0).collect();
+    gaps.push(points[0].0.wrapping_sub(points[999].0));
+    gaps.sort();
+    gaps.dedup();
+    assert!(gaps.len() <= 3, "{gaps:?}");
+    // An odd step visits every point of a narrow backing.
+    let mut all: Vec<Poui<u8>> = GoldenRatioSeq::starting_at(Poui(7)).take(256).collect();
+    all.sort();
+    all.dedup();
+    assert_eq!(all.len(), 256);
+    assert_eq!(GoldenRatioSeq::<u64>::step(), Poui(0x9E37_79B9_7F4A_7C15));
+}
+
+#[test]
+fn r2_sequence_fills_the_square() {
+    let points: Vec<[Poui<u16>; 2]> = R2Seq::new().take(1024).collect();
+    let mut cells = [0u32; 64];
+    for [x, y] in &points {
+        cells[(x.0 >> 13) as usize * 8 + (y.0 >> 13) as usize] += 1;
+    }
+    // 16 points per cell on average, and none far off.
+    assert!(cells.iter().all(|&c| (12..=20).contains(&c)), "{cells:?}");
+    let offset = R2Seq::starting_at([Poui(1u16), Poui(2)]).nth(1).unwrap();
+    assert_eq!(offset, [points[1][0] + Poui(1), points[1][1] + Poui(2)]);
+}
@@ -0,0 +1,119 @@
+use crate::frac_bits;
+use crate::Poui;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+
+/// A first-order sigma-delta modulator, which turns a precise duty into a
+/// stream of coarser levels that average out to exactly that duty.
+///
+/// PWM peripherals often have only 8 or 10 bits of resolution, which makes
+/// slow LED fades visibly step, and at the bottom of the range flicker
+/// between neighboring levels. Modulating the duty instead gets the missing
+/// bits back over time: each tick outputs the top `bits` bits of the duty,
+/// and the rest accumulate, wrapping, into an accumulator whose carry adds
+/// one more level. Over any `2^(BITS - bits)` consecutive ticks the levels
+/// add up to exactly the duty, and the error at any moment is below one
+/// level.
+///
+/// Levels are in units of `2^-bits`, from 0 to `2^bits`, the last being
+/// fully on, as PWM peripherals with a period of `2^bits` ticks usually
+/// allow. With 0 bits the output is a stream of single bits, for driving a
+/// pin directly.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Poui;
+/// use poui::SigmaDelta;
+///
+/// // A quarter duty as a 1-bit stream.
+/// let mut modulator = SigmaDelta::new(0);
+/// let bits: Vec<_> = (0..8).map(|_| modulator.tick(Poui(0x4000u16))).collect();
+/// assert_eq!(bits, [0, 0, 0, 1, 0, 0, 0, 1]);
+///
+/// // A 16-bit duty on an 8-bit PWM, between levels 0x12 and 0x13.
+/// let mut modulator = SigmaDelta::new(8);
+/// let levels: u16 = (0..256).map(|_| modulator.tick(Poui(0x1280u16))).sum();
+/// assert_eq!(levels, 0x1280);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct SigmaDelta<N: PrimInt + Unsigned + WrappingAdd> {
+    accumulator: N,
+    bits: u32,
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> SigmaDelta<N> {
+    /// A modulator whose output levels have `bits` bits of resolution.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `bits` is not less than the bits of the duty.
+    pub fn new(bits: u32) -> Self {
+        assert!(
+            (bits as usize) < frac_bits::<N>(),
+            "{bits} output bits leave nothing to modulate"
+        );
+        SigmaDelta {
+            accumulator: N::zero(),
+            bits,
+        }
+    }
+
+    /// The bits of resolution of the output levels.
+    pub fn bits(&self) -> u32 {
+        self.bits
+    }
+
+    /// Empties the accumulator.
+    pub fn reset(&mut self) {
+        self.accumulator = N::zero();
+    }
+
+    /// Outputs the next level for `duty`, between 0 and `2^bits`.
+    pub fn tick(&mut self, duty: Poui<N>) -> N {
+        let shift = frac_bits::<N>() - self.bits as usize;
+        let mask = N::max_value() >> self.bits as usize;
+        let level = if self.bits == 0 {
+            N::zero()
+        } else {
+            duty.0 >> shift
+        };
+        // With no output bits the sum can wrap; otherwise it can only pass
+        // the mask. Either way, that is the carry.
+        let sum = self.accumulator.wrapping_add(&(duty.0 & mask));
+        let carry = sum < self.accumulator || sum > mask;
+        self.accumulator = sum & mask;
+        if carry {
+            level + N::one()
+        } else {
+            level
+        }
+    }
+}
+
+#[test]
+fn sigma_delta_averages_to_the_duty() {
+    for bits in [0, 1, 4, 7] {
+        for duty in [0u8, 1, 0x55, 0x80, 0xFE, 0xFF] {
+            let mut modulator = SigmaDelta::new(bits);
+            let period = 1u32 << (8 - bits);
+            let mut total = 0u32;
+            for i in 1..=period * 3 {
+                let level = modulator.tick(Poui(duty)) as u32;
+                assert!(level <= 1 << bits);
+                total += level;
+                // The running error stays below one level.
+                let exact = duty as u32 * i;
+                assert!(exact - (total << (8 - bits)) < period, "{bits} {duty} {i}");
+            }
+            assert_eq!(total << (8 - bits), duty as u32 * period * 3);
+        }
+    }
+}
+
+#[test]
+#[should_panic]
+fn sigma_delta_needs_bits_to_modulate() {
+    SigmaDelta::<u16>::new(16);
+}
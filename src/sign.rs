@@ -0,0 +1,270 @@
+use crate::Poui;
+use num_traits::PrimInt;
+use num_traits::WrappingAdd;
+use num_traits::WrappingSub;
+
+/// Unsigned integer types with a signed counterpart of the same width.
+///
+/// The conversion reinterprets the bits, like an `as` cast.
+pub trait ToSigned {
+    type Signed;
+    fn to_signed(self) -> Self::Signed;
+}
+
+impl ToSigned for u8 {
+    type Signed = i8;
+    fn to_signed(self) -> Self::Signed {
+        self as i8
+    }
+}
+
+impl ToSigned for u16 {
+    type Signed = i16;
+    fn to_signed(self) -> Self::Signed {
+        self as i16
+    }
+}
+
+impl ToSigned for u32 {
+    type Signed = i32;
+    fn to_signed(self) -> Self::Signed {
+        self as i32
+    }
+}
+
+impl ToSigned for u64 {
+    type Signed = i64;
+    fn to_signed(self) -> Self::Signed {
+        self as i64
+    }
+}
+
+impl ToSigned for u128 {
+    type Signed = i128;
+    fn to_signed(self) -> Self::Signed {
+        self as i128
+    }
+}
+
+/// Signed integer types with an unsigned counterpart of the same width.
+///
+/// The conversion reinterprets the bits, like an `as` cast.
+pub trait ToUnsigned {
+    type Unsigned;
+    fn to_unsigned(self) -> Self::Unsigned;
+}
+
+impl ToUnsigned for i8 {
+    type Unsigned = u8;
+    fn to_unsigned(self) -> Self::Unsigned {
+        self as u8
+    }
+}
+
+impl ToUnsigned for i16 {
+    type Unsigned = u16;
+    fn to_unsigned(self) -> Self::Unsigned {
+        self as u16
+    }
+}
+
+impl ToUnsigned for i32 {
+    type Unsigned = u32;
+    fn to_unsigned(self) -> Self::Unsigned {
+        self as u32
+    }
+}
+
+impl ToUnsigned for i64 {
+    type Unsigned = u64;
+    fn to_unsigned(self) -> Self::Unsigned {
+        self as u64
+    }
+}
+
+impl ToUnsigned for i128 {
+    type Unsigned = u128;
+    fn to_unsigned(self) -> Self::Unsigned {
+        self as u128
+    }
+}
+
+impl<U, I> Poui<U>
+where
+    U: PrimInt + WrappingAdd + ToSigned<Signed = I>,
+    I: PrimInt + WrappingAdd,
+{
+    /// Maps `self` from [0, 1) onto the signed interval [-1, 1), as
+    /// `2 * self - 1`.
+    ///
+    /// This is the *offset-binary* view of the bits: flipping the most
+    /// significant bit turns an unsigned value into a signed one, so zero maps
+    /// to -1, one half maps to zero, and the largest unsigned value maps to
+    /// the largest signed value. The mapping is exact, preserves ordering, and
+    /// is undone by [`Poui::to_unsigned`]. It is the usual conversion between
+    /// unipolar and bipolar signals, e.g. turning an LFO output in [0, 1) into
+    /// a modulation amount in [-1, 1).
+    ///
+    /// Note that this is *not* the same as reinterpreting the bits with
+    /// [`Poui::reinterpret_signed`], which keeps the point on the circle fixed
+    /// instead.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui(0u8).to_signed(), Poui(-128i8));
+    /// assert_eq!(Poui(128u8).to_signed(), Poui(0i8));
+    /// assert_eq!(Poui(192u8).to_signed(), Poui(64i8));
+    /// assert_eq!(Poui(255u8).to_signed(), Poui(127i8));
+    /// ```
+    pub fn to_signed(self) -> Poui<I> {
+        Poui(self.0.to_signed() ^ I::min_value())
+    }
+
+    /// Reinterprets the bits of `self` as a signed `Poui`.
+    ///
+    /// Viewing a `Poui` as a fraction of a turn, the unsigned value `x` and
+    /// the signed value `x - 1` describe the same point on the circle. This
+    /// conversion keeps that point fixed: angles in `[0, 0.5)` turns are
+    /// unchanged, and angles in `[0.5, 1)` turns become negative. The signed
+    /// result counts half-turns, i.e. a signed `Poui` of `-0.5` is a quarter
+    /// turn backwards.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui(64u8).reinterpret_signed(), Poui(64i8));
+    /// assert_eq!(Poui(192u8).reinterpret_signed(), Poui(-64i8));
+    /// ```
+    pub fn reinterpret_signed(self) -> Poui<I> {
+        Poui(self.0.to_signed())
+    }
+
+    /// The shortest signed rotation that takes `self` to `target`.
+    ///
+    /// Viewing both points as fractions of a turn, this is the rotation in
+    /// [-0.5, 0.5) turns that, added to `self`, gives `target`: positive
+    /// means turn forwards, negative means turn backwards. When the two
+    /// points are exactly opposite, the rotation is half a turn backwards.
+    ///
+    /// As with [`Poui::reinterpret_signed`], the result counts half-turns, so
+    /// that every rotation is represented exactly: a result of `Poui(-64i8)`,
+    /// i.e. -0.5, is a quarter turn backwards. Use
+    /// [`Poui::reinterpret_unsigned`] to add it back onto an angle.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui(10u8).signed_delta(Poui(30u8)), Poui(20i8));
+    /// assert_eq!(Poui(250u8).signed_delta(Poui(4u8)), Poui(10i8));
+    /// assert_eq!(Poui(4u8).signed_delta(Poui(250u8)), Poui(-10i8));
+    /// assert_eq!(Poui(0u8).signed_delta(Poui(128u8)), Poui(-128i8));
+    ///
+    /// let heading = Poui(200u8);
+    /// let delta = heading.signed_delta(Poui(64u8));
+    /// assert_eq!(heading + delta.reinterpret_unsigned(), Poui(64u8));
+    /// ```
+    pub fn signed_delta(self, target: Self) -> Poui<I>
+    where
+        U: WrappingSub,
+    {
+        Poui(target.0.wrapping_sub(&self.0).to_signed())
+    }
+}
+
+impl<I, U> Poui<I>
+where
+    I: PrimInt + WrappingAdd + ToUnsigned<Unsigned = U>,
+    U: PrimInt + WrappingAdd,
+{
+    /// Maps `self` from [-1, 1) onto the unsigned interval [0, 1), as
+    /// `(self + 1) / 2`.
+    ///
+    /// This is the inverse of [`Poui::to_signed`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui(-128i8).to_unsigned(), Poui(0u8));
+    /// assert_eq!(Poui(0i8).to_unsigned(), Poui(128u8));
+    /// assert_eq!(Poui(127i8).to_unsigned(), Poui(255u8));
+    /// ```
+    pub fn to_unsigned(self) -> Poui<U> {
+        Poui((self.0 ^ I::min_value()).to_unsigned())
+    }
+
+    /// Reinterprets the bits of `self` as an unsigned `Poui`.
+    ///
+    /// This is the inverse of [`Poui::reinterpret_signed`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui(-64i8).reinterpret_unsigned(), Poui(192u8));
+    /// ```
+    pub fn reinterpret_unsigned(self) -> Poui<U> {
+        Poui(self.0.to_unsigned())
+    }
+}
+
+#[test]
+fn to_signed_offset_binary() {
+    assert_eq!(Poui(0u16).to_signed(), Poui(i16::MIN));
+    assert_eq!(Poui(32768u16).to_signed(), Poui(0i16));
+    assert_eq!(Poui(u32::MAX).to_signed(), Poui(i32::MAX));
+    assert_eq!(Poui(1u64 << 62).to_signed(), Poui(-(1i64 << 62)));
+    assert_eq!(Poui(3u128 << 126).to_signed(), Poui(1i128 << 126));
+}
+
+#[test]
+fn to_signed_round_trip() {
+    for x in 0..=255u8 {
+        assert_eq!(Poui(x).to_signed().to_unsigned(), Poui(x));
+        assert_eq!(Poui(x).reinterpret_signed().reinterpret_unsigned(), Poui(x));
+    }
+}
+
+#[test]
+fn to_signed_preserves_order() {
+    for x in 0..255u8 {
+        assert!(Poui(x).to_signed() < Poui(x + 1).to_signed());
+    }
+}
+
+#[test]
+fn to_signed_doubles_distances() {
+    let a: f64 = num_traits::AsPrimitive::as_(Poui(0x3000u16));
+    let b: f64 = num_traits::AsPrimitive::as_(Poui(0x3000u16).to_signed());
+    assert_eq!(b, 2.0 * a - 1.0);
+}
+
+#[test]
+fn reinterpret_keeps_angle() {
+    assert_eq!(Poui(0u8).reinterpret_signed(), Poui(0i8));
+    assert_eq!(Poui(128u8).reinterpret_signed(), Poui(-128i8));
+    assert_eq!(Poui(255u8).reinterpret_signed(), Poui(-1i8));
+    assert_eq!(Poui(-1i32).reinterpret_unsigned(), Poui(u32::MAX));
+}
+
+#[test]
+fn signed_delta_is_shortest() {
+    for a in 0..=255u8 {
+        for b in 0..=255u8 {
+            let delta = Poui(a).signed_delta(Poui(b)).0;
+            assert_eq!(a.wrapping_add(delta as u8), b);
+            assert!((delta as i16).abs() <= 128);
+        }
+    }
+    assert_eq!(Poui(0u64).signed_delta(Poui(u64::MAX)), Poui(-1i64));
+    assert_eq!(Poui(u128::MAX).signed_delta(Poui(0)), Poui(1i128));
+}
@@ -0,0 +1,228 @@
+use crate::frac_bits;
+use crate::probability::div_scaled;
+use crate::Poui;
+use crate::StrictWiden;
+use num_traits::AsPrimitive;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+
+/// A discrete probability distribution, as probabilities that sum to one.
+///
+/// The sum is exact: the raw values always add up to exactly `2^bits`. A
+/// `Poui` can't represent 1 itself, so when a single outcome is certain it is
+/// one ULP short, and that is the only exception. Every constructor
+/// normalizes its input by the largest remainder method, which rounds each
+/// probability down, then hands the ULPs still missing from the sum to the
+/// probabilities that lost the most in rounding, so no probability is ever
+/// off by a whole ULP or more.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Poui;
+/// use poui::Simplex;
+///
+/// let thirds = Simplex::<u8>::from_weights(&[1u32, 1, 1]).unwrap();
+/// assert_eq!(thirds.as_slice(), [Poui(86), Poui(85), Poui(85)]);
+/// assert_eq!(thirds[1], Poui(85));
+///
+/// let sum: u32 = thirds.as_slice().iter().map(|p| p.0 as u32).sum();
+/// assert_eq!(sum, 256);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Simplex<N: PrimInt + Unsigned + WrappingAdd> {
+    probabilities: Vec<Poui<N>>,
+}
+
+impl<N, M> Simplex<N>
+where
+    N: PrimInt + Unsigned + WrappingAdd + StrictWiden<Widened = M> + 'static,
+    M: PrimInt + AsPrimitive<N>,
+{
+    /// Normalizes probabilities that may not quite sum to one, or returns
+    /// `None` if they are all zero.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    /// use poui::Simplex;
+    ///
+    /// let simplex = Simplex::new(vec![Poui(100u8), Poui(100)]).unwrap();
+    /// assert_eq!(simplex.as_slice(), [Poui(128), Poui(128)]);
+    /// ```
+    pub fn new(probabilities: Vec<Poui<N>>) -> Option<Self> {
+        let raw: Vec<N> = probabilities.into_iter().map(|p| p.0).collect();
+        Self::from_weights(&raw)
+    }
+
+    /// The distribution choosing each index with probability proportional
+    /// to its weight, or `None` if every weight is zero.
+    ///
+    /// The weights' shares are computed exactly, by long division on their
+    /// 128-bit sum.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the weights sum to `2^128` or more.
+    pub fn from_weights<W: PrimInt + Unsigned>(weights: &[W]) -> Option<Self> {
+        let total = weights.iter().fold(0u128, |sum, w| {
+            sum.checked_add(w.to_u128().unwrap())
+                .expect("weights sum to 2^128 or more")
+        });
+        if total == 0 {
+            return None;
+        }
+        let bits = frac_bits::<N>();
+        let quotas = weights.iter().map(|w| match w.to_u128().unwrap() {
+            w if w == total => (1 << bits, 0),
+            w => div_scaled(w, total, bits),
+        });
+        Some(Self::apportion(quotas.collect()))
+    }
+
+    /// The uniform distribution over `len` outcomes, or `None` if `len` is
+    /// zero.
+    pub fn uniform(len: usize) -> Option<Self> {
+        Self::from_weights(&vec![1u8; len])
+    }
+
+    /// Mixes two distributions over the same outcomes, giving `self` weight
+    /// `1 − t` and `other` weight `t`.
+    ///
+    /// The mixture is computed exactly in the widened type before it is
+    /// normalized, so it is still exactly a distribution.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the distributions have different lengths.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    /// use poui::Simplex;
+    ///
+    /// let a = Simplex::<u8>::from_weights(&[1u8, 0]).unwrap();
+    /// let b = Simplex::<u8>::from_weights(&[0u8, 1]).unwrap();
+    /// assert_eq!(a.mix(&b, Poui(64)).as_slice(), [Poui(192), Poui(64)]);
+    /// ```
+    pub fn mix(&self, other: &Self, t: Poui<N>) -> Self {
+        assert_eq!(self.len(), other.len(), "distributions differ in length");
+        let bits = frac_bits::<N>();
+        let t = t.0.widen();
+        let s = (M::one() << bits) - t;
+        let quotas = self
+            .probabilities
+            .iter()
+            .zip(&other.probabilities)
+            .map(|(a, b)| {
+                let mixed = (a.0.widen() * s + b.0.widen() * t).to_u128().unwrap();
+                (mixed >> bits, mixed & ((1 << bits) - 1))
+            });
+        Self::apportion(quotas.collect())
+    }
+
+    /// The probabilities as exact shares of `2^bits`, which sum to exactly
+    /// `2^bits`, with a certain outcome restored to 1.
+    pub(crate) fn shares(&self) -> Vec<M> {
+        let mut nonzero = self.probabilities.iter().filter(|p| p.0 != N::zero());
+        let certain = nonzero.next().is_some() && nonzero.next().is_none();
+        self.probabilities
+            .iter()
+            .map(|p| match p.0.widen() {
+                p if certain && p != M::zero() => M::one() << frac_bits::<N>(),
+                p => p,
+            })
+            .collect()
+    }
+
+    /// Rounds exact shares of `2^bits`, given as integer parts and
+    /// remainders with a common denominator, by the largest remainder method.
+    pub(crate) fn apportion(quotas: Vec<(u128, u128)>) -> Self {
+        let mut shares: Vec<u128> = quotas.iter().map(|&(share, _)| share).collect();
+        let missing = (1u128 << frac_bits::<N>()) - shares.iter().sum::<u128>();
+        let mut order: Vec<usize> = (0..quotas.len()).collect();
+        order.sort_by_key(|&i| std::cmp::Reverse(quotas[i].1));
+        for &i in order.iter().cycle().take(missing as usize) {
+            shares[i] += 1;
+        }
+        let probabilities = shares
+            .into_iter()
+            .map(|share| Poui(N::from(share).unwrap_or(N::max_value())))
+            .collect();
+        Simplex { probabilities }
+    }
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Simplex<N> {
+    /// The probabilities of the outcomes.
+    pub fn as_slice(&self) -> &[Poui<N>] {
+        &self.probabilities
+    }
+
+    /// The number of outcomes.
+    pub fn len(&self) -> usize {
+        self.probabilities.len()
+    }
+
+    /// Whether there are no outcomes, which is never the case for a
+    /// normalized distribution.
+    pub fn is_empty(&self) -> bool {
+        self.probabilities.is_empty()
+    }
+
+    /// Unwraps the probabilities.
+    pub fn into_vec(self) -> Vec<Poui<N>> {
+        self.probabilities
+    }
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> std::ops::Index<usize> for Simplex<N> {
+    type Output = Poui<N>;
+
+    fn index(&self, index: usize) -> &Self::Output {
+        &self.probabilities[index]
+    }
+}
+
+#[cfg(test)]
+fn raw_sum<N: PrimInt + Unsigned + WrappingAdd>(simplex: &Simplex<N>) -> u128 {
+    simplex
+        .as_slice()
+        .iter()
+        .map(|p| p.0.to_u128().unwrap())
+        .sum()
+}
+
+#[test]
+fn simplex_largest_remainder_is_exact() {
+    let weights = [7u64, 0, 13, 1, 1, 1, 977];
+    let simplex = Simplex::<u16>::from_weights(&weights).unwrap();
+    assert_eq!(raw_sum(&simplex), 1 << 16);
+    let total: u64 = weights.iter().sum();
+    for (w, p) in weights.iter().zip(simplex.as_slice()) {
+        let exact = *w as f64 * 65536.0 / total as f64;
+        assert!((p.0 as f64 - exact).abs() < 1.0, "{w}: {p:?}");
+    }
+    assert_eq!(simplex[1], Poui(0));
+    let certain = Simplex::<u32>::from_weights(&[0u8, 5, 0]).unwrap();
+    assert_eq!(certain.as_slice(), [Poui(0), Poui(u32::MAX), Poui(0)]);
+    assert_eq!(Simplex::<u8>::from_weights(&[0u8, 0]), None);
+    assert_eq!(Simplex::<u8>::uniform(0), None);
+    assert_eq!(raw_sum(&Simplex::<u64>::uniform(7).unwrap()), 1 << 64);
+}
+
+#[test]
+fn simplex_mix_stays_normalized() {
+    let a = Simplex::<u8>::from_weights(&[1u8, 1, 1]).unwrap();
+    let b = Simplex::<u8>::from_weights(&[1u8, 2, 4]).unwrap();
+    for t in 0..=255u8 {
+        let mixed = a.mix(&b, Poui(t));
+        assert_eq!(raw_sum(&mixed), 256, "{t}");
+    }
+    assert_eq!(a.mix(&b, Poui(0)), a);
+    let certain = Simplex::<u8>::from_weights(&[1u8, 0]).unwrap();
+    assert_eq!(certain.mix(&certain, Poui(77)), certain);
+}
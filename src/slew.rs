@@ -0,0 +1,149 @@
+use crate::frac_bits;
+use crate::Poui;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+use num_traits::WrappingSub;
+
+/// A slew-rate limiter, bounding how fast a control value may change.
+///
+/// Each tick the value moves towards the target by at most the rise rate
+/// going up, or the fall rate going down, so steps in the target become
+/// ramps. Separate rates suit e.g. a meter that should jump up quickly and
+/// fall back slowly.
+///
+/// In wrapping mode the value is an angle, and it approaches the target
+/// along the shorter arc, crossing from 1 to 0 if need be: rising means
+/// turning forwards and falling means turning backwards. When the target is
+/// exactly opposite, the value turns backwards, like
+/// [`Poui::signed_delta`].
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Poui;
+/// use poui::SlewLimiter;
+///
+/// let mut level = SlewLimiter::new(Poui(0u8), Poui(100), Poui(10));
+/// assert_eq!(level.process(Poui(250)), Poui(100));
+/// assert_eq!(level.process(Poui(250)), Poui(200));
+/// assert_eq!(level.process(Poui(250)), Poui(250));
+/// assert_eq!(level.process(Poui(0)), Poui(240));
+///
+/// let mut heading = SlewLimiter::new(Poui(250u8), Poui(4), Poui(4)).with_wrapping(true);
+/// assert_eq!(heading.process(Poui(10)), Poui(254));
+/// assert_eq!(heading.process(Poui(10)), Poui(2));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SlewLimiter<N: PrimInt + Unsigned + WrappingAdd> {
+    value: Poui<N>,
+    rise: Poui<N>,
+    fall: Poui<N>,
+    wrapping: bool,
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd + WrappingSub> SlewLimiter<N> {
+    /// A limiter starting at `initial`, rising by at most `rise` and falling
+    /// by at most `fall` per tick.
+    pub fn new(initial: Poui<N>, rise: Poui<N>, fall: Poui<N>) -> Self {
+        SlewLimiter {
+            value: initial,
+            rise,
+            fall,
+            wrapping: false,
+        }
+    }
+
+    /// Sets whether the value is an angle, which wraps around from 1 to 0.
+    pub fn with_wrapping(self, wrapping: bool) -> Self {
+        SlewLimiter { wrapping, ..self }
+    }
+
+    /// Whether the value is an angle, which wraps around from 1 to 0.
+    pub fn is_wrapping(&self) -> bool {
+        self.wrapping
+    }
+
+    /// The largest increase per tick.
+    pub fn rise(&self) -> Poui<N> {
+        self.rise
+    }
+
+    /// The largest decrease per tick.
+    pub fn fall(&self) -> Poui<N> {
+        self.fall
+    }
+
+    /// Changes the rates, keeping the current value.
+    pub fn set_rates(&mut self, rise: Poui<N>, fall: Poui<N>) {
+        self.rise = rise;
+        self.fall = fall;
+    }
+
+    /// The current value.
+    pub fn value(&self) -> Poui<N> {
+        self.value
+    }
+
+    /// Jumps to `value`, e.g. to start from a measured position.
+    pub fn reset(&mut self, value: Poui<N>) {
+        self.value = value;
+    }
+
+    /// Moves the value one tick towards `target`, and returns it.
+    pub fn process(&mut self, target: Poui<N>) -> Poui<N> {
+        let (value, target) = (self.value.0, target.0);
+        let rising = if self.wrapping {
+            let half = N::one() << (frac_bits::<N>() - 1);
+            target.wrapping_sub(&value) < half
+        } else {
+            target > value
+        };
+        self.value = if rising {
+            let distance = target.wrapping_sub(&value);
+            Poui(value.wrapping_add(&distance.min(self.rise.0)))
+        } else {
+            let distance = value.wrapping_sub(&target);
+            Poui(value.wrapping_sub(&distance.min(self.fall.0)))
+        };
+        self.value
+    }
+}
+
+#[test]
+fn slew_limiter_never_exceeds_rates() {
+    let mut limiter = SlewLimiter::new(Poui(0x8000u16), Poui(0x0300), Poui(0x0100));
+    let mut previous = limiter.value();
+    for i in 0..2000u32 {
+        let target = Poui((i * 40503 % 65536) as u16);
+        let value = limiter.process(target);
+        if value > previous {
+            assert!(value.0 - previous.0 <= 0x0300 && value <= target);
+        } else {
+            assert!(previous.0 - value.0 <= 0x0100 && value >= target);
+        }
+        previous = value;
+    }
+    limiter.reset(Poui(0));
+    limiter.set_rates(Poui(u16::MAX), Poui(u16::MAX));
+    assert_eq!(limiter.process(Poui(u16::MAX)), Poui(u16::MAX));
+    assert_eq!(limiter.process(Poui(0)), Poui(0));
+}
+
+#[test]
+fn wrapping_slew_takes_the_shorter_arc() {
+    let mut heading =
+        SlewLimiter::new(Poui(0x1000u16), Poui(0x0800), Poui(0x0400)).with_wrapping(true);
+    // Backwards through 0 to 0xF000 is shorter than forwards.
+    let path: Vec<_> = (0..6).map(|_| heading.process(Poui(0xF000)).0).collect();
+    assert_eq!(path, [0x0C00, 0x0800, 0x0400, 0x0000, 0xFC00, 0xF800]);
+    for _ in 0..2 {
+        heading.process(Poui(0xF000));
+    }
+    assert_eq!(heading.value(), Poui(0xF000));
+    assert_eq!(heading.process(Poui(0x0000)), Poui(0xF800));
+    assert_eq!(heading.process(Poui(0x0000)), Poui(0x0000));
+    // Exactly opposite targets are approached backwards.
+    assert_eq!(heading.process(Poui(0x8000)), Poui(0xFC00));
+    assert!(heading.is_wrapping());
+}
@@ -0,0 +1,103 @@
+use crate::frac_bits;
+use crate::Poui;
+use crate::StrictWiden;
+use num_traits::AsPrimitive;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+
+/// Evaluates a Bézier curve with nondecreasing control points at `t`, by de
+/// Casteljau's algorithm with every product rounded down.
+///
+/// Rounding down preserves the order of the intermediate points at every
+/// level, and each lerp is nondecreasing in `t` and in both endpoints, so
+/// the result is exactly monotone in `t` despite the rounding.
+fn monotone_bezier<M: PrimInt>(mut points: [M; 6], degree: usize, t: M, f: usize) -> M {
+    for level in (1..=degree).rev() {
+        for i in 0..level {
+            points[i] = points[i] + (((points[i + 1] - points[i]) * t) >> f);
+        }
+    }
+    points[0]
+}
+
+impl<N, M> Poui<N>
+where
+    N: PrimInt + Unsigned + WrappingAdd + StrictWiden<Widened = M> + 'static,
+    M: PrimInt + AsPrimitive<N>,
+{
+    /// The Hermite smoothing curve `3t² - 2t³`, which eases in and out with
+    /// zero slope at both ends.
+    ///
+    /// The cubic is evaluated in the widened type from its Bézier form, with
+    /// control points 0, 0, 1, 1, so the result is monotone in `self` and
+    /// exactly 0 at 0. Each step rounds down, so the result is within a few
+    /// ULPs below the exact curve.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui(0u8).smoothstep(), Poui(0u8));
+    /// assert_eq!(Poui(64u8).smoothstep(), Poui(40u8));
+    /// assert_eq!(Poui(128u8).smoothstep(), Poui(128u8));
+    /// assert_eq!(Poui(0x4000u16).smoothstep(), Poui(0x2800u16));
+    /// ```
+    pub fn smoothstep(self) -> Self {
+        self.hermite([0, 0, 1, 1, 0, 0], 3)
+    }
+
+    /// Ken Perlin's smoother curve `6t⁵ - 15t⁴ + 10t³`, which also has zero
+    /// second derivative at both ends.
+    ///
+    /// Like [`Poui::smoothstep`], this is evaluated in the widened type from
+    /// its Bézier form, with control points 0, 0, 0, 1, 1, 1, so it is
+    /// monotone and exactly 0 at 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui(0u8).smootherstep(), Poui(0u8));
+    /// assert_eq!(Poui(64u8).smootherstep(), Poui(26u8));
+    /// assert_eq!(Poui(0x4000u16).smootherstep(), Poui(0x1A80u16));
+    /// ```
+    pub fn smootherstep(self) -> Self {
+        self.hermite([0, 0, 0, 1, 1, 1], 5)
+    }
+
+    fn hermite(self, points: [u8; 6], degree: usize) -> Self {
+        let f = frac_bits::<N>();
+        let one = M::one() << f;
+        let points = points.map(|p| if p == 0 { M::zero() } else { one });
+        let result = monotone_bezier(points, degree, self.0.widen(), f);
+        Poui(result.min(N::max_value().widen()).as_())
+    }
+}
+
+#[test]
+fn smoothstep_matches_polynomial() {
+    for n in 0..=u16::MAX {
+        let t = n as f64 / 65536.0;
+        let expected = (3.0 * t * t - 2.0 * t * t * t) * 65536.0;
+        let actual = Poui(n).smoothstep().0 as f64;
+        assert!((actual - expected).abs() <= 3.0, "{n}: {actual} {expected}");
+        let expected = t * t * t * (t * (6.0 * t - 15.0) + 10.0) * 65536.0;
+        let actual = Poui(n).smootherstep().0 as f64;
+        assert!((actual - expected).abs() <= 5.0, "{n}: {actual} {expected}");
+    }
+}
+
+#[test]
+fn smoothstep_is_monotone() {
+    let mut previous = (Poui(0u16), Poui(0u16));
+    for n in 0..=u16::MAX {
+        let current = (Poui(n).smoothstep(), Poui(n).smootherstep());
+        assert!(current.0 >= previous.0 && current.1 >= previous.1, "{n}");
+        previous = current;
+    }
+    assert_eq!(Poui(u32::MAX).smoothstep(), Poui(u32::MAX - 1));
+    assert_eq!(Poui(1u64).smootherstep(), Poui(0u64));
+}
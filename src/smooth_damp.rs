@@ -0,0 +1,208 @@
+use crate::easing::from_q62;
+use crate::easing::to_q62;
+use crate::Poui;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+use std::time::Duration;
+
+/// The coefficient of x² in the approximation of the exponential, 0.48, in
+/// Q32.
+const C2: i128 = 2061584302;
+
+/// The coefficient of x³ in the approximation of the exponential, 0.235, in
+/// Q32.
+const C3: i128 = 1009317315;
+
+/// Nanoseconds per second.
+const NANOS: i128 = 1_000_000_000;
+
+/// A critically damped spring, easing a value towards a moving target.
+///
+/// This is the `SmoothDamp` of game engines: the value follows the target
+/// without overshooting it, taking roughly `smooth_time` to get there, and
+/// changes of the target don't cause sudden jumps in speed. It's handy for
+/// cameras and UI elements chasing something that moves.
+///
+/// The spring is integrated with the same rational approximation of the
+/// exponential as in Unity, but in fixed point, with 62 fractional bits for
+/// the position and velocity, so it behaves identically on every target and
+/// never stalls short of the target because of rounding.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Poui;
+/// use poui::SmoothDamp;
+/// use std::time::Duration;
+///
+/// let mut camera = SmoothDamp::new(Poui(0u16), Duration::from_millis(300));
+/// camera.set_target(Poui(0x8000));
+/// let frame = Duration::from_millis(16);
+/// let first = camera.update(frame);
+/// assert!(first > Poui(0) && first < Poui(0x0400));
+/// for _ in 0..200 {
+///     camera.update(frame);
+/// }
+/// assert_eq!(camera.current(), Poui(0x8000));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SmoothDamp<N: PrimInt + Unsigned + WrappingAdd> {
+    current: i128,
+    velocity: i128,
+    target: Poui<N>,
+    smooth_time: Duration,
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> SmoothDamp<N> {
+    /// A spring at rest at `value`, taking about `smooth_time` to reach a
+    /// new target.
+    pub fn new(value: Poui<N>, smooth_time: Duration) -> Self {
+        SmoothDamp {
+            current: to_q62(value),
+            velocity: 0,
+            target: value,
+            smooth_time,
+        }
+    }
+
+    /// The current value.
+    pub fn current(&self) -> Poui<N> {
+        from_q62(self.current)
+    }
+
+    /// The target.
+    pub fn target(&self) -> Poui<N> {
+        self.target
+    }
+
+    /// Moves the target.
+    pub fn set_target(&mut self, target: Poui<N>) {
+        self.target = target;
+    }
+
+    /// The approximate time to reach the target.
+    pub fn smooth_time(&self) -> Duration {
+        self.smooth_time
+    }
+
+    /// Changes the approximate time to reach the target.
+    pub fn set_smooth_time(&mut self, smooth_time: Duration) {
+        self.smooth_time = smooth_time;
+    }
+
+    /// Jumps to `value` and stops, also making it the target.
+    pub fn snap(&mut self, value: Poui<N>) {
+        *self = SmoothDamp::new(value, self.smooth_time);
+    }
+
+    /// Advances the spring by `dt`, and returns the new value.
+    pub fn update(&mut self, dt: Duration) -> Poui<N> {
+        let target = to_q62(self.target);
+        let smooth = self.smooth_time.as_nanos() as i128;
+        let dt = dt.as_nanos() as i128;
+        // After a few hundred smoothing times the spring has long settled,
+        // and bailing out keeps the arithmetic below in range.
+        if dt >= smooth.saturating_mul(256) {
+            self.current = target;
+            self.velocity = 0;
+            return self.current();
+        }
+        // The coefficients are in Q32, which leaves room for the products
+        // with positions and velocities in Q62.
+        let mul = |a: i128, b: i128| (a * b) >> 32;
+        // x = omega * dt, where omega = 2 / smooth_time.
+        let x = (dt << 33) / smooth;
+        let x2 = mul(x, x);
+        let decay = (1 << 64) / ((1 << 32) + x + mul(x2, C2) + mul(mul(x2, x), C3));
+        let change = self.current - target;
+        let temp = self.velocity * dt / NANOS + mul(x, change);
+        self.velocity = mul(self.velocity - 2 * temp * NANOS / smooth, decay);
+        let next = target + mul(change + temp, decay);
+        // Never overshoot the target.
+        if (change < 0) == (next > target) {
+            self.current = target;
+            self.velocity = 0;
+        } else {
+            self.current = next;
+        }
+        self.current()
+    }
+}
+
+#[cfg(test)]
+fn reference(mut current: f64, target: f64, smooth_time: f64, dt: f64, steps: usize) -> f64 {
+    let mut velocity = 0.0;
+    for _ in 0..steps {
+        let omega = 2.0 / smooth_time;
+        let x = omega * dt;
+        let decay = 1.0 / (1.0 + x + 0.48 * x * x + 0.235 * x * x * x);
+        let change = current - target;
+        let temp = (velocity + omega * change) * dt;
+        velocity = (velocity - omega * temp) * decay;
+        let next = target + (change + temp) * decay;
+        if (target - current > 0.0) == (next > target) {
+            current = target;
+            velocity = 0.0;
+        } else {
+            current = next;
+        }
+    }
+    current
+}
+
+#[test]
+fn smooth_damp_matches_reference() {
+    for (from, to) in [(0u16, 0xFFFF), (0xC000, 0x1000), (0x8000, 0x8001)] {
+        let mut spring = SmoothDamp::new(Poui(from), Duration::from_millis(250));
+        spring.set_target(Poui(to));
+        for steps in 1..=60 {
+            let actual = spring.update(Duration::from_millis(10)).0 as f64;
+            let expected = reference(from as f64, to as f64, 0.25, 0.01, steps);
+            assert!(
+                (actual - expected).abs() <= 1.0,
+                "{steps}: {actual} {expected}"
+            );
+        }
+    }
+}
+
+#[test]
+fn smooth_damp_never_overshoots() {
+    let mut spring = SmoothDamp::new(Poui(0u8), Duration::from_millis(100));
+    spring.set_target(Poui(200));
+    let mut last = Poui(0);
+    for _ in 0..100 {
+        let value = spring.update(Duration::from_millis(33));
+        assert!(value >= last && value <= Poui(200));
+        last = value;
+    }
+    assert_eq!(last, Poui(200));
+}
+
+#[test]
+fn smooth_damp_settles_on_long_or_degenerate_steps() {
+    let mut spring = SmoothDamp::new(Poui(10u32), Duration::ZERO);
+    spring.set_target(Poui(1000));
+    assert_eq!(spring.update(Duration::from_millis(1)), Poui(1000));
+    spring.set_smooth_time(Duration::from_millis(1));
+    spring.set_target(Poui(u32::MAX));
+    assert_eq!(spring.update(Duration::from_secs(3600)), Poui(u32::MAX));
+    spring.snap(Poui(7));
+    assert_eq!((spring.current(), spring.target()), (Poui(7), Poui(7)));
+    assert_eq!(spring.update(Duration::from_millis(5)), Poui(7));
+}
+
+#[test]
+fn smooth_damp_extreme_times_stay_in_range() {
+    for smooth in [1_000u64, 1_000_000, 3_600_000_000_000] {
+        let mut spring = SmoothDamp::new(Poui(0u64), Duration::from_nanos(smooth));
+        for (i, dt) in [1u64, 999, 16_000_000, 7, smooth * 255, 1]
+            .into_iter()
+            .enumerate()
+        {
+            spring.set_target(Poui(if i % 2 == 0 { u64::MAX } else { 0 }));
+            spring.update(Duration::from_nanos(dt));
+        }
+    }
+}
@@ -0,0 +1,180 @@
+use crate::Poui;
+
+/// The primitive polynomials and initial direction numbers of the
+/// dimensions after the first, from Joe and Kuo's `new-joe-kuo-6.21201`
+/// table: the degree `s`, the inner coefficients `a` of the polynomial, and
+/// the first `s` direction numbers `m`, which are odd and less than `2^i`.
+const DIRECTIONS: [(u32, u32, &[u32]); 9] = [
+    (1, 0, &[1]),
+    (2, 1, &[1, 3]),
+    (3, 1, &[1, 3, 1]),
+    (3, 2, &[1, 1, 1]),
+    (4, 1, &[1, 1, 3, 3]),
+    (4, 4, &[1, 3, 5, 13]),
+    (5, 2, &[1, 1, 5, 5, 17]),
+    (5, 4, &[1, 1, 5, 5, 5]),
+    (5, 7, &[1, 1, 7, 11, 19]),
+];
+
+/// A Sobol sequence of points in the `D`-dimensional unit hypercube, for up
+/// to 10 dimensions.
+///
+/// The first `2^k` points put exactly one point in every interval of length
+/// `2^-k` along each axis, and in the first two dimensions in every
+/// rectangle of area `2^-k` aligned to the binary subdivisions. This makes
+/// the sequence a good choice for quasi-Monte Carlo integration.
+///
+/// Each point is the previous one with a single direction number XORed into
+/// each coordinate, in Gray code order, which works directly on the raw
+/// `u32` values. The first dimension is the van der Corput sequence,
+/// permuted into Gray code order, and the others use Joe and Kuo's
+/// direction numbers. The sequence ends after `2^32` points, when the
+/// direction numbers run out.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Poui;
+/// use poui::Sobol;
+///
+/// let points: Vec<_> = Sobol::<2>::new().take(4).collect();
+/// assert_eq!(
+///     points,
+///     [
+///         [Poui(0), Poui(0)],
+///         [Poui(0x8000_0000), Poui(0x8000_0000)],
+///         [Poui(0xC000_0000), Poui(0x4000_0000)],
+///         [Poui(0x4000_0000), Poui(0xC000_0000)],
+///     ]
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Sobol<const D: usize> {
+    directions: [[u32; 32]; D],
+    point: [u32; D],
+    index: u64,
+}
+
+impl<const D: usize> Sobol<D> {
+    /// The sequence from its first point, the origin.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `D` is zero or more than 10.
+    pub fn new() -> Self {
+        assert!(
+            (1..=DIRECTIONS.len() + 1).contains(&D),
+            "Sobol sequences have 1 to 10 dimensions"
+        );
+        Sobol {
+            directions: std::array::from_fn(directions),
+            point: [0; D],
+            index: 0,
+        }
+    }
+
+    /// The index of the next point.
+    pub fn index(&self) -> u64 {
+        self.index
+    }
+}
+
+impl<const D: usize> Default for Sobol<D> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const D: usize> Iterator for Sobol<D> {
+    type Item = [Poui<u32>; D];
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.index > u32::MAX as u64 {
+            return None;
+        }
+        let point = self.point.map(Poui);
+        // The next point in Gray code order differs in the direction number
+        // of the lowest zero bit of the index.
+        let bit = self.index.trailing_ones() as usize;
+        if bit < 32 {
+            for (x, v) in self.point.iter_mut().zip(&self.directions) {
+                *x ^= v[bit];
+            }
+        }
+        self.index += 1;
+        Some(point)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // On 32-bit targets a fresh sequence has more points than `usize`
+        // can count.
+        match usize::try_from((1 << 32) - self.index) {
+            Ok(remaining) => (remaining, Some(remaining)),
+            Err(_) => (usize::MAX, None),
+        }
+    }
+}
+
+/// The direction numbers of a dimension, as fractions of `2^32`.
+fn directions(dimension: usize) -> [u32; 32] {
+    let mut v = [0u32; 32];
+    let Some(&(s, a, m)) = dimension.checked_sub(1).map(|d| &DIRECTIONS[d]) else {
+        // The first dimension is the van der Corput sequence.
+        return std::array::from_fn(|i| 1 << (31 - i));
+    };
+    let s = s as usize;
+    for i in 0..32 {
+        v[i] = if i < s {
+            m[i] << (31 - i)
+        } else {
+            let inner = (1..s)
+                .filter(|k| a >> (s - 1 - k) & 1 == 1)
+                .fold(0, |x, k| x ^ v[i - k]);
+            v[i - s] ^ v[i - s] >> s ^ inner
+        };
+    }
+    v
+}
+
+#[test]
+fn sobol_coordinates_are_stratified() {
+    let points: Vec<_> = Sobol::<10>::new().take(1 << 12).collect();
+    for k in [1, 4, 12] {
+        for d in 0..10 {
+            let mut strata: Vec<_> = points[..1 << k]
+                .iter()
+                .map(|p| p[d].0 >> (32 - k))
+                .collect();
+            strata.sort();
+            assert!(
+                strata.iter().enumerate().all(|(i, &s)| s as usize == i),
+                "{d} {k}"
+            );
+        }
+    }
+}
+
+#[test]
+fn sobol_first_two_dimensions_form_a_net() {
+    let points: Vec<_> = Sobol::<2>::new().take(1 << 10).collect();
+    for x_bits in 0..=10 {
+        let y_bits = 10 - x_bits;
+        let mut boxes: Vec<_> = points
+            .iter()
+            .map(|p| {
+                let x = p[0].0.checked_shr(32 - x_bits).unwrap_or(0);
+                let y = p[1].0.checked_shr(32 - y_bits).unwrap_or(0);
+                x << y_bits | y
+            })
+            .collect();
+        boxes.sort();
+        boxes.dedup();
+        assert_eq!(boxes.len(), 1 << 10, "{x_bits}");
+    }
+    let mut one = Sobol::<1>::new();
+    assert_eq!(one.nth(3), Some([Poui(0x4000_0000)]));
+    assert_eq!(
+        one.size_hint(),
+        (u32::MAX as usize - 3, Some(u32::MAX as usize - 3))
+    );
+}
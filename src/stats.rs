@@ -0,0 +1,351 @@
+use crate::frac_bits;
+use crate::probability::mul_hi;
+use crate::probability::ratio;
+use crate::Lerp;
+use crate::Poui;
+use crate::StrictWiden;
+use num_traits::AsPrimitive;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+use std::marker::PhantomData;
+
+/// A running summary of `Poui` samples: their count, mean, and variance.
+///
+/// Rather than updating a rounded mean as each sample arrives, as
+/// Welford's algorithm does with floats, the accumulator keeps the exact
+/// sum of the samples and of their squares, the latter in 256 bits. Nothing
+/// drifts or overflows however many samples there are, every statistic is
+/// computed exactly and rounded once when it is asked for, and two
+/// summaries can be merged losslessly.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::OnlineStats;
+/// use poui::Poui;
+///
+/// let mut stats = OnlineStats::new();
+/// stats.extend([Poui(0u8), Poui(128), Poui(128), Poui(0)]);
+/// assert_eq!(stats.count(), 4);
+/// assert_eq!(stats.mean(), Some(Poui(64)));
+/// // The variance is 1/16.
+/// assert_eq!(stats.variance(), Some(Poui(16)));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct OnlineStats<N: PrimInt + Unsigned + WrappingAdd> {
+    count: u64,
+    sum: u128,
+    /// The sum of the squares, as its high and low halves.
+    sum_sq: (u128, u128),
+    samples: PhantomData<N>,
+}
+
+impl<N, M> OnlineStats<N>
+where
+    N: PrimInt + Unsigned + WrappingAdd + StrictWiden<Widened = M> + 'static,
+    M: PrimInt + AsPrimitive<N>,
+{
+    /// An empty summary.
+    pub fn new() -> Self {
+        OnlineStats {
+            count: 0,
+            sum: 0,
+            sum_sq: (0, 0),
+            samples: PhantomData,
+        }
+    }
+
+    /// Adds a sample to the summary.
+    pub fn insert(&mut self, x: Poui<N>) {
+        let square = (x.0.widen() * x.0.widen()).to_u128().unwrap();
+        self.count += 1;
+        self.sum += x.0.to_u128().unwrap();
+        self.sum_sq = add(self.sum_sq, (0, square));
+    }
+
+    /// Adds every sample summarized by `other`.
+    pub fn merge(&mut self, other: &Self) {
+        self.count += other.count;
+        self.sum += other.sum;
+        self.sum_sq = add(self.sum_sq, other.sum_sq);
+    }
+
+    /// The number of samples.
+    pub fn count(&self) -> u64 {
+        self.count
+    }
+
+    /// The mean of the samples, rounded to the nearest `Poui`, or `None` if
+    /// there are none.
+    pub fn mean(&self) -> Option<Poui<N>> {
+        let n = self.count as u128;
+        (n != 0).then(|| Poui(N::from((self.sum + n / 2) / n).unwrap()))
+    }
+
+    /// The population variance of the samples, i.e. the mean squared
+    /// deviation from their mean, rounded down, or `None` if there are none.
+    ///
+    /// The variance of values in [0, 1) is at most 1/4, so it always fits.
+    pub fn variance(&self) -> Option<Poui<N>> {
+        let n = self.count as u128;
+        (n != 0).then(|| self.scaled_deviation(n * n))
+    }
+
+    /// The sample variance, with Bessel's correction, rounded down, or
+    /// `None` if there are fewer than two samples.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::OnlineStats;
+    /// use poui::Poui;
+    ///
+    /// let mut stats = OnlineStats::new();
+    /// stats.extend([Poui(0u8), Poui(128)]);
+    /// assert_eq!(stats.variance(), Some(Poui(16)));
+    /// assert_eq!(stats.sample_variance(), Some(Poui(32)));
+    /// ```
+    pub fn sample_variance(&self) -> Option<Poui<N>> {
+        let n = self.count as u128;
+        (n >= 2).then(|| self.scaled_deviation(n * (n - 1)))
+    }
+
+    /// Computes `(n · Σx² − (Σx)²) / divisor`, the sum of squared
+    /// deviations times `n` over `divisor`, as a `Poui`.
+    fn scaled_deviation(&self, divisor: u128) -> Poui<N> {
+        let n = self.count as u128;
+        let (hi, lo) = self.sum_sq;
+        let (carry, lo) = (mul_hi(lo, n), lo.wrapping_mul(n));
+        let n_sum_sq = (hi.wrapping_mul(n).wrapping_add(carry), lo);
+        let sum_squared = (mul_hi(self.sum, self.sum), self.sum.wrapping_mul(self.sum));
+        // The difference is `n²` times the variance, which is less than
+        // `n² · 2^(2 bits) / 4`, so it fits even if `n · Σx²` doesn't.
+        let deviation = sub(n_sum_sq, sum_squared);
+        let shifted = shr(deviation, frac_bits::<N>());
+        Poui(N::from(div(shifted, divisor)).unwrap())
+    }
+}
+
+impl<N, M> Default for OnlineStats<N>
+where
+    N: PrimInt + Unsigned + WrappingAdd + StrictWiden<Widened = M> + 'static,
+    M: PrimInt + AsPrimitive<N>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N, M> Extend<Poui<N>> for OnlineStats<N>
+where
+    N: PrimInt + Unsigned + WrappingAdd + StrictWiden<Widened = M> + 'static,
+    M: PrimInt + AsPrimitive<N>,
+{
+    fn extend<I: IntoIterator<Item = Poui<N>>>(&mut self, samples: I) {
+        for x in samples {
+            self.insert(x);
+        }
+    }
+}
+
+/// The mean of the values weighted by `weights`, rounded to the nearest
+/// `Poui`, or `None` if the weights are all zero.
+///
+/// The weights don't need to be normalized. The weighted sum is accumulated
+/// exactly, in 256 bits, and divided by the total weight once at the end.
+///
+/// # Panics
+///
+/// Panics if `values` and `weights` have different lengths.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::weighted_mean;
+/// use poui::Poui;
+///
+/// let values = [Poui(0u8), Poui(100), Poui(200)];
+/// let weights = [Poui(50u8), Poui(0), Poui(150)];
+/// assert_eq!(weighted_mean(&values, &weights), Some(Poui(150)));
+/// assert_eq!(weighted_mean(&values, &[Poui(0); 3]), None);
+/// ```
+pub fn weighted_mean<N, M>(values: &[Poui<N>], weights: &[Poui<N>]) -> Option<Poui<N>>
+where
+    N: PrimInt + Unsigned + WrappingAdd + StrictWiden<Widened = M> + 'static,
+    M: PrimInt + AsPrimitive<N>,
+{
+    assert_eq!(
+        values.len(),
+        weights.len(),
+        "values and weights differ in length"
+    );
+    let (sum, total) = values
+        .iter()
+        .zip(weights)
+        .fold(((0, 0), 0u128), |(sum, total), (v, w)| {
+            let product = (v.0.widen() * w.0.widen()).to_u128().unwrap();
+            (add(sum, (0, product)), total + w.0.to_u128().unwrap())
+        });
+    (total != 0).then(|| Poui(N::from(div(add(sum, (0, total / 2)), total)).unwrap()))
+}
+
+/// The mean of any values that can be interpolated, weighted by `weights`,
+/// or `None` if the weights are all zero.
+///
+/// Without a wider accumulator for an arbitrary [`Lerp`] type, the mean is
+/// built up one value at a time: each value is blended into the mean of
+/// those before it by its share of the weight so far. Each blend rounds, so
+/// for `Poui` values [`weighted_mean`] is more precise.
+///
+/// # Panics
+///
+/// Panics if `values` and `weights` have different lengths.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::weighted_blend;
+/// use poui::Poui;
+///
+/// let colors = [[255u8, 0, 0], [0, 0, 255]];
+/// let weights = [Poui(192u8), Poui(64)];
+/// assert_eq!(weighted_blend(&colors, &weights), Some([192, 0, 63]));
+/// ```
+pub fn weighted_blend<N, T>(values: &[T], weights: &[Poui<N>]) -> Option<T>
+where
+    N: PrimInt + Unsigned + WrappingAdd,
+    T: Lerp<N> + Clone,
+{
+    assert_eq!(
+        values.len(),
+        weights.len(),
+        "values and weights differ in length"
+    );
+    let mut pairs = values.iter().zip(weights);
+    let (first, w) = pairs.find(|(_, w)| w.0 != N::zero())?;
+    let mut total = w.0.to_u128().unwrap();
+    let mut mean = first.clone();
+    for (v, w) in pairs.filter(|(_, w)| w.0 != N::zero()) {
+        total += w.0.to_u128().unwrap();
+        mean = mean.lerp(v.clone(), ratio(w.0.to_u128().unwrap(), total));
+    }
+    Some(mean)
+}
+
+/// Adds two 256-bit integers, wrapping.
+fn add((a1, a0): (u128, u128), (b1, b0): (u128, u128)) -> (u128, u128) {
+    let (lo, carry) = a0.overflowing_add(b0);
+    (a1.wrapping_add(b1).wrapping_add(carry as u128), lo)
+}
+
+/// Subtracts two 256-bit integers, wrapping.
+fn sub((a1, a0): (u128, u128), (b1, b0): (u128, u128)) -> (u128, u128) {
+    let (lo, borrow) = a0.overflowing_sub(b0);
+    (a1.wrapping_sub(b1).wrapping_sub(borrow as u128), lo)
+}
+
+/// Shifts a 256-bit integer right by less than 256 bits.
+fn shr((hi, lo): (u128, u128), shift: usize) -> (u128, u128) {
+    match shift {
+        0 => (hi, lo),
+        1..=127 => (hi >> shift, lo >> shift | hi << (128 - shift)),
+        _ => (0, hi >> (shift - 128)),
+    }
+}
+
+/// Divides a 256-bit integer by a nonzero 128-bit one, for a quotient that
+/// fits in 128 bits, by long division.
+fn div((hi, lo): (u128, u128), divisor: u128) -> u128 {
+    let (mut q, mut r) = (0u128, 0u128);
+    for bit in (0..256).rev() {
+        let next = if bit >= 128 {
+            hi >> (bit - 128)
+        } else {
+            lo >> bit
+        } & 1;
+        // `r < divisor`, so `2r + 1 - divisor` fits whenever `2r` overflows.
+        let carry = r >> 127 == 1;
+        r = r << 1 | next;
+        q <<= 1;
+        if carry || r >= divisor {
+            r = r.wrapping_sub(divisor);
+            q |= 1;
+        }
+    }
+    q
+}
+
+#[test]
+fn online_stats_match_exact_rationals() {
+    let samples: Vec<u16> = (0..5000u32).map(|i| (i * i % 65521) as u16).collect();
+    let mut stats = OnlineStats::new();
+    stats.extend(samples.iter().map(|&x| Poui(x)));
+    let n = samples.len() as f64;
+    let mean = samples.iter().map(|&x| x as f64).sum::<f64>() / n;
+    let variance = samples
+        .iter()
+        .map(|&x| (x as f64 - mean).powi(2))
+        .sum::<f64>()
+        / n;
+    assert_eq!(stats.count(), 5000);
+    assert!((stats.mean().unwrap().0 as f64 - mean).abs() <= 0.5);
+    let expected = variance / 65536.0;
+    let actual = stats.variance().unwrap().0 as f64;
+    assert!(
+        actual <= expected && expected - actual < 1.0,
+        "{actual} {expected}"
+    );
+    assert_eq!(OnlineStats::<u16>::new().mean(), None);
+    assert_eq!(OnlineStats::<u16>::new().variance(), None);
+}
+
+#[test]
+fn online_stats_wide_backings_and_merge() {
+    let (lo, hi) = (Poui(0u64), Poui(u64::MAX));
+    let mut a = OnlineStats::new();
+    let mut b = OnlineStats::new();
+    for _ in 0..1000 {
+        a.insert(lo);
+        b.insert(hi);
+    }
+    a.merge(&b);
+    assert_eq!(a.count(), 2000);
+    assert_eq!(a.mean(), Some(Poui(1 << 63)));
+    // (2^64 − 1)² / 4, divided by 2^64.
+    assert_eq!(a.variance(), Some(Poui((1 << 62) - 1)));
+    assert_eq!(b.variance(), Some(Poui(0)));
+    let mut single = OnlineStats::new();
+    single.insert(Poui(200u8));
+    assert_eq!(single.sample_variance(), None);
+    assert_eq!(single.variance(), Some(Poui(0)));
+}
+
+#[test]
+fn weighted_means_match_exact_rationals() {
+    let values: Vec<Poui<u64>> = (1..=50u64)
+        .map(|i| Poui(i.wrapping_mul(0x9E37_79B9_7F4A_7C15)))
+        .collect();
+    let weights: Vec<Poui<u64>> = (1..=50u64).map(|i| Poui(u64::MAX / i)).collect();
+    let mean = weighted_mean(&values, &weights).unwrap();
+    let sum: f64 = values
+        .iter()
+        .zip(&weights)
+        .map(|(v, w)| v.0 as f64 * w.0 as f64)
+        .sum();
+    let total: f64 = weights.iter().map(|w| w.0 as f64).sum();
+    let expected = sum / total;
+    assert!(
+        (mean.0 as f64 - expected).abs() < expected * 1e-12,
+        "{mean:?}"
+    );
+    let blended = weighted_blend(&values, &weights).unwrap();
+    assert!(blended.0.abs_diff(mean.0) < 1 << 16, "{blended:?} {mean:?}");
+    let same = [Poui(7u8); 4];
+    assert_eq!(
+        weighted_mean(&same, &[Poui(1), Poui(9), Poui(0), Poui(3)]),
+        Some(Poui(7))
+    );
+    assert_eq!(weighted_blend(&[3i32, 9], &[Poui(0u8), Poui(0)]), None);
+    assert_eq!(weighted_blend(&[3i32, 9], &[Poui(0u8), Poui(5)]), Some(9));
+}
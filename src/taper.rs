@@ -0,0 +1,104 @@
+use crate::easing::exp2;
+use crate::easing::from_q62;
+use crate::easing::to_q62;
+use crate::frac_bits;
+use crate::probability::log2_q62;
+use crate::probability::LOG2_10;
+use crate::Poui;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+
+/// One in Q62.
+const ONE: i128 = 1 << 62;
+
+/// The position below which the taper is linear, 1/16, in Q62.
+const KNEE: i128 = ONE >> 4;
+
+/// The gain at the knee, `10^(-3 · 15/16)`, in Q62.
+const KNEE_GAIN: i128 = 7101657629634017;
+
+/// Maps a linear fader position to a gain, along an audio taper.
+///
+/// Loudness is perceived logarithmically, so a fader whose gain is
+/// proportional to its position does almost everything in the bottom
+/// tenth of its travel. Along this taper the gain falls by 3 dB for every
+/// 1/20 of the travel down from the top, a 60 dB range, so equal movements
+/// sound like equal changes. Below 1/16 of the travel, where that curve is
+/// already 56 dB down, the gain instead falls in a straight line to exactly
+/// 0 at the bottom, so the fader can turn the sound off. The exponential is
+/// computed in Q62, and the result rounded to the nearest `Poui`.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::audio_taper;
+/// use poui::Poui;
+///
+/// assert_eq!(audio_taper(Poui(0u16)), Poui(0));
+/// // Halfway up is -30 dB.
+/// assert_eq!(audio_taper(Poui(0x8000u16)), Poui(2072));
+/// // The top of the travel is just below 1, -0.0003 dB.
+/// assert_eq!(audio_taper(Poui(u16::MAX)), Poui(65529));
+/// ```
+pub fn audio_taper<N: PrimInt + Unsigned + WrappingAdd>(x: Poui<N>) -> Poui<N> {
+    let x = to_q62(x);
+    if x < KNEE {
+        return from_q62((x * KNEE_GAIN) / KNEE);
+    }
+    // 10^(3 (x - 1)) = 2^(3 log2(10) (x - 1)).
+    from_q62(exp2(3 * (((x - ONE) * LOG2_10) >> 62)))
+}
+
+/// Maps a gain back to the fader position that gives it along the
+/// [`audio_taper`], rounded to the nearest `Poui`.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::audio_taper;
+/// use poui::audio_taper_inverse;
+/// use poui::Poui;
+///
+/// assert_eq!(audio_taper_inverse(Poui(0u16)), Poui(0));
+/// let x = Poui(0x1234_5678u32);
+/// assert!(audio_taper_inverse(audio_taper(x)).0.abs_diff(x.0) < 0x100);
+/// ```
+pub fn audio_taper_inverse<N: PrimInt + Unsigned + WrappingAdd>(gain: Poui<N>) -> Poui<N> {
+    let g = to_q62(gain);
+    if g < KNEE_GAIN {
+        return from_q62((g * KNEE) / KNEE_GAIN);
+    }
+    let bits = frac_bits::<N>() as i128;
+    let log2 = log2_q62(gain.0.to_u128().unwrap()) - (bits << 62);
+    // Dividing by 3 first keeps the shifted logarithm within 128 bits.
+    from_q62(ONE + ((log2 / 3) << 62) / LOG2_10)
+}
+
+#[test]
+fn audio_taper_matches_float() {
+    for x in (0..=u32::MAX).step_by(0x0070_0001) {
+        let t = x as f64 / 4294967296.0;
+        let expected = if t < 1.0 / 16.0 {
+            t * 16.0 * 10f64.powf(-3.0 * 15.0 / 16.0)
+        } else {
+            10f64.powf(3.0 * (t - 1.0))
+        };
+        let actual = audio_taper(Poui(x)).0 as f64 / 4294967296.0;
+        assert!((actual - expected).abs() < 2e-9, "{x}");
+    }
+}
+
+#[test]
+fn audio_taper_is_monotone_and_invertible() {
+    let mut previous = Poui(0u16);
+    for x in 0..=u16::MAX {
+        let gain = audio_taper(Poui(x));
+        assert!(gain >= previous, "{x}");
+        previous = gain;
+    }
+    for x in (0..=u32::MAX).step_by(0x0123_4567) {
+        let back = audio_taper_inverse(audio_taper(Poui(x)));
+        assert!(back.0.abs_diff(x) <= 0x40, "{x} {back:?}");
+    }
+}
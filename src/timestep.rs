@@ -0,0 +1,106 @@
+use crate::tween::fraction;
+use crate::Poui;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+/// A fixed-timestep accumulator for game loops.
+///
+/// Simulations are most stable when stepped by a fixed amount of time, but
+/// frames take however long they take. Each frame, [`FixedTimestep::advance`]
+/// adds the frame time to an accumulator and returns how many whole steps to
+/// simulate; whatever is left over, as a fraction of a step, is the
+/// [`alpha`](FixedTimestep::alpha) to blend the previous and current
+/// simulation states with when rendering. Time is kept in whole nanoseconds,
+/// so nothing drifts however long the loop runs.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::FixedTimestep;
+/// use poui::Poui;
+/// use std::time::Duration;
+///
+/// let mut timestep = FixedTimestep::<u8>::new(Duration::from_millis(10));
+/// assert_eq!(timestep.advance(Duration::from_millis(25)), 2);
+/// assert_eq!(timestep.alpha(), Poui(128));
+/// assert_eq!(timestep.advance(Duration::from_millis(5)), 1);
+/// assert_eq!(timestep.alpha(), Poui(0));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FixedTimestep<N> {
+    step: Duration,
+    accumulator: Duration,
+    alpha: PhantomData<N>,
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> FixedTimestep<N> {
+    /// An empty accumulator for steps of length `step`.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `step` is zero.
+    pub fn new(step: Duration) -> Self {
+        assert!(!step.is_zero(), "timestep must not be zero");
+        FixedTimestep {
+            step,
+            accumulator: Duration::ZERO,
+            alpha: PhantomData,
+        }
+    }
+
+    /// The length of each step.
+    pub fn step(&self) -> Duration {
+        self.step
+    }
+
+    /// The accumulated time not yet simulated, less than one step.
+    pub fn accumulated(&self) -> Duration {
+        self.accumulator
+    }
+
+    /// Adds `dt` to the accumulator, and returns the number of whole steps
+    /// to simulate.
+    pub fn advance(&mut self, dt: Duration) -> u64 {
+        let step = self.step.as_nanos();
+        let total = self.accumulator.as_nanos() + dt.as_nanos();
+        let left = total % step;
+        self.accumulator = Duration::from_nanos(left as u64);
+        u64::try_from(total / step).unwrap_or(u64::MAX)
+    }
+
+    /// The fraction of a step accumulated but not yet simulated, rounded
+    /// down.
+    pub fn alpha(&self) -> Poui<N> {
+        fraction(self.accumulator.as_nanos(), self.step.as_nanos())
+    }
+
+    /// Discards the accumulated time.
+    pub fn reset(&mut self) {
+        self.accumulator = Duration::ZERO;
+    }
+}
+
+#[test]
+fn fixed_timestep_does_not_drift() {
+    let mut timestep = FixedTimestep::<u32>::new(Duration::from_nanos(16_666_667));
+    let frame = Duration::from_nanos(6_944_444);
+    let steps: u64 = (0..100_000).map(|_| timestep.advance(frame)).sum();
+    let total = frame.as_nanos() * 100_000;
+    assert_eq!(steps as u128, total / 16_666_667);
+    assert_eq!(timestep.accumulated().as_nanos(), total % 16_666_667);
+}
+
+#[test]
+fn fixed_timestep_alpha() {
+    let mut timestep = FixedTimestep::<u16>::new(Duration::from_millis(4));
+    assert_eq!(timestep.advance(Duration::from_millis(3)), 0);
+    assert_eq!(timestep.alpha(), Poui(0xC000));
+    assert_eq!(timestep.advance(Duration::from_secs(1)), 250);
+    assert_eq!(timestep.alpha(), Poui(0xC000));
+    timestep.reset();
+    assert_eq!(timestep.alpha(), Poui(0));
+    assert_eq!(timestep.step(), Duration::from_millis(4));
+}
@@ -0,0 +1,175 @@
+use crate::easing::Easing;
+use crate::Lerp;
+use crate::Poui;
+use crate::StrictWiden;
+use num_traits::AsPrimitive;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+
+/// A value at a point in time on a [`Track`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Keyframe<N: PrimInt + Unsigned + WrappingAdd, V> {
+    /// When the track reaches the value.
+    pub time: Poui<N>,
+    /// The value.
+    pub value: V,
+    /// The easing used on the way from this keyframe to the next one.
+    pub easing: Easing,
+}
+
+/// Animation data: values at given times, interpolated in between.
+///
+/// The keyframes are kept sorted by time. Sampling between two keyframes
+/// eases the local progress through the segment with the first keyframe's
+/// easing, then interpolates the values with [`Lerp`]. Before the first
+/// keyframe and after the last one, the track holds their values.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::easing::Easing;
+/// use poui::Poui;
+/// use poui::Track;
+///
+/// let mut track = Track::new();
+/// track.insert(Poui(0u8), Poui(0u8), Easing::Linear);
+/// track.insert(Poui(128u8), Poui(200u8), Easing::Linear);
+/// track.insert(Poui(192u8), Poui(100u8), Easing::Linear);
+/// assert_eq!(track.sample(Poui(64)), Some(Poui(100)));
+/// assert_eq!(track.sample(Poui(160)), Some(Poui(150)));
+/// assert_eq!(track.sample(Poui(255)), Some(Poui(100)));
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Track<N: PrimInt + Unsigned + WrappingAdd, V> {
+    keyframes: Vec<Keyframe<N, V>>,
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd + 'static, V> Track<N, V> {
+    /// An empty track.
+    pub fn new() -> Self {
+        Track {
+            keyframes: Vec::new(),
+        }
+    }
+
+    /// The keyframes, sorted by time.
+    pub fn keyframes(&self) -> &[Keyframe<N, V>] {
+        &self.keyframes
+    }
+
+    /// The number of keyframes.
+    pub fn len(&self) -> usize {
+        self.keyframes.len()
+    }
+
+    /// Whether the track has no keyframes.
+    pub fn is_empty(&self) -> bool {
+        self.keyframes.is_empty()
+    }
+
+    /// Adds a keyframe, replacing any existing keyframe at the same time.
+    pub fn insert(&mut self, time: Poui<N>, value: V, easing: Easing) {
+        let keyframe = Keyframe {
+            time,
+            value,
+            easing,
+        };
+        match self.keyframes.binary_search_by(|k| k.time.cmp(&time)) {
+            Ok(i) => self.keyframes[i] = keyframe,
+            Err(i) => self.keyframes.insert(i, keyframe),
+        }
+    }
+
+    /// Removes and returns the keyframe at `time`, if there is one.
+    pub fn remove(&mut self, time: Poui<N>) -> Option<Keyframe<N, V>> {
+        let i = self
+            .keyframes
+            .binary_search_by(|k| k.time.cmp(&time))
+            .ok()?;
+        Some(self.keyframes.remove(i))
+    }
+
+    /// The value of the track at time `t`, or `None` if the track is empty.
+    pub fn sample<M>(&self, t: Poui<N>) -> Option<V>
+    where
+        N: StrictWiden<Widened = M>,
+        M: PrimInt + AsPrimitive<N>,
+        V: Lerp<N> + Clone,
+    {
+        let next = self.keyframes.partition_point(|k| k.time <= t);
+        let (from, to) = match next {
+            0 => return self.keyframes.first().map(|k| k.value.clone()),
+            n if n == self.keyframes.len() => return Some(self.keyframes[n - 1].value.clone()),
+            n => (&self.keyframes[n - 1], &self.keyframes[n]),
+        };
+        let local = Poui::from_ratio(t.0 - from.time.0, to.time.0 - from.time.0).unwrap();
+        let eased = from.easing.apply(local);
+        Some(from.value.clone().lerp(to.value.clone(), eased))
+    }
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd + 'static, V> Default for Track<N, V> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd + 'static, V> FromIterator<Keyframe<N, V>>
+    for Track<N, V>
+{
+    fn from_iter<T: IntoIterator<Item = Keyframe<N, V>>>(iter: T) -> Self {
+        let mut track = Track::new();
+        for k in iter {
+            track.insert(k.time, k.value, k.easing);
+        }
+        track
+    }
+}
+
+#[test]
+fn track_holds_outside_keyframes() {
+    let mut track = Track::<u16, Poui<u16>>::new();
+    assert_eq!(track.sample(Poui(0)), None);
+    track.insert(Poui(0x4000), Poui(0x1000), Easing::Linear);
+    assert_eq!(track.sample(Poui(0)), Some(Poui(0x1000)));
+    assert_eq!(track.sample(Poui(u16::MAX)), Some(Poui(0x1000)));
+    track.insert(Poui(0x8000), Poui(0x3000), Easing::Linear);
+    assert_eq!(track.sample(Poui(0x3FFF)), Some(Poui(0x1000)));
+    assert_eq!(track.sample(Poui(0x6000)), Some(Poui(0x2000)));
+    assert_eq!(track.sample(Poui(0x8000)), Some(Poui(0x3000)));
+    assert_eq!(track.sample(Poui(0xF000)), Some(Poui(0x3000)));
+}
+
+#[test]
+fn track_segment_easing() {
+    let track: Track<u16, Poui<u16>> = [
+        Keyframe {
+            time: Poui(0x8000),
+            value: Poui(0xFFFF),
+            easing: Easing::Linear,
+        },
+        Keyframe {
+            time: Poui(0),
+            value: Poui(0),
+            easing: Easing::QuadIn,
+        },
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(track.keyframes()[0].time, Poui(0));
+    let quarter = track.sample(Poui(0x4000)).unwrap();
+    assert!(quarter.0.abs_diff(0x4000) <= 1, "{quarter:?}");
+}
+
+#[test]
+fn track_insert_replaces_and_remove() {
+    let mut track = Track::<u8, Poui<u8>>::new();
+    track.insert(Poui(10), Poui(1), Easing::Linear);
+    track.insert(Poui(10), Poui(2), Easing::Linear);
+    assert_eq!(track.len(), 1);
+    assert_eq!(track.sample(Poui(10)), Some(Poui(2)));
+    assert_eq!(track.remove(Poui(11)), None);
+    assert_eq!(track.remove(Poui(10)).map(|k| k.value), Some(Poui(2)));
+    assert!(track.is_empty());
+}
@@ -0,0 +1,496 @@
+use crate::frac_bits;
+use crate::Poui;
+use crate::ToSigned;
+use crate::ToUnsigned;
+use num_traits::AsPrimitive;
+use num_traits::PrimInt;
+use num_traits::Signed;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+
+/// Number of CORDIC iterations, one per fractional bit of the working format.
+const ITERATIONS: usize = 62;
+
+/// `atan(2^-i)` as a fraction of a turn, scaled by `2^64`.
+const ATAN_TURNS: [u64; ITERATIONS] = [
+    0x2000000000000000,
+    0x12e4051d9df30866,
+    0x09fb385b5ee39e8e,
+    0x051111d41ddd9a1b,
+    0x028b0d430e589aed,
+    0x0145d7e159046278,
+    0x00a2f61e5c28262a,
+    0x00517c5511d442af,
+    0x0028be5346d0c337,
+    0x00145f2ebb30ab38,
+    0x000a2f980091ba7b,
+    0x000517cc14a80cb7,
+    0x00028be60cdfec62,
+    0x000145f306c172f2,
+    0x0000a2f9836ae911,
+    0x0000517cc1b6ba7c,
+    0x000028be60db85fc,
+    0x0000145f306dc816,
+    0x00000a2f9836e4ae,
+    0x00000517cc1b726b,
+    0x0000028be60db938,
+    0x00000145f306dc9c,
+    0x000000a2f9836e4e,
+    0x000000517cc1b727,
+    0x00000028be60db94,
+    0x000000145f306dca,
+    0x0000000a2f9836e5,
+    0x0000000517cc1b72,
+    0x000000028be60db9,
+    0x0000000145f306dd,
+    0x00000000a2f9836e,
+    0x00000000517cc1b7,
+    0x0000000028be60dc,
+    0x00000000145f306e,
+    0x000000000a2f9837,
+    0x000000000517cc1b,
+    0x00000000028be60e,
+    0x000000000145f307,
+    0x0000000000a2f983,
+    0x0000000000517cc2,
+    0x000000000028be61,
+    0x0000000000145f30,
+    0x00000000000a2f98,
+    0x00000000000517cc,
+    0x0000000000028be6,
+    0x00000000000145f3,
+    0x000000000000a2fa,
+    0x000000000000517d,
+    0x00000000000028be,
+    0x000000000000145f,
+    0x0000000000000a30,
+    0x0000000000000518,
+    0x000000000000028c,
+    0x0000000000000146,
+    0x00000000000000a3,
+    0x0000000000000051,
+    0x0000000000000029,
+    0x0000000000000014,
+    0x000000000000000a,
+    0x0000000000000005,
+    0x0000000000000003,
+    0x0000000000000001,
+];
+
+/// The inverse of the CORDIC gain over all iterations, in Q62.
+const INV_GAIN: i64 = 2800459870029452954;
+
+/// One in the Q62 working format.
+const ONE: i64 = 1 << 62;
+
+/// Left-aligns an unsigned angle so that a full turn is `2^64`.
+pub(crate) fn turn_bits<N: PrimInt + Unsigned>(n: N) -> u64 {
+    let bits = frac_bits::<N>();
+    ((n.to_u128().unwrap() << (128 - bits)) >> 64) as u64
+}
+
+/// Computes `(cos, sin)` of an angle in turns, scaled by `2^64`, in Q62.
+///
+/// This is a `const fn` so that lookup tables can be generated at compile time.
+pub(crate) const fn cordic_cos_sin(turn: u64) -> (i64, i64) {
+    let quadrant = turn >> 62;
+    let mut z = (turn & (u64::MAX >> 2)) as i64;
+    let (mut x, mut y) = if z == 0 { (ONE, 0) } else { (INV_GAIN, 0) };
+    if z != 0 {
+        let mut i = 0;
+        while i < ITERATIONS {
+            let atan = ATAN_TURNS[i];
+            let (dx, dy) = (y >> i, x >> i);
+            if z >= 0 {
+                x -= dx;
+                y += dy;
+                z -= atan as i64;
+            } else {
+                x += dx;
+                y -= dy;
+                z += atan as i64;
+            }
+            i += 1;
+        }
+    }
+    match quadrant {
+        0 => (x, y),
+        1 => (-y, x),
+        2 => (-x, -y),
+        _ => (y, -x),
+    }
+}
+
+/// Rounds a Q62 value to the nearest signed `Poui`, saturating at 1.
+pub(crate) fn from_q62<S>(v: i64) -> Poui<S>
+where
+    S: PrimInt + WrappingAdd + 'static,
+    i128: AsPrimitive<S>,
+{
+    Poui(round_q62(v, frac_bits::<S>()).as_())
+}
+
+/// Rounds a Q62 value to `bits` fractional bits, saturating below 1.
+pub(crate) const fn round_q62(v: i64, bits: usize) -> i128 {
+    let v = v as i128;
+    let scaled = if bits < 62 {
+        let shift = 62 - bits;
+        (v + (1 << (shift - 1))) >> shift
+    } else {
+        v << (bits - 62)
+    };
+    let max = i128::MAX >> (127 - bits);
+    if scaled > max {
+        max
+    } else if scaled < -max - 1 {
+        -max - 1
+    } else {
+        scaled
+    }
+}
+
+/// Scales the vector `(x, y)` so that its larger component has its top bit in
+/// bit 60, leaving headroom for the CORDIC gain.
+pub(crate) fn normalize(y: i128, x: i128) -> (i64, i64) {
+    let magnitude = y.unsigned_abs().max(x.unsigned_abs());
+    if magnitude == 0 {
+        return (0, 0);
+    }
+    let top = 127 - magnitude.leading_zeros();
+    let scale = |v: i128| {
+        (if top > 60 {
+            v >> (top - 60)
+        } else {
+            v << (60 - top)
+        }) as i64
+    };
+    (scale(y), scale(x))
+}
+
+/// Computes the angle of the vector `(x, y)` in turns, scaled by `2^64`.
+pub(crate) fn cordic_atan2(y: i64, x: i64) -> u64 {
+    if y == 0 {
+        return if x < 0 { 1 << 63 } else { 0 };
+    }
+    if x == 0 {
+        return if y > 0 { 1 << 62 } else { 3 << 62 };
+    }
+    let (mut x, mut y, mut z) = if x < 0 {
+        (-x, -y, 1u64 << 63)
+    } else {
+        (x, y, 0)
+    };
+    for (i, &atan) in ATAN_TURNS.iter().enumerate() {
+        let (dx, dy) = (y >> i, x >> i);
+        if y > 0 {
+            x += dx;
+            y -= dy;
+            z = z.wrapping_add(atan);
+        } else {
+            x -= dx;
+            y += dy;
+            z = z.wrapping_sub(atan);
+        }
+    }
+    z
+}
+
+/// Rounds an angle scaled by `2^64` to the nearest unsigned `Poui`, wrapping.
+pub(crate) fn from_turn_bits<N: PrimInt + Unsigned>(z: u64) -> N {
+    let bits = frac_bits::<N>();
+    let z = (z as u128) << 64;
+    let rounded = if bits < 128 {
+        z.wrapping_add(1 << (127 - bits)) >> (128 - bits)
+    } else {
+        z
+    };
+    N::from(rounded).unwrap()
+}
+
+impl<N, S> Poui<N>
+where
+    N: PrimInt + Unsigned + WrappingAdd + ToSigned<Signed = S>,
+    S: PrimInt + WrappingAdd + 'static,
+    i128: AsPrimitive<S>,
+{
+    /// The sine of the angle, interpreting `self` as a fraction of a turn.
+    ///
+    /// This uses a fixed-point CORDIC rotation with no lookup beyond a small
+    /// table of arctangents and no floating point, so it is suitable for
+    /// targets without an FPU. The result is computed with 62 fractional bits
+    /// and rounded to the signed backing type, saturating at the top of the
+    /// range since 1 is not representable. The error is within one ULP for
+    /// backings of up to 32 bits and a few ULPs for 64-bit backings.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui(0u8).sin(), Poui(0i8));
+    /// assert_eq!(Poui(64u8).sin(), Poui(i8::MAX));
+    /// assert_eq!(Poui(0x1555u16).sin(), Poui(0x3FFFi16)); // sin(30°) ≈ 0.5
+    /// assert_eq!(Poui(0xC000u16).sin(), Poui(i16::MIN));
+    /// ```
+    pub fn sin(self) -> Poui<S> {
+        from_q62(cordic_cos_sin(turn_bits(self.0)).1)
+    }
+
+    /// The cosine of the angle, interpreting `self` as a fraction of a turn.
+    ///
+    /// See [`Poui::sin`] for details of the computation.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui(0u8).cos(), Poui(i8::MAX));
+    /// assert_eq!(Poui(64u8).cos(), Poui(0i8));
+    /// assert_eq!(Poui(128u8).cos(), Poui(i8::MIN));
+    /// ```
+    pub fn cos(self) -> Poui<S> {
+        from_q62(cordic_cos_sin(turn_bits(self.0)).0)
+    }
+
+    /// The sine and cosine of the angle, computed together.
+    ///
+    /// This costs the same as either [`Poui::sin`] or [`Poui::cos`] alone,
+    /// since CORDIC produces both components of the rotated vector at once.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// let (sin, cos) = Poui(0x2000u16).sin_cos();
+    /// assert_eq!(sin, cos);
+    /// assert_eq!((sin, cos), (Poui(0x2000u16).sin(), Poui(0x2000u16).cos()));
+    /// ```
+    pub fn sin_cos(self) -> (Poui<S>, Poui<S>) {
+        let (cos, sin) = cordic_cos_sin(turn_bits(self.0));
+        (from_q62(sin), from_q62(cos))
+    }
+
+    /// The angle of the vector `(x, y)` as a fraction of a turn, measured
+    /// anticlockwise from the positive x axis.
+    ///
+    /// Like [`f64::atan2`], the arguments are in the order `y`, then `x`.
+    /// This uses CORDIC in vectoring mode and no floating point. Only the
+    /// direction of the vector matters, so the components needn't be
+    /// normalized. The angle of `(0, 0)` is 0.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui::<u8>::atan2(Poui(0i8), Poui(100i8)), Poui(0u8));
+    /// assert_eq!(Poui::<u8>::atan2(Poui(50i8), Poui(50i8)), Poui(32u8));
+    /// assert_eq!(Poui::<u8>::atan2(Poui(-1i8), Poui(0i8)), Poui(192u8));
+    ///
+    /// let angle = Poui(0x1234_5678u32);
+    /// let (sin, cos) = angle.sin_cos();
+    /// assert_eq!(Poui::atan2(sin, cos), angle);
+    /// ```
+    pub fn atan2(y: Poui<S>, x: Poui<S>) -> Self {
+        let (y, x) = normalize(y.0.to_i128().unwrap(), x.0.to_i128().unwrap());
+        Poui(from_turn_bits(cordic_atan2(y, x)))
+    }
+}
+
+/// Converts a signed `Poui` to Q62, truncating 128-bit backings.
+fn to_q62<S: PrimInt + WrappingAdd>(v: Poui<S>) -> i64 {
+    let bits = frac_bits::<S>();
+    let v = v.0.to_i128().unwrap();
+    (if bits <= 62 {
+        v << (62 - bits)
+    } else {
+        v >> (bits - 62)
+    }) as i64
+}
+
+/// Computes `sqrt(1 - v^2)` in Q62 for `v` in Q62.
+fn complement_q62(v: i64) -> i128 {
+    let square = (v as i128 * v as i128) as u128;
+    ((1u128 << 124) - square).isqrt() as i128
+}
+
+impl<S, N> Poui<S>
+where
+    S: PrimInt + Signed + WrappingAdd + ToUnsigned<Unsigned = N>,
+    N: PrimInt + Unsigned + WrappingAdd,
+{
+    /// The arcsine of `self` as a fraction of a turn.
+    ///
+    /// The result is in [-1/4, 1/4] turns, with negative angles wrapped into
+    /// the top of the unsigned range as usual for `Poui` angles. The input
+    /// can't be exactly 1, so the largest result is just short of a quarter
+    /// turn.
+    ///
+    /// This is computed without floating point, as the CORDIC angle of the
+    /// vector `(sqrt(1 - v^2), v)`. For backings of up to 32 bits the result
+    /// is within 1 ULP of the exact arcsine of the input. Error in the
+    /// intermediate square root is below 2^-62, so wider backings lose
+    /// accuracy near the ends of the range, where the arcsine is steepest.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui(0i16).asin(), Poui(0u16));
+    /// assert_eq!(Poui(0x4000i16).asin(), Poui(0x1555u16)); // 30°
+    /// assert_eq!(Poui(i16::MIN).asin(), Poui(0xC000u16));
+    /// ```
+    pub fn asin(self) -> Poui<N> {
+        let v = to_q62(self);
+        let (y, x) = normalize(v as i128, complement_q62(v));
+        Poui(from_turn_bits(cordic_atan2(y, x)))
+    }
+
+    /// The arccosine of `self` as a fraction of a turn.
+    ///
+    /// The result is in [0, 1/2] turns. See [`Poui::asin`] for the method and
+    /// accuracy.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui(0i16).acos(), Poui(0x4000u16));
+    /// assert_eq!(Poui(0x4000i16).acos(), Poui(0x2AABu16)); // 60°
+    /// assert_eq!(Poui(i16::MIN).acos(), Poui(0x8000u16));
+    /// ```
+    pub fn acos(self) -> Poui<N> {
+        let v = to_q62(self);
+        let (y, x) = normalize(complement_q62(v), v as i128);
+        Poui(from_turn_bits(cordic_atan2(y, x)))
+    }
+}
+
+#[test]
+fn sin_cos_match_float_u16() {
+    for n in (0..=u16::MAX).step_by(97) {
+        let angle = n as f64 / 65536.0 * std::f64::consts::TAU;
+        let expected = |v: f64| (v * 32768.0).round().clamp(-32768.0, 32767.0) as i16;
+        let sin = Poui(n).sin().0;
+        let cos = Poui(n).cos().0;
+        assert!(
+            (sin as i32 - expected(angle.sin()) as i32).abs() <= 1,
+            "sin {n}"
+        );
+        assert!(
+            (cos as i32 - expected(angle.cos()) as i32).abs() <= 1,
+            "cos {n}"
+        );
+    }
+}
+
+#[test]
+fn sin_cos_match_float_u32() {
+    for k in 0..=1000u64 {
+        let n = (k * (u32::MAX as u64) / 1000) as u32;
+        let angle = n as f64 / 4294967296.0 * std::f64::consts::TAU;
+        let sin = Poui(n).sin().0 as f64 / 2147483648.0;
+        let cos = Poui(n).cos().0 as f64 / 2147483648.0;
+        assert!((sin - angle.sin()).abs() < 1e-9, "sin {n}");
+        assert!((cos - angle.cos()).abs() < 1e-9, "cos {n}");
+    }
+}
+
+#[test]
+fn sin_cos_axes_are_exact() {
+    assert_eq!(Poui(0u64).sin(), Poui(0i64));
+    assert_eq!(Poui(0u64).cos(), Poui(i64::MAX));
+    assert_eq!(Poui(1u64 << 62).cos(), Poui(0i64));
+    assert_eq!(Poui(1u64 << 63).sin(), Poui(0i64));
+    assert_eq!(Poui(1u64 << 63).cos(), Poui(i64::MIN));
+    assert_eq!(Poui(3u128 << 126).sin(), Poui(i128::MIN));
+}
+
+#[test]
+fn sin_cos_agrees_with_separate_calls() {
+    for n in (0..=u32::MAX).step_by(0x0100_0001) {
+        assert_eq!(Poui(n).sin_cos(), (Poui(n).sin(), Poui(n).cos()));
+    }
+}
+
+#[test]
+fn atan2_matches_float() {
+    for k in 0..1000 {
+        let angle = k as f64 / 1000.0 * std::f64::consts::TAU;
+        let r = 0.3 + (k % 7) as f64 / 10.0;
+        let y = Poui((angle.sin() * r * 32768.0) as i16);
+        let x = Poui((angle.cos() * r * 32768.0) as i16);
+        let expected = (y.0 as f64)
+            .atan2(x.0 as f64)
+            .rem_euclid(std::f64::consts::TAU);
+        let expected = (expected / std::f64::consts::TAU * 65536.0).round() as i64 as u16;
+        let actual = Poui::<u16>::atan2(y, x).0;
+        assert!(
+            actual.wrapping_sub(expected) as i16 == 0,
+            "{k}: {actual} {expected}"
+        );
+    }
+}
+
+#[test]
+fn atan2_inverts_sin_cos() {
+    for n in (0..=u32::MAX).step_by(0x0101_0101) {
+        let (sin, cos) = Poui(n).sin_cos();
+        let delta = Poui::<u32>::atan2(sin, cos).0.wrapping_sub(n) as i32;
+        assert!(delta.abs() <= 1, "{n}");
+    }
+}
+
+#[test]
+fn atan2_axes_are_exact() {
+    assert_eq!(Poui::<u64>::atan2(Poui(0i64), Poui(0i64)), Poui(0));
+    assert_eq!(Poui::<u64>::atan2(Poui(0i64), Poui(-5i64)), Poui(1 << 63));
+    assert_eq!(
+        Poui::<u64>::atan2(Poui(i64::MIN), Poui(0i64)),
+        Poui(3 << 62)
+    );
+    assert_eq!(
+        Poui::<u128>::atan2(Poui(1i128), Poui(0i128)),
+        Poui(1 << 126)
+    );
+}
+
+#[test]
+fn asin_acos_within_one_ulp_i16() {
+    let turns = |radians: f64| radians.rem_euclid(std::f64::consts::TAU) / std::f64::consts::TAU;
+    for n in i16::MIN..=i16::MAX {
+        let v = n as f64 / 32768.0;
+        for (actual, expected) in [
+            (Poui(n).asin().0, turns(v.asin())),
+            (Poui(n).acos().0, turns(v.acos())),
+        ] {
+            let error = (actual as f64 - expected * 65536.0).abs();
+            assert!(
+                error.min(65536.0 - error) <= 1.0,
+                "{n}: {actual} {expected}"
+            );
+        }
+    }
+}
+
+#[test]
+fn asin_acos_within_one_ulp_i32() {
+    let turns = |radians: f64| radians.rem_euclid(std::f64::consts::TAU) / std::f64::consts::TAU;
+    for n in (i32::MIN..=i32::MAX).step_by(0x0010_0001) {
+        let v = n as f64 / 2147483648.0;
+        for (actual, expected) in [
+            (Poui(n).asin().0, turns(v.asin())),
+            (Poui(n).acos().0, turns(v.acos())),
+        ] {
+            let error = (actual as f64 - expected * 4294967296.0).abs();
+            assert!(
+                error.min(4294967296.0 - error) <= 1.0,
+                "{n}: {actual} {expected}"
+            );
+        }
+    }
+}
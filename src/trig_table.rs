@@ -0,0 +1,136 @@
+use crate::trig::cordic_cos_sin;
+use crate::trig::round_q62;
+use crate::Poui;
+use num_traits::Num;
+use num_traits::WrappingAdd;
+
+/// A lookup table of sines, generated at compile time.
+///
+/// The table holds `SIZE` evenly spaced samples of one cycle of the sine,
+/// computed with the same CORDIC routine as [`Poui::sin`], and lookups
+/// interpolate linearly between adjacent samples with [`Poui::lerp`]. This
+/// trades a little accuracy and `SIZE` entries of memory for a lookup that
+/// costs one multiplication, for hot paths such as audio synthesis or LED
+/// animation on microcontrollers.
+///
+/// Linear interpolation of a sine sampled `SIZE` times per cycle is accurate
+/// to within `(π / SIZE)² / 2`, e.g. about `7.5e-5` for 256 entries.
+///
+/// `SIZE` must be a power of two, and no larger than the number of angles the
+/// input type can represent. Violating this is a compile error when the table
+/// is built in a `const`, and a panic otherwise.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Poui;
+/// use poui::TrigTable;
+///
+/// static SINE: TrigTable<256, i16> = TrigTable::<256, i16>::new();
+///
+/// assert_eq!(SINE.sin(Poui(0u16)), Poui(0i16));
+/// assert_eq!(SINE.cos(Poui(0u16)), Poui(i16::MAX));
+/// let angle = Poui(0x1234u16);
+/// assert!((SINE.sin(angle).0 - angle.sin().0).abs() <= 3);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TrigTable<const SIZE: usize, N: Num + WrappingAdd> {
+    sin: [Poui<N>; SIZE],
+}
+
+/// Implements table generation and lookups for each signed backing and the
+/// unsigned backing of the same width used for angles.
+macro_rules! impl_trig_table {
+    ($($s:ty => $u:ty),*) => {
+        $(
+            impl<const SIZE: usize> TrigTable<SIZE, $s> {
+                const INDEX_BITS: u32 = SIZE.trailing_zeros();
+
+                /// Generates the table.
+                pub const fn new() -> Self {
+                    assert!(SIZE.is_power_of_two(), "table size must be a power of two");
+                    assert!(Self::INDEX_BITS <= <$u>::BITS, "table larger than the angle type");
+                    let mut sin = [Poui(0); SIZE];
+                    let mut i = 0;
+                    while i < SIZE {
+                        let turn = ((i as u128) << (128 - Self::INDEX_BITS)) >> 64;
+                        let (_, v) = cordic_cos_sin(turn as u64);
+                        sin[i] = Poui(round_q62(v, <$s>::BITS as usize - 1) as $s);
+                        i += 1;
+                    }
+                    TrigTable { sin }
+                }
+
+                /// The sine of `angle`, interpreted as a fraction of a turn.
+                pub fn sin(&self, angle: Poui<$u>) -> Poui<$s> {
+                    let index = (angle.0 as u128 >> (<$u>::BITS - Self::INDEX_BITS)) as usize;
+                    let rest = (angle.0 as u128)
+                        .checked_shl(128 - <$u>::BITS + Self::INDEX_BITS)
+                        .unwrap_or(0);
+                    let t = Poui((rest >> (129 - <$s>::BITS)) as $s);
+                    let a = self.sin[index];
+                    let b = self.sin[(index + 1) % SIZE];
+                    a.lerp(b, t)
+                }
+
+                /// The cosine of `angle`, interpreted as a fraction of a turn.
+                pub fn cos(&self, angle: Poui<$u>) -> Poui<$s> {
+                    self.sin(angle.wrapping_add(Poui(1 << (<$u>::BITS - 2))))
+                }
+
+                /// The sine and cosine of `angle`.
+                pub fn sin_cos(&self, angle: Poui<$u>) -> (Poui<$s>, Poui<$s>) {
+                    (self.sin(angle), self.cos(angle))
+                }
+            }
+
+            impl<const SIZE: usize> Default for TrigTable<SIZE, $s> {
+                fn default() -> Self {
+                    Self::new()
+                }
+            }
+        )*
+    };
+}
+
+impl_trig_table!(i8 => u8, i16 => u16, i32 => u32, i64 => u64);
+
+#[test]
+fn trig_table_entries_match_cordic() {
+    let table = TrigTable::<64, i32>::new();
+    for i in 0..64u32 {
+        let angle = Poui(i << 26);
+        assert_eq!(table.sin(angle), angle.sin());
+        assert_eq!(table.cos(angle), angle.cos());
+    }
+}
+
+#[test]
+fn trig_table_interpolation_error_is_bounded() {
+    const TABLE: TrigTable<256, i32> = TrigTable::<256, i32>::new();
+    let bound = (std::f64::consts::PI / 256.0).powi(2) / 2.0 + 1e-9;
+    for n in (0..=u32::MAX).step_by(0x0001_2345) {
+        let exact = (n as f64 / 4294967296.0 * std::f64::consts::TAU).sin();
+        let (sin, cos) = TABLE.sin_cos(Poui(n));
+        let exact_cos = (n as f64 / 4294967296.0 * std::f64::consts::TAU).cos();
+        assert!((sin.0 as f64 / 2147483648.0 - exact).abs() < bound, "{n}");
+        assert!(
+            (cos.0 as f64 / 2147483648.0 - exact_cos).abs() < bound,
+            "{n}"
+        );
+    }
+}
+
+#[test]
+fn trig_table_full_size() {
+    let table = TrigTable::<256, i8>::new();
+    for n in 0..=u8::MAX {
+        assert_eq!(table.sin(Poui(n)), Poui(n).sin());
+    }
+}
+
+#[test]
+#[should_panic]
+fn trig_table_size_must_be_power_of_two() {
+    TrigTable::<100, i16>::new();
+}
@@ -0,0 +1,199 @@
+use crate::Mixed;
+use crate::Poui;
+use num_traits::AsPrimitive;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+use num_traits::WrappingSub;
+
+/// An angle that keeps count of complete revolutions.
+///
+/// A `Poui` is a good representation of an angle as a fraction of a turn, but
+/// it forgets how many complete turns have gone by. `Turns` pairs the `Poui`
+/// phase with an `i64` count of whole turns, so that e.g. a motor position or
+/// an oscillator phase can be accumulated without losing track of where it
+/// is.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Poui;
+/// use poui::Turns;
+///
+/// let mut angle = Turns::new(0, Poui(0xC000u16));
+/// angle.advance(Poui(0x8000u16));
+/// assert_eq!(angle.turns(), 1);
+/// assert_eq!(angle.phase(), Poui(0x4000u16));
+/// assert_eq!(angle.to_f64(), 1.25);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Turns<N: Unsigned + WrappingAdd>(Mixed<i64, N>);
+
+impl<N: PrimInt + Unsigned + WrappingAdd + WrappingSub> Turns<N> {
+    /// Creates an angle of `turns` complete turns plus `phase`.
+    pub fn new(turns: i64, phase: Poui<N>) -> Self {
+        Turns(Mixed::new(turns, phase))
+    }
+
+    /// Creates an angle within the first turn.
+    pub fn from_phase(phase: Poui<N>) -> Self {
+        Turns::new(0, phase)
+    }
+
+    /// The number of complete turns, rounded towards negative infinity.
+    pub fn turns(self) -> i64 {
+        self.0.int
+    }
+
+    /// The position within the current turn.
+    pub fn phase(self) -> Poui<N> {
+        self.0.frac
+    }
+
+    /// The angle as a mixed number of turns.
+    pub fn to_mixed(self) -> Mixed<i64, N> {
+        self.0
+    }
+
+    /// Moves the angle forwards by `delta`, counting a turn if the phase wraps
+    /// around.
+    pub fn advance(&mut self, delta: Poui<N>) {
+        self.0 = self.0 + delta;
+    }
+
+    /// Moves the angle backwards by `delta`, uncounting a turn if the phase
+    /// wraps around.
+    pub fn retreat(&mut self, delta: Poui<N>) {
+        self.0 = self.0 - delta;
+    }
+
+    /// Updates the angle from a new wrapped reading of the phase, e.g. from an
+    /// absolute encoder, and returns the updated angle.
+    ///
+    /// The angle is assumed to have moved by less than half a turn since the
+    /// previous reading, so it moves to whichever point with the phase
+    /// `reading` is closest. This is the standard phase-unwrapping rule: if the
+    /// readings are taken often enough, the count of whole turns is tracked
+    /// exactly.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    /// use poui::Turns;
+    ///
+    /// let mut angle = Turns::from_phase(Poui(240u8));
+    /// assert_eq!(angle.track(Poui(16u8)), Turns::new(1, Poui(16u8)));
+    /// assert_eq!(angle.track(Poui(240u8)), Turns::new(0, Poui(240u8)));
+    /// ```
+    pub fn track(&mut self, reading: Poui<N>) -> Self {
+        let phase = self.phase().0;
+        let delta = reading.0.wrapping_sub(&phase);
+        let half = N::one() << (N::zero().count_zeros() as usize - 1);
+        let turns = if delta < half && reading.0 < phase {
+            self.turns() + 1
+        } else if delta >= half && reading.0 > phase {
+            self.turns() - 1
+        } else {
+            self.turns()
+        };
+        *self = Turns::new(turns, reading);
+        *self
+    }
+
+    /// Unwraps a stream of phase readings into a stream of angles, starting
+    /// from the first reading within the first turn.
+    ///
+    /// See [`Turns::track`] for the unwrapping rule.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    /// use poui::Turns;
+    ///
+    /// let readings = [200u8, 10, 80, 170, 250, 40].map(Poui);
+    /// let turns: Vec<i64> = Turns::unwrap(readings).map(Turns::turns).collect();
+    /// assert_eq!(turns, [0, 1, 1, 1, 1, 2]);
+    /// ```
+    pub fn unwrap<T: IntoIterator<Item = Poui<N>>>(readings: T) -> impl Iterator<Item = Self> {
+        let mut state: Option<Self> = None;
+        readings.into_iter().map(move |reading| match &mut state {
+            Some(angle) => angle.track(reading),
+            None => *state.insert(Turns::from_phase(reading)),
+        })
+    }
+
+    /// The total angle in turns, as a float.
+    pub fn to_f64(self) -> f64
+    where
+        N: AsPrimitive<f64>,
+    {
+        self.0.as_()
+    }
+
+    /// The total angle in radians.
+    pub fn to_radians(self) -> f64
+    where
+        N: AsPrimitive<f64>,
+    {
+        self.to_f64() * std::f64::consts::TAU
+    }
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd + WrappingSub> From<Mixed<i64, N>> for Turns<N> {
+    fn from(mixed: Mixed<i64, N>) -> Self {
+        Turns(mixed)
+    }
+}
+
+#[test]
+fn turns_advance_and_retreat() {
+    let mut angle = Turns::from_phase(Poui(200u8));
+    angle.advance(Poui(100u8));
+    assert_eq!(angle, Turns::new(1, Poui(44u8)));
+    angle.retreat(Poui(50u8));
+    assert_eq!(angle, Turns::new(0, Poui(250u8)));
+    angle.retreat(Poui(251u8));
+    assert_eq!(angle, Turns::new(-1, Poui(255u8)));
+}
+
+#[test]
+fn turns_track_forward_and_backward() {
+    let mut angle = Turns::from_phase(Poui(0u16));
+    for _ in 0..10 {
+        angle.track(angle.phase() + Poui(0x7000u16));
+    }
+    assert_eq!(angle, Turns::new(4, Poui(0x6000u16)));
+    for _ in 0..20 {
+        let phase = angle.phase().0.wrapping_sub(0x7000);
+        angle.track(Poui(phase));
+    }
+    assert_eq!(angle, Turns::new(-5, Poui(0xA000u16)));
+}
+
+#[test]
+fn turns_unwrap_stream() {
+    let readings = [0xF0u8, 0x10, 0xF0, 0xE0, 0x80, 0x10].map(Poui);
+    let angles: Vec<_> = Turns::unwrap(readings).collect();
+    assert_eq!(
+        angles,
+        [
+            Turns::new(0, Poui(0xF0)),
+            Turns::new(1, Poui(0x10)),
+            Turns::new(0, Poui(0xF0)),
+            Turns::new(0, Poui(0xE0)),
+            Turns::new(0, Poui(0x80)),
+            Turns::new(0, Poui(0x10)),
+        ]
+    );
+}
+
+#[test]
+fn turns_to_float() {
+    assert_eq!(Turns::new(-2, Poui(0x4000_0000u32)).to_f64(), -1.75);
+    assert_eq!(
+        Turns::new(3, Poui(0u64)).to_radians(),
+        3.0 * std::f64::consts::TAU
+    );
+}
@@ -0,0 +1,281 @@
+use crate::easing::Easing;
+use crate::frac_bits;
+use crate::Poui;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+use std::marker::PhantomData;
+use std::time::Duration;
+
+/// What a [`Tween`] does when it reaches the end of its duration.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum TweenRepeat {
+    /// Stop at the end.
+    #[default]
+    Once,
+    /// Jump back to the start and play again, forever.
+    Loop,
+    /// Play backwards to the start, then forwards again, forever.
+    PingPong,
+}
+
+/// The progress of an animation over time.
+///
+/// A tween runs for a fixed duration, advanced by [`Tween::tick`], and maps
+/// the fraction of the duration elapsed through an [`Easing`] to give the
+/// animation's progress as a `Poui`. Since 1 isn't representable, the
+/// progress at the end of the duration is the largest `Poui`.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::easing::Easing;
+/// use poui::Poui;
+/// use poui::Tween;
+/// use poui::TweenRepeat;
+/// use std::time::Duration;
+///
+/// let mut tween = Tween::<u8>::new(Duration::from_millis(400), Easing::Linear, TweenRepeat::Once);
+/// assert_eq!(tween.tick(Duration::from_millis(100)), Poui(64));
+/// assert_eq!(tween.tick(Duration::from_millis(100)), Poui(128));
+/// assert_eq!(tween.tick(Duration::from_millis(300)), Poui(255));
+/// assert!(tween.is_finished());
+///
+/// let mut ping_pong =
+///     Tween::<u8>::new(Duration::from_secs(1), Easing::Linear, TweenRepeat::PingPong);
+/// assert_eq!(ping_pong.tick(Duration::from_millis(1250)), Poui(192));
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Tween<N> {
+    duration: Duration,
+    easing: Easing,
+    repeat: TweenRepeat,
+    elapsed: Duration,
+    progress: PhantomData<N>,
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Tween<N> {
+    /// A tween at its start, lasting `duration` per play.
+    pub fn new(duration: Duration, easing: Easing, repeat: TweenRepeat) -> Self {
+        Tween {
+            duration,
+            easing,
+            repeat,
+            elapsed: Duration::ZERO,
+            progress: PhantomData,
+        }
+    }
+
+    /// The duration of one play.
+    pub fn duration(&self) -> Duration {
+        self.duration
+    }
+
+    /// The time elapsed since the start.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    /// Whether a tween that plays once has reached the end. Repeating tweens
+    /// never finish.
+    pub fn is_finished(&self) -> bool {
+        self.repeat == TweenRepeat::Once && self.elapsed >= self.duration
+    }
+
+    /// Goes back to the start.
+    pub fn reset(&mut self) {
+        self.elapsed = Duration::ZERO;
+    }
+
+    /// Advances the tween by `dt`, and returns the new progress.
+    pub fn tick(&mut self, dt: Duration) -> Poui<N> {
+        self.elapsed = self.elapsed.saturating_add(dt);
+        self.progress()
+    }
+
+    /// The current progress, after easing.
+    pub fn progress(&self) -> Poui<N> {
+        let duration = self.duration.as_nanos();
+        let elapsed = self.elapsed.as_nanos();
+        let linear = match self.repeat {
+            _ if duration == 0 => Poui(N::max_value()),
+            TweenRepeat::Once if elapsed >= duration => Poui(N::max_value()),
+            TweenRepeat::Once => fraction(elapsed, duration),
+            TweenRepeat::Loop => fraction(elapsed % duration, duration),
+            TweenRepeat::PingPong => match elapsed % (2 * duration) {
+                t if t < duration => fraction(t, duration),
+                t if t == duration => Poui(N::max_value()),
+                t => fraction(2 * duration - t, duration),
+            },
+        };
+        self.easing.apply(linear)
+    }
+
+    /// The time by which a finished tween has overrun its end.
+    fn overrun(&self) -> Option<Duration> {
+        self.is_finished().then(|| self.elapsed - self.duration)
+    }
+}
+
+/// Computes `num / den`, for `num < den`, rounded down to a `Poui`.
+pub(crate) fn fraction<N: PrimInt + Unsigned + WrappingAdd>(num: u128, den: u128) -> Poui<N> {
+    // Durations in nanoseconds fit comfortably in 64 bits, so the quotient
+    // has at least 64 bits of precision.
+    let shift = den.leading_zeros().min(64);
+    let q = (num << shift) / (den >> (64 - shift));
+    let bits = frac_bits::<N>();
+    let raw = if bits <= 64 {
+        q >> (64 - bits)
+    } else {
+        q << (bits - 64)
+    };
+    Poui(N::from(raw).unwrap_or(N::max_value()))
+}
+
+/// A sequence of tweens played one after another.
+///
+/// Time left over when one tween finishes carries over into the next, so
+/// the timeline stays in sync however coarse the ticks are. A tween that
+/// repeats never finishes, so it plays forever once reached.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::easing::Easing;
+/// use poui::Poui;
+/// use poui::Timeline;
+/// use poui::Tween;
+/// use poui::TweenRepeat;
+/// use std::time::Duration;
+///
+/// let fade_in = Tween::<u8>::new(Duration::from_millis(200), Easing::Linear, TweenRepeat::Once);
+/// let hold = Tween::new(Duration::from_millis(100), Easing::Linear, TweenRepeat::Once);
+/// let mut timeline: Timeline<u8> = [fade_in, hold].into_iter().collect();
+/// assert_eq!(timeline.tick(Duration::from_millis(100)), Some((0, Poui(128))));
+/// assert_eq!(timeline.tick(Duration::from_millis(150)), Some((1, Poui(128))));
+/// assert_eq!(timeline.tick(Duration::from_millis(150)), Some((1, Poui(255))));
+/// assert!(timeline.is_finished());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Timeline<N> {
+    tweens: Vec<Tween<N>>,
+    current: usize,
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Timeline<N> {
+    /// An empty timeline.
+    pub fn new() -> Self {
+        Timeline {
+            tweens: Vec::new(),
+            current: 0,
+        }
+    }
+
+    /// Appends a tween to the end of the timeline.
+    pub fn push(&mut self, tween: Tween<N>) {
+        self.tweens.push(tween);
+    }
+
+    /// The tweens in the timeline.
+    pub fn tweens(&self) -> &[Tween<N>] {
+        &self.tweens
+    }
+
+    /// Whether the last tween has finished.
+    pub fn is_finished(&self) -> bool {
+        self.tweens.last().is_none_or(|last| last.is_finished())
+    }
+
+    /// Goes back to the start of the first tween.
+    pub fn reset(&mut self) {
+        self.tweens.iter_mut().for_each(Tween::reset);
+        self.current = 0;
+    }
+
+    /// Advances the timeline by `dt`, and returns the index of the tween now
+    /// playing and its progress, or `None` if the timeline is empty.
+    pub fn tick(&mut self, dt: Duration) -> Option<(usize, Poui<N>)> {
+        let mut dt = dt;
+        let last = self.tweens.len().checked_sub(1)?;
+        loop {
+            let tween = self.tweens.get_mut(self.current)?;
+            tween.tick(dt);
+            match tween.overrun() {
+                Some(overrun) if self.current < last => {
+                    tween.elapsed = tween.duration;
+                    dt = overrun;
+                    self.current += 1;
+                }
+                _ => break,
+            }
+        }
+        self.progress()
+    }
+
+    /// The index of the tween playing and its progress, or `None` if the
+    /// timeline is empty.
+    pub fn progress(&self) -> Option<(usize, Poui<N>)> {
+        let tween = self.tweens.get(self.current)?;
+        Some((self.current, tween.progress()))
+    }
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> FromIterator<Tween<N>> for Timeline<N> {
+    fn from_iter<T: IntoIterator<Item = Tween<N>>>(iter: T) -> Self {
+        Timeline {
+            tweens: iter.into_iter().collect(),
+            current: 0,
+        }
+    }
+}
+
+#[test]
+fn tween_repeat_modes() {
+    let ms = Duration::from_millis;
+    let mut once = Tween::<u16>::new(ms(1000), Easing::Linear, TweenRepeat::Once);
+    let mut looping = Tween::<u16>::new(ms(1000), Easing::Linear, TweenRepeat::Loop);
+    let mut ping_pong = Tween::<u16>::new(ms(1000), Easing::Linear, TweenRepeat::PingPong);
+    for _ in 0..5 {
+        once.tick(ms(250));
+        looping.tick(ms(250));
+        ping_pong.tick(ms(250));
+    }
+    assert_eq!(once.progress(), Poui(u16::MAX));
+    assert_eq!(looping.progress(), Poui(0x4000));
+    assert_eq!(ping_pong.progress(), Poui(0xC000));
+    assert!(once.is_finished() && !looping.is_finished() && !ping_pong.is_finished());
+    once.reset();
+    assert_eq!(once.progress(), Poui(0));
+}
+
+#[test]
+fn tween_easing_and_precision() {
+    let mut tween = Tween::<u32>::new(Duration::from_secs(4), Easing::QuadIn, TweenRepeat::Once);
+    assert_eq!(tween.tick(Duration::from_secs(1)), Poui(1 << 28));
+    let mut tween = Tween::<u32>::new(Duration::from_secs(3), Easing::Linear, TweenRepeat::Once);
+    assert_eq!(tween.tick(Duration::from_secs(1)), Poui(u32::MAX / 3));
+    let zero = Tween::<u8>::new(Duration::ZERO, Easing::Linear, TweenRepeat::Loop);
+    assert_eq!(zero.progress(), Poui(255));
+    let mut long = Tween::<u128>::new(
+        Duration::from_secs(1 << 40),
+        Easing::Linear,
+        TweenRepeat::Once,
+    );
+    assert_eq!(long.tick(Duration::from_secs(1 << 39)), Poui(1 << 127));
+}
+
+#[test]
+fn timeline_carries_over() {
+    let ms = Duration::from_millis;
+    let mut timeline = Timeline::<u8>::new();
+    assert_eq!(timeline.tick(ms(10)), None);
+    assert!(timeline.is_finished());
+    timeline.push(Tween::new(ms(100), Easing::Linear, TweenRepeat::Once));
+    timeline.push(Tween::new(ms(100), Easing::Linear, TweenRepeat::Once));
+    timeline.push(Tween::new(ms(100), Easing::Linear, TweenRepeat::Loop));
+    assert_eq!(timeline.tick(ms(275)), Some((2, Poui(192))));
+    assert_eq!(timeline.tick(ms(50)), Some((2, Poui(64))));
+    assert!(!timeline.is_finished());
+    timeline.reset();
+    assert_eq!(timeline.progress(), Some((0, Poui(0))));
+}
@@ -0,0 +1,194 @@
+use crate::frac_bits;
+use crate::Poui;
+use crate::StrictWiden;
+use crate::ToSigned;
+use crate::ToUnsigned;
+use num_traits::AsPrimitive;
+use num_traits::PrimInt;
+use num_traits::Signed;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+
+/// A point on the unit circle, i.e. a rotation, stored as its cosine and
+/// sine.
+///
+/// Multiplying two points composes their rotations, like multiplying unit
+/// complex numbers, and renormalizes the result so that it stays on the
+/// circle. Repeatedly multiplying by a fixed step therefore gives a rotation
+/// that advances incrementally without evaluating any trigonometry and
+/// without the magnitude drifting away from 1.
+///
+/// Since 1 isn't representable, the identity rotation has a cosine of the
+/// largest `Poui` instead.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Poui;
+/// use poui::UnitCirclePoint;
+///
+/// let step = UnitCirclePoint::<i32>::from_angle(Poui(0x0100_0000));
+/// let mut point = UnitCirclePoint::identity();
+/// for _ in 0..64 {
+///     point *= step;
+/// }
+/// let angle: Poui<u32> = point.angle();
+/// assert!(angle.signed_delta(Poui(0x4000_0000)).0.abs() < 64);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UnitCirclePoint<N: PrimInt + WrappingAdd> {
+    cos: Poui<N>,
+    sin: Poui<N>,
+}
+
+impl<N, M> UnitCirclePoint<N>
+where
+    N: PrimInt + Signed + WrappingAdd + StrictWiden<Widened = M> + 'static,
+    M: PrimInt + AsPrimitive<N>,
+{
+    /// The identity rotation, at angle 0.
+    pub fn identity() -> Self {
+        UnitCirclePoint {
+            cos: Poui(N::max_value()),
+            sin: Poui(N::zero()),
+        }
+    }
+
+    /// The point with the given components, scaled onto the unit circle.
+    ///
+    /// The scaling is a single Newton step, so the components should already
+    /// be close to unit length, e.g. the output of [`Poui::sin_cos`] from
+    /// another source.
+    pub fn new(cos: Poui<N>, sin: Poui<N>) -> Self {
+        Self::renormalize(cos.0.widen(), sin.0.widen())
+    }
+
+    /// The cosine of the rotation.
+    pub fn cos(self) -> Poui<N> {
+        self.cos
+    }
+
+    /// The sine of the rotation.
+    pub fn sin(self) -> Poui<N> {
+        self.sin
+    }
+
+    /// The rotation in the opposite direction.
+    pub fn inverse(self) -> Self {
+        UnitCirclePoint {
+            cos: self.cos,
+            sin: Poui(
+                N::zero()
+                    .saturating_sub(self.sin.0)
+                    .max(N::min_value() + N::one()),
+            ),
+        }
+    }
+
+    /// Scales `(re, im)`, which are in the widened type with the same number
+    /// of fractional bits as `N`, to unit length.
+    fn renormalize(re: M, im: M) -> Self {
+        let f = frac_bits::<N>();
+        let one = M::one() << f;
+        let half = M::one() << (f - 1);
+        // Each square fits, but their sum can overflow when both components
+        // are near -1, so they are halved first.
+        let squared = (((re * re) >> 1) + ((im * im) >> 1) + (half >> 1)) >> (f - 1);
+        let scale = (one + one + one - squared) >> 1;
+        let lo = N::min_value().widen();
+        let hi = N::max_value().widen();
+        let scaled = |v: M| ((v * scale + half) >> f).max(lo).min(hi).as_();
+        UnitCirclePoint {
+            cos: Poui(scaled(re)),
+            sin: Poui(scaled(im)),
+        }
+    }
+}
+
+impl<N, M> UnitCirclePoint<N>
+where
+    N: PrimInt + Signed + WrappingAdd + StrictWiden<Widened = M> + ToUnsigned + 'static,
+    M: PrimInt + AsPrimitive<N>,
+    N::Unsigned: PrimInt + Unsigned + WrappingAdd + ToSigned<Signed = N>,
+    i128: AsPrimitive<N>,
+{
+    /// The point at `angle`, as a fraction of a turn.
+    pub fn from_angle(angle: Poui<N::Unsigned>) -> Self {
+        let (sin, cos) = angle.sin_cos();
+        UnitCirclePoint { cos, sin }
+    }
+
+    /// The angle of the point, as a fraction of a turn.
+    pub fn angle(self) -> Poui<N::Unsigned> {
+        Poui::atan2(self.sin, self.cos)
+    }
+}
+
+impl<N, M> std::ops::Mul for UnitCirclePoint<N>
+where
+    N: PrimInt + Signed + WrappingAdd + StrictWiden<Widened = M> + 'static,
+    M: PrimInt + AsPrimitive<N>,
+{
+    type Output = Self;
+
+    fn mul(self, rhs: Self) -> Self::Output {
+        let f = frac_bits::<N>();
+        let half = M::one() << (f - 1);
+        let (a, b) = (self.cos.0.widen(), self.sin.0.widen());
+        let (c, d) = (rhs.cos.0.widen(), rhs.sin.0.widen());
+        let re = (a * c - b * d + half) >> f;
+        let im = (a * d + b * c + half) >> f;
+        Self::renormalize(re, im)
+    }
+}
+
+impl<N, M> std::ops::MulAssign for UnitCirclePoint<N>
+where
+    N: PrimInt + Signed + WrappingAdd + StrictWiden<Widened = M> + 'static,
+    M: PrimInt + AsPrimitive<N>,
+{
+    fn mul_assign(&mut self, rhs: Self) {
+        *self = *self * rhs;
+    }
+}
+
+#[test]
+fn unit_circle_composes_angles() {
+    let a = UnitCirclePoint::<i32>::from_angle(Poui(0x1000_0000));
+    let b = UnitCirclePoint::<i32>::from_angle(Poui(0x2345_6789));
+    let delta = (a * b).angle().signed_delta(Poui(0x3345_6789)).0;
+    assert!(delta.abs() < 4, "{delta}");
+    let delta = (b * b.inverse()).angle().signed_delta(Poui(0)).0;
+    assert!(delta.abs() < 4, "{delta}");
+}
+
+#[test]
+fn unit_circle_does_not_drift() {
+    let step = UnitCirclePoint::<i16>::from_angle(Poui(0x0101));
+    let mut point = UnitCirclePoint::identity();
+    for k in 1..=100_000u32 {
+        point *= step;
+        if k % 1000 == 0 {
+            let (c, s) = (point.cos().0 as i64, point.sin().0 as i64);
+            let squared = c * c + s * s;
+            assert!((squared - (1 << 30)).abs() < 1 << 18, "{k}: {squared}");
+        }
+    }
+}
+
+#[test]
+fn unit_circle_identity() {
+    let point = UnitCirclePoint::<i8>::from_angle(Poui(100));
+    assert_eq!(point * UnitCirclePoint::identity(), point);
+    assert_eq!(UnitCirclePoint::<i64>::identity().angle(), Poui(0));
+}
+
+#[test]
+fn unit_circle_new_scales_extreme_components() {
+    let point = UnitCirclePoint::<i8>::new(Poui(i8::MIN), Poui(i8::MIN));
+    assert_eq!((point.cos(), point.sin()), (Poui(-64), Poui(-64)));
+    let point = UnitCirclePoint::<i64>::new(Poui(i64::MIN), Poui(i64::MIN));
+    assert_eq!(point.cos(), Poui(i64::MIN / 2));
+    let angle: Poui<u8> = UnitCirclePoint::<i8>::new(Poui(-100), Poui(0)).angle();
+    assert_eq!(angle, Poui(128));
+}
@@ -0,0 +1,207 @@
+use crate::Nco;
+use crate::Poui;
+use crate::ToSigned;
+use num_traits::PrimInt;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Poui<N> {
+    /// The value of a pulse wave with the given duty cycle at phase `self`.
+    ///
+    /// The wave is high for the first `duty` of each cycle and low for the
+    /// rest, so a duty of one half gives a square wave and a duty of 0 is
+    /// always low. Since the comparison is exact, the edges of the wave never
+    /// jitter, which makes this suitable for software PWM.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// let duty = Poui(64u8);
+    /// assert!(Poui(0u8).pulse(duty));
+    /// assert!(Poui(63u8).pulse(duty));
+    /// assert!(!Poui(64u8).pulse(duty));
+    /// assert!(!Poui(255u8).pulse(duty));
+    /// ```
+    pub fn pulse(self, duty: Self) -> bool {
+        self < duty
+    }
+}
+
+impl<N, I> Poui<N>
+where
+    N: PrimInt + Unsigned + WrappingAdd + ToSigned<Signed = I>,
+    I: PrimInt + WrappingAdd,
+{
+    /// The value of a rising sawtooth wave at phase `self`.
+    ///
+    /// The wave rises linearly from -1 at the start of the cycle to just
+    /// below 1 at the end, which is the same as [`Poui::to_signed`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui(0u8).to_sawtooth(), Poui(-128i8));
+    /// assert_eq!(Poui(128u8).to_sawtooth(), Poui(0i8));
+    /// assert_eq!(Poui(255u8).to_sawtooth(), Poui(127i8));
+    /// ```
+    pub fn to_sawtooth(self) -> Poui<I> {
+        self.to_signed()
+    }
+
+    /// The value of a falling sawtooth wave at phase `self`.
+    ///
+    /// The wave falls linearly from just below 1 at the start of the cycle to
+    /// -1 at the end, mirroring [`Poui::to_sawtooth`].
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui(0u8).to_ramp_down(), Poui(127i8));
+    /// assert_eq!(Poui(128u8).to_ramp_down(), Poui(-1i8));
+    /// assert_eq!(Poui(255u8).to_ramp_down(), Poui(-128i8));
+    /// ```
+    pub fn to_ramp_down(self) -> Poui<I> {
+        Poui(!self.0).to_signed()
+    }
+
+    /// The value of a triangle wave at phase `self`.
+    ///
+    /// The wave rises from -1 at the start of the cycle to its peak just
+    /// below 1 halfway through, then falls back, symmetrically about the
+    /// midpoint. Each step of the phase moves the output by two steps, so the
+    /// output only takes even values.
+    ///
+    /// # Examples
+    ///
+    /// ```rust
+    /// use poui::Poui;
+    ///
+    /// assert_eq!(Poui(0u8).to_triangle(), Poui(-128i8));
+    /// assert_eq!(Poui(64u8).to_triangle(), Poui(0i8));
+    /// assert_eq!(Poui(128u8).to_triangle(), Poui(126i8));
+    /// assert_eq!(Poui(192u8).to_triangle(), Poui(-2i8));
+    /// ```
+    pub fn to_triangle(self) -> Poui<I> {
+        let half = N::one() << (N::zero().count_zeros() as usize - 1);
+        let folded = if self.0 < half { self.0 } else { !self.0 };
+        Poui(folded << 1).to_signed()
+    }
+}
+
+/// A pulse wave generator, yielding whether the output is high each tick.
+///
+/// The phase comes from an [`Nco`] and is compared against the duty cycle
+/// with [`Poui::pulse`].
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Nco;
+/// use poui::Poui;
+/// use poui::PulseWave;
+///
+/// let wave = PulseWave::new(Nco::new(Poui(64u8)), Poui(128u8));
+/// let levels: Vec<_> = wave.take(8).collect();
+/// assert_eq!(levels, [true, true, false, false, true, true, false, false]);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PulseWave<N: PrimInt + WrappingAdd> {
+    nco: Nco<N>,
+    duty: Poui<N>,
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> PulseWave<N> {
+    /// A pulse wave with the duty cycle `duty`, driven by `nco`.
+    pub fn new(nco: Nco<N>, duty: Poui<N>) -> Self {
+        PulseWave { nco, duty }
+    }
+
+    /// The fraction of each cycle for which the output is high.
+    pub fn duty(&self) -> Poui<N> {
+        self.duty
+    }
+
+    /// Sets the fraction of each cycle for which the output is high.
+    pub fn set_duty(&mut self, duty: Poui<N>) {
+        self.duty = duty;
+    }
+
+    /// The phase accumulator driving the wave.
+    pub fn nco(&self) -> &Nco<N> {
+        &self.nco
+    }
+
+    /// The phase accumulator driving the wave, e.g. to retune it.
+    pub fn nco_mut(&mut self) -> &mut Nco<N> {
+        &mut self.nco
+    }
+
+    /// Returns the output at the current phase, and advances to the next
+    /// phase.
+    pub fn tick(&mut self) -> bool {
+        self.nco.tick().pulse(self.duty)
+    }
+}
+
+impl<N: PrimInt + Unsigned + WrappingAdd> Iterator for PulseWave<N> {
+    type Item = bool;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        Some(self.tick())
+    }
+}
+
+#[test]
+fn pulse_duty_fraction_is_exact() {
+    for duty in [0u16, 1, 0x4000, 0x8000, 0xABCD, u16::MAX] {
+        let high = (0..=u16::MAX)
+            .filter(|&n| Poui(n).pulse(Poui(duty)))
+            .count();
+        assert_eq!(high, duty as usize);
+    }
+}
+
+#[test]
+fn pulse_wave_pwm() {
+    let mut wave = PulseWave::new(Nco::<u32>::with_frequency(1, 100), Poui(0));
+    wave.set_duty(Poui(u32::MAX / 4 + 1));
+    let high = wave.by_ref().take(100).filter(|&level| level).count();
+    assert_eq!(high, 25);
+    assert_eq!(wave.duty(), Poui(1 << 30));
+    assert_eq!(wave.nco().tuning_word(), Poui(42949673));
+    wave.nco_mut().set_phase(Poui(0));
+    assert!(wave.tick());
+}
+
+#[test]
+fn waveshapers_are_monotonic_pieces() {
+    for n in 0..u16::MAX {
+        let (a, b) = (Poui(n), Poui(n + 1));
+        assert!(a.to_sawtooth() < b.to_sawtooth());
+        assert!(a.to_ramp_down() > b.to_ramp_down());
+        if n < 0x7FFF {
+            assert!(a.to_triangle() < b.to_triangle());
+        } else if n > 0x7FFF {
+            assert!(a.to_triangle() > b.to_triangle());
+        }
+    }
+}
+
+#[test]
+fn triangle_is_symmetric() {
+    for n in 1..=u32::MAX / 0x1_0001 {
+        let n = n * 0x1_0001;
+        assert_eq!(
+            Poui(n).to_triangle(),
+            Poui(n.wrapping_neg().wrapping_sub(1)).to_triangle()
+        );
+    }
+    assert_eq!(Poui(u64::MAX / 2).to_triangle(), Poui(i64::MAX - 1));
+    assert_eq!(Poui(1u128 << 126).to_triangle(), Poui(0i128));
+}
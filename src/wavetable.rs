@@ -0,0 +1,170 @@
+use crate::trig::turn_bits;
+use crate::Nco;
+use crate::Poui;
+use num_traits::AsPrimitive;
+use num_traits::PrimInt;
+use num_traits::Signed;
+use num_traits::Unsigned;
+use num_traits::WrappingAdd;
+
+/// How a [`Wavetable`] computes the wave between its samples.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SampleInterpolation {
+    /// A straight line between the two nearest samples.
+    #[default]
+    Linear,
+    /// A Catmull-Rom spline through the four nearest samples, which is
+    /// smoother, and more accurate for smooth waves, at the cost of a few
+    /// more multiplications. It can overshoot the samples slightly, and
+    /// saturates if that leaves the range of the samples.
+    Cubic,
+}
+
+impl SampleInterpolation {
+    /// The value a fraction `t`, in Q32, of the way from `p[1]` to `p[2]`,
+    /// where `p[0]` and `p[3]` are the samples either side of them.
+    pub(crate) fn interpolate(self, p: [i128; 4], t: i128) -> i128 {
+        let [p0, p1, p2, p3] = p;
+        match self {
+            SampleInterpolation::Linear => p1 + (((p2 - p1) * t + (1 << 31)) >> 32),
+            SampleInterpolation::Cubic => {
+                // Catmull-Rom in Horner form, with t in Q32, halved and
+                // rounded once at the end.
+                let c3 = 3 * (p1 - p2) + p3 - p0;
+                let c2 = 2 * p0 - 5 * p1 + 4 * p2 - p3;
+                let c1 = p2 - p0;
+                let v = (((((c3 * t) >> 32) + c2) * t) >> 32) + c1;
+                p1 + ((v * t + (1 << 32)) >> 33)
+            }
+        }
+    }
+}
+
+/// One cycle of a waveform, sampled into a table of signed `Poui`s, for
+/// playing back at any frequency.
+///
+/// The table is indexed by an unsigned `Poui` phase, a fraction of the
+/// cycle, which is typically the output of an [`Nco`]. The phase selects a
+/// position between two samples, and the fractional part of the position,
+/// to 32 bits, interpolates between them. The table wraps around, so the
+/// last sample blends back into the first, and it can have any length.
+///
+/// Samples can be at most 64 bits.
+///
+/// # Examples
+///
+/// ```rust
+/// use poui::Nco;
+/// use poui::Poui;
+/// use poui::SampleInterpolation;
+/// use poui::Wavetable;
+///
+/// let triangle = Wavetable::new(&[Poui(0i16), Poui(0x4000), Poui(0), Poui(-0x4000)]);
+/// assert_eq!(triangle.sample(Poui(0x2000u16)), Poui(0x2000));
+/// assert_eq!(triangle.sample(Poui(0xE000u16)), Poui(-0x2000));
+///
+/// let smooth = triangle.clone().with_interpolation(SampleInterpolation::Cubic);
+/// assert_eq!(smooth.sample(Poui(0x2000u16)), Poui(0x2800));
+///
+/// let mut nco = Nco::new(Poui(0x4000u16));
+/// let cycle: Vec<_> = (0..4).map(|_| triangle.tick(&mut nco)).collect();
+/// assert_eq!(cycle, [Poui(0), Poui(0x4000), Poui(0), Poui(-0x4000)]);
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Wavetable<S: PrimInt + Signed + WrappingAdd> {
+    samples: Vec<Poui<S>>,
+    interpolation: SampleInterpolation,
+}
+
+impl<S> Wavetable<S>
+where
+    S: PrimInt + Signed + WrappingAdd + Into<i64> + 'static,
+    i128: AsPrimitive<S>,
+{
+    /// A table of one cycle of a wave, with linear interpolation.
+    ///
+    /// # Panics
+    ///
+    /// Panics if there are no samples.
+    pub fn new(samples: &[Poui<S>]) -> Self {
+        assert!(!samples.is_empty(), "a wavetable needs samples");
+        Wavetable {
+            samples: samples.to_vec(),
+            interpolation: SampleInterpolation::Linear,
+        }
+    }
+
+    /// Sets how to interpolate between the samples.
+    pub fn with_interpolation(self, interpolation: SampleInterpolation) -> Self {
+        Wavetable {
+            interpolation,
+            ..self
+        }
+    }
+
+    /// How the table interpolates between its samples.
+    pub fn interpolation(&self) -> SampleInterpolation {
+        self.interpolation
+    }
+
+    /// The samples of the cycle.
+    pub fn samples(&self) -> &[Poui<S>] {
+        &self.samples
+    }
+
+    /// The value of the wave at `phase`, a fraction of the cycle.
+    pub fn sample<U: PrimInt + Unsigned + WrappingAdd>(&self, phase: Poui<U>) -> Poui<S> {
+        let len = self.samples.len();
+        let position = turn_bits(phase.0) as u128 * len as u128;
+        let index = (position >> 64) as usize;
+        let t = (position as u64 >> 32) as i128;
+        let at = |offset: usize| -> i128 {
+            let v: i64 = self.samples[(index + offset) % len].0.into();
+            v as i128
+        };
+        let value = self
+            .interpolation
+            .interpolate([at(len - 1), at(0), at(1), at(2)], t);
+        let (min, max) = (S::min_value().into(), S::max_value().into());
+        Poui(value.clamp(min as i128, max as i128).as_())
+    }
+
+    /// Samples the wave at the phase of `nco`, and advances it.
+    pub fn tick<U: PrimInt + Unsigned + WrappingAdd>(&self, nco: &mut Nco<U>) -> Poui<S> {
+        self.sample(nco.tick())
+    }
+}
+
+#[test]
+fn wavetable_passes_through_samples() {
+    let samples: Vec<_> = (0..12i32)
+        .map(|i| Poui(i.wrapping_mul(0x1234_5678).wrapping_mul(7)))
+        .collect();
+    for interpolation in [SampleInterpolation::Linear, SampleInterpolation::Cubic] {
+        let table = Wavetable::new(&samples).with_interpolation(interpolation);
+        for (i, &s) in samples.iter().enumerate() {
+            // The phase of sample i, rounded up so that it isn't just short.
+            let phase = Poui(((i as u128) << 64).div_ceil(12) as u64);
+            assert_eq!(table.sample(phase), s, "{interpolation:?} {i}");
+        }
+    }
+}
+
+#[test]
+fn cubic_sine_is_more_accurate_than_linear() {
+    let sine: Vec<_> = (0..32u16).map(|i| Poui(i << 11).sin()).collect();
+    let linear = Wavetable::new(&sine);
+    let cubic = linear
+        .clone()
+        .with_interpolation(SampleInterpolation::Cubic);
+    let error = |table: &Wavetable<i16>| {
+        (0..=u16::MAX)
+            .step_by(97)
+            .map(|n| (table.sample(Poui(n)).0 as i32 - Poui(n).sin().0 as i32).abs())
+            .max()
+            .unwrap()
+    };
+    let (linear, cubic) = (error(&linear), error(&cubic));
+    assert!(linear > 100 && linear < 170, "{linear}");
+    assert!(cubic < 20, "{cubic}");
+}